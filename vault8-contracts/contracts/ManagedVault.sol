@@ -175,8 +175,8 @@ contract ManagedVault is ERC4626, Ownable {
     // Solana bridge helpers
     // ---------------------------------------------------------------------
 
-    /// @notice Trigger the CCTP bridge step on a Solana strategy.
-    function initiateBridge(address strategy, uint256 amount) external onlyOwner returns (uint64 nonce) {
+    /// @notice Trigger the CCTP bridge step on a Solana strategy; returns the strategy's bridge id.
+    function initiateBridge(address strategy, uint256 amount) external onlyOwner returns (uint64 bridgeId) {
         require(_isAllowedStrategy[strategy], "ManagedVault: strategy not allowed");
         require(amount > 0, "ManagedVault: zero amount");
 
@@ -189,7 +189,7 @@ contract ManagedVault is ERC4626, Ownable {
         require(amount <= vaultToken.balanceOf(address(this)), "Not enough free assets");
 
         SafeERC20.forceApprove(vaultToken, strategy, amount);
-        nonce = ISolanaBridgeStrategy(strategy).bridge(amount);
+        bridgeId = ISolanaBridgeStrategy(strategy).bridge(amount);
     }
 
     /// @notice Update LayerZero execution options on a Solana strategy.
@@ -17,9 +17,26 @@ struct MessagingReceipt {
 }
 
 interface IMyOAppBridge {
-    function depositViaCCTP(uint256 amount) external returns (uint64);
-    function requestDeposit(uint32 dstEid, bytes calldata options) external payable returns (MessagingReceipt memory);
-    function getPendingDeposit(address user) external view returns (uint256 amount, uint64 nonce);
+    function depositViaCCTP(uint256 amount) external;
+    function requestDeposit(
+        uint32 dstEid,
+        address vault,
+        bytes calldata options
+    ) external payable returns (MessagingReceipt memory);
+    function getPendingDeposit(address user) external view returns (uint256 amount, bytes32 nonce);
+    function requestWithdraw(
+        uint32 dstEid,
+        uint64 amount,
+        address vault,
+        bytes calldata options
+    ) external payable returns (MessagingReceipt memory);
+    function quoteWithdraw(
+        uint32 dstEid,
+        uint64 amount,
+        address vault,
+        bytes calldata options,
+        bool payInLzToken
+    ) external view returns (MessagingFee memory);
 }
 
 /**
@@ -27,7 +44,11 @@ interface IMyOAppBridge {
  * @notice Adapter that orchestrates bridging to Solana via MyOApp (CCTP + LayerZero).
  * @dev Requires two explicit calls from the vault owner:
  *      1. `bridge(amount)` – burns USDC via Circle CCTP and records the net amount.
- *      2. `deposit(amount)` – finalizes the LayerZero leg, requiring the bridge record.
+ *      2. `deposit(amount)` – finalizes the LayerZero leg, requiring the bridge record and the
+ *         CCTP nonce the relayer records on MyOApp once the burn is attested.
+ *      `withdraw(amount, vault)` sends the LayerZero withdraw request; Solana burns the USDC back
+ *      to the vault via CCTP, so it lands asynchronously. The LayerZero fee is paid from the ETH
+ *      this adapter holds.
  */
 contract StrategyAdapterSolana is IStrategy {
     using SafeERC20 for IERC20;
@@ -42,10 +63,20 @@ contract StrategyAdapterSolana is IStrategy {
     bytes public lzOptions;
 
     mapping(bytes32 => uint256) public bridged;
+    // CCTP V2 nonces are only known once attested, so bridges are keyed by a local id
+    uint64 public bridgeCount;
+    uint64 public pendingBridgeId;
 
     event VaultBound(address indexed vault);
-    event BridgeInitiated(address indexed vault, uint64 indexed nonce, uint256 grossAmount, uint256 netAmount);
-    event DepositRequested(address indexed vault, uint64 indexed nonce, bytes32 guid, uint256 amount);
+    event BridgeInitiated(address indexed vault, uint64 indexed bridgeId, uint256 grossAmount, uint256 netAmount);
+    event DepositRequested(
+        address indexed vault,
+        uint64 indexed bridgeId,
+        bytes32 cctpNonce,
+        bytes32 guid,
+        uint256 amount
+    );
+    event WithdrawRequested(address indexed vault, bytes32 guid, uint256 amount);
     event LayerZeroOptionsUpdated(bytes options);
 
     error NotVault();
@@ -53,7 +84,9 @@ contract StrategyAdapterSolana is IStrategy {
     error Sealed();
     error PendingBridge();
     error BridgeMissing();
+    error NonceNotRecorded();
     error AmountMismatch();
+    error InsufficientFee();
 
     constructor(IERC20 asset_, IMyOAppBridge myOApp_, uint32 dstEid_, bytes memory options_) {
         if (address(asset_) == address(0) || address(myOApp_) == address(0)) revert ZeroAddress();
@@ -81,10 +114,10 @@ contract StrategyAdapterSolana is IStrategy {
     }
 
     /**
-     * @notice Initiate the CCTP burn and record the bridged amount keyed by `vault + nonce`.
+     * @notice Initiate the CCTP burn and record the bridged amount keyed by `vault + bridgeId`.
      * @dev Expects `vault` to have approved this adapter for `amount`.
      */
-    function bridge(uint256 amount) external returns (uint64 nonce) {
+    function bridge(uint256 amount) external returns (uint64 bridgeId) {
         if (msg.sender != vault) revert NotVault();
         if (amount == 0) revert AmountMismatch();
 
@@ -94,20 +127,20 @@ contract StrategyAdapterSolana is IStrategy {
         asset.safeTransferFrom(vault, address(this), amount);
         SafeERC20.forceApprove(asset, address(myOApp), amount);
 
-        nonce = myOApp.depositViaCCTP(amount);
+        myOApp.depositViaCCTP(amount);
 
         SafeERC20.forceApprove(asset, address(myOApp), 0);
 
         (uint256 mintedAmount, ) = myOApp.getPendingDeposit(address(this));
 
         _mintedAmount += mintedAmount;
-        bytes32 key = _bridgeKey(vault, nonce);
-        if (bridged[key] != 0) revert PendingBridge();
-        bridged[key] = mintedAmount;
+        bridgeId = ++bridgeCount;
+        pendingBridgeId = bridgeId;
+        bridged[_bridgeKey(vault, bridgeId)] = mintedAmount;
 
-        emit BridgeInitiated(vault, nonce, amount, mintedAmount);
+        emit BridgeInitiated(vault, bridgeId, amount, mintedAmount);
 
-        return nonce;
+        return bridgeId;
     }
 
     /**
@@ -118,27 +151,46 @@ contract StrategyAdapterSolana is IStrategy {
         if (msg.sender != vault) revert NotVault();
         if (amount == 0) revert AmountMismatch();
 
-        (uint256 pendingAmount, uint64 nonce) = myOApp.getPendingDeposit(address(this));
+        (uint256 pendingAmount, bytes32 cctpNonce) = myOApp.getPendingDeposit(address(this));
         if (pendingAmount == 0) revert BridgeMissing();
+        if (cctpNonce == bytes32(0)) revert NonceNotRecorded();
 
-        bytes32 key = _bridgeKey(vault, nonce);
+        uint64 bridgeId = pendingBridgeId;
+        bytes32 key = _bridgeKey(vault, bridgeId);
         uint256 recorded = bridged[key];
         if (recorded == 0) revert BridgeMissing();
         if (pendingAmount != recorded || pendingAmount != amount) revert AmountMismatch();
 
-        MessagingReceipt memory receipt = myOApp.requestDeposit{value: msg.value}(dstEid, lzOptions);
+        // Credit the vault's own position on Solana
+        MessagingReceipt memory receipt = myOApp.requestDeposit{value: msg.value}(dstEid, vault, lzOptions);
 
         delete bridged[key];
+        pendingBridgeId = 0;
 
-        emit DepositRequested(vault, nonce, receipt.guid, amount);
+        emit DepositRequested(vault, bridgeId, cctpNonce, receipt.guid, amount);
     }
 
+    /**
+     * @notice Request `amount` USDC back from Solana; it is minted to the vault via CCTP.
+     * @dev Debits the vault's Solana position; the LayerZero fee comes from this adapter's ETH.
+     */
     function withdraw(uint256 amount, address senderVault) external override {
-        if (msg.sender != senderVault) revert NotVault();
-        if (amount == 0) revert AmountMismatch();
+        if (msg.sender != vault || senderVault != vault) revert NotVault();
+        if (amount == 0 || amount > _mintedAmount || amount > type(uint64).max) revert AmountMismatch();
+
+        MessagingFee memory fee = myOApp.quoteWithdraw(dstEid, uint64(amount), vault, lzOptions, false);
+        if (address(this).balance < fee.nativeFee) revert InsufficientFee();
+
         _mintedAmount -= amount;
+        MessagingReceipt memory receipt =
+            myOApp.requestWithdraw{value: fee.nativeFee}(dstEid, uint64(amount), vault, lzOptions);
+
+        emit WithdrawRequested(vault, receipt.guid, amount);
     }
 
+    /// @notice Funds the LayerZero fees of withdraw requests (and receives MyOApp refunds).
+    receive() external payable {}
+
     /**
      * @notice Reports the currently pending bridged amount (net minted).
      */
@@ -146,14 +198,17 @@ contract StrategyAdapterSolana is IStrategy {
         return _mintedAmount;
     }
 
-    function pendingBridge() external view returns (uint256 amount, uint64 nonce, bytes32 key) {
-        (amount, nonce) = myOApp.getPendingDeposit(address(this));
+    /**
+     * @notice Reports the pending bridge; `cctpNonce` stays zero until the relayer records it.
+     */
+    function pendingBridge() external view returns (uint256 amount, bytes32 cctpNonce, bytes32 key) {
+        (amount, cctpNonce) = myOApp.getPendingDeposit(address(this));
         if (amount != 0) {
-            key = _bridgeKey(vault, nonce);
+            key = _bridgeKey(vault, pendingBridgeId);
         }
     }
 
-    function _bridgeKey(address vaultAddress, uint64 nonce) private pure returns (bytes32) {
-        return keccak256(abi.encodePacked(vaultAddress, nonce));
+    function _bridgeKey(address vaultAddress, uint64 bridgeId) private pure returns (bytes32) {
+        return keccak256(abi.encodePacked(vaultAddress, bridgeId));
     }
 }
@@ -3,11 +3,11 @@ const { ethers } = require("hardhat");
 const delay = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
 
 const quoteInterface = new ethers.Interface([
-  "function quoteDeposit(uint32 _dstEid, bytes _options, bool _payInLzToken) view returns (uint256 nativeFee, uint256 lzTokenFee)",
+  "function quoteDeposit(uint32 _dstEid, address _vault, bytes _options, bool _payInLzToken) view returns (uint256 nativeFee, uint256 lzTokenFee)",
 ]);
 
-async function quoteNativeFee(provider, myOApp, adapterAddress, dstEid, options) {
-  const data = quoteInterface.encodeFunctionData("quoteDeposit", [dstEid, options, false]);
+async function quoteNativeFee(provider, myOApp, adapterAddress, vaultAddress, dstEid, options) {
+  const data = quoteInterface.encodeFunctionData("quoteDeposit", [dstEid, vaultAddress, options, false]);
   const result = await provider.call({ to: myOApp, data, from: adapterAddress });
   const [nativeFee] = quoteInterface.decodeFunctionResult("quoteDeposit", result);
   return nativeFee;
@@ -44,11 +44,11 @@ async function main() {
   const existingPending = await solanaAdapter.pendingBridge();
   if (existingPending.amount !== 0n) {
     console.log(
-      `⚠️  Existing pending bridge detected (amount=${ethers.formatUnits(existingPending.amount, 6)} USDC, nonce=${existingPending.nonce}). Finalizing first...`
+      `⚠️  Existing pending bridge detected (amount=${ethers.formatUnits(existingPending.amount, 6)} USDC, cctpNonce=${existingPending.cctpNonce}). Finalizing first...`
     );
     const dstEid = await solanaAdapter.dstEid();
     const options = await solanaAdapter.lzOptions();
-    const nativeFee = await quoteNativeFee(owner.provider, myOAppAddress, solanaAdapterAddress, dstEid, options);
+    const nativeFee = await quoteNativeFee(owner.provider, myOAppAddress, solanaAdapterAddress, vaultAddress, dstEid, options);
     console.log(`➡️  Finalizing pending bridge, fee=${ethers.formatEther(nativeFee)} ETH`);
     const finalizeTx = await vault.allocate(existingPending.amount, solanaAdapterAddress, { value: nativeFee });
     const finalizeRc = await finalizeTx.wait();
@@ -107,9 +107,9 @@ async function main() {
 
   const pending = await solanaAdapter.pendingBridge();
   const pendingAmount = pending.amount;
-  const pendingNonce = pending.nonce;
+  const pendingNonce = pending.cctpNonce;
   console.log(
-    `📥 Pending bridge recorded: amount=${ethers.formatUnits(pendingAmount, 6)} USDC, nonce=${pendingNonce}`
+    `📥 Pending bridge recorded: amount=${ethers.formatUnits(pendingAmount, 6)} USDC, cctpNonce=${pendingNonce}`
   );
 
   await delay(delayMs);
@@ -118,10 +118,21 @@ async function main() {
     throw new Error("Bridge did not record any pending amount; aborting finalization.");
   }
 
+  // The attestation bot records the CCTP nonce once Circle has attested the burn
+  let cctpNonce = pendingNonce;
+  for (let attempt = 0; cctpNonce === ethers.ZeroHash && attempt < 120; attempt++) {
+    await delay(5000);
+    cctpNonce = (await solanaAdapter.pendingBridge()).cctpNonce;
+  }
+  if (cctpNonce === ethers.ZeroHash) {
+    throw new Error("CCTP nonce was not recorded by the relayer; aborting finalization.");
+  }
+  console.log(`🔖 CCTP nonce recorded: ${cctpNonce}`);
+
   // Step 3: Quote LayerZero fee and finalize allocation
   const dstEid = await solanaAdapter.dstEid();
   const options = await solanaAdapter.lzOptions();
-  const nativeFee = await quoteNativeFee(owner.provider, myOAppAddress, solanaAdapterAddress, dstEid, options);
+  const nativeFee = await quoteNativeFee(owner.provider, myOAppAddress, solanaAdapterAddress, vaultAddress, dstEid, options);
   console.log(`💰 Quoted native fee: ${ethers.formatEther(nativeFee)} ETH`);
 
   console.log("➡️  Finalizing bridge via ManagedVault.allocate...");
@@ -1,7 +1,7 @@
 const { ethers } = require("hardhat");
 
 const quoteInterface = new ethers.Interface([
-  "function quoteDeposit(uint32 _dstEid, bytes _options, bool _payInLzToken) view returns (uint256 nativeFee, uint256 lzTokenFee)",
+  "function quoteDeposit(uint32 _dstEid, address _vault, bytes _options, bool _payInLzToken) view returns (uint256 nativeFee, uint256 lzTokenFee)",
 ]);
 
 async function main() {
@@ -24,7 +24,7 @@ async function main() {
 
   const pending = await solanaAdapter.pendingBridge();
   const amount = pending.amount;
-  const nonce = pending.nonce;
+  const cctpNonce = pending.cctpNonce;
 
   if (amount === 0n) {
     console.log("No pending Solana bridge detected. Nothing to finalize.");
@@ -32,14 +32,19 @@ async function main() {
   }
 
   console.log(
-    `Pending bridge found: amount=${ethers.formatUnits(amount, 6)} USDC, nonce=${nonce}, key=${pending.key}`
+    `Pending bridge found: amount=${ethers.formatUnits(amount, 6)} USDC, cctpNonce=${cctpNonce}, key=${pending.key}`
   );
 
+  if (cctpNonce === ethers.ZeroHash) {
+    console.log("CCTP nonce not recorded yet; wait for the attestation bot to record it.");
+    return;
+  }
+
   const dstEid = await solanaAdapter.dstEid();
   const options = await solanaAdapter.lzOptions();
 
   console.log("Quoting LayerZero fee...");
-  const quoteData = quoteInterface.encodeFunctionData("quoteDeposit", [dstEid, options, false]);
+  const quoteData = quoteInterface.encodeFunctionData("quoteDeposit", [dstEid, vaultAddress, options, false]);
   const quoteResult = await owner.provider.call({
     to: myOAppAddress,
     data: quoteData,
@@ -11,6 +11,7 @@ const MyOAppABI = [
   {
     "inputs": [
       { "internalType": "uint32", "name": "_dstEid", "type": "uint32" },
+      { "internalType": "address", "name": "_vault", "type": "address" },
       { "internalType": "bytes", "name": "_options", "type": "bytes" },
       { "internalType": "bool", "name": "_payInLzToken", "type": "bool" }
     ],
@@ -46,6 +47,7 @@ export function useQuoteLayerZeroFee(userAddress?: Address, enabled: boolean = t
     functionName: "quoteDeposit",
     args: [
       TARGET_PROTOCOLS.SOLANA_DST_EID,
+      userAddress as Address, // credited vault
       TARGET_PROTOCOLS.DEFAULT_LZ_OPTIONS as `0x${string}`,
       false, // _payInLzToken
     ],
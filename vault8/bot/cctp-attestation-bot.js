@@ -9,9 +9,12 @@
  * 2. For each event: fetch attestation from Circle Iris API
  * 3. Submit attestation to Solana MessageTransmitterV2
  * 4. Confirm USDC minted to Store's USDC ATA
+ * 5. Record the attested CCTP nonce and minted amount on MyOApp (recordCctpNonce), so
+ *    requestDeposit can bind the LayerZero deposit to this mint. The relayer key must be MyOApp's
+ *    `cctpRelayer`.
  * 
  * Usage:
- *   MYOAPP_ADDRESS=0x... SOLANA_PAYER_KEYPAIR=~/.config/solana/id.json node bot/cctp-attestation-bot.js
+ *   MYOAPP_ADDRESS=0x... RELAYER_PRIVATE_KEY=0x... SOLANA_PAYER_KEYPAIR=~/.config/solana/id.json node bot/cctp-attestation-bot.js
  */
 
 const { ethers } = require("ethers");
@@ -24,6 +27,7 @@ const fs = require("fs");
 const BASE_SEPOLIA_RPC = process.env.BASE_SEPOLIA_RPC || "https://sepolia.base.org";
 const SOLANA_RPC = process.env.SOLANA_RPC || "https://api.devnet.solana.com";
 const MYOAPP_ADDRESS = process.env.MYOAPP_ADDRESS;
+const RELAYER_PRIVATE_KEY = process.env.RELAYER_PRIVATE_KEY;
 const SOLANA_PAYER_KEYPAIR_PATH = process.env.SOLANA_PAYER_KEYPAIR || "./bot/bot-keypair.json";
 
 // CCTP Configuration
@@ -72,6 +76,9 @@ async function init() {
     if (!MYOAPP_ADDRESS) {
         throw new Error("❌ MYOAPP_ADDRESS environment variable required");
     }
+    if (!RELAYER_PRIVATE_KEY) {
+        throw new Error("❌ RELAYER_PRIVATE_KEY environment variable required");
+    }
 
     // Load Solana payer keypair
     const keypairPath = SOLANA_PAYER_KEYPAIR_PATH.replace("~", process.env.HOME);
@@ -84,6 +91,8 @@ async function init() {
 
     // Initialize providers
     const provider = new ethers.providers.JsonRpcProvider(BASE_SEPOLIA_RPC);
+    const relayer = new ethers.Wallet(RELAYER_PRIVATE_KEY, provider);
+    console.log("🔑 Bot Base relayer address:", relayer.address);
     const connection = new Connection(SOLANA_RPC, "confirmed");
 
     // Check Solana balance
@@ -99,7 +108,7 @@ async function init() {
     console.log("\n✅ Bot initialized successfully");
     console.log("👀 Watching for CCTP deposits...\n");
 
-    return { provider, connection, payer };
+    return { provider, relayer, connection, payer };
 }
 
 // ==================== Attestation Fetching ====================
@@ -292,10 +301,9 @@ async function submitAttestationToSolana(attestationData, connection, payer) {
   
 // ==================== Event Handler ====================
 
-async function handleCctpDeposit(event, provider, connection, payer) {
-    const { user, depositAmount, mintedAmount, fee, cctpNonce, destinationDomain } = event.args;
+async function handleCctpDeposit(event, myOApp, connection, payer) {
+    const { user, depositAmount, mintedAmount, fee } = event.args;
     const txHash = event.transactionHash;
-    const nonceStr = cctpNonce.toString();
     
     console.log("\n" + "═".repeat(60));
     console.log("🔔 New CCTP Deposit Detected");
@@ -304,7 +312,6 @@ async function handleCctpDeposit(event, provider, connection, payer) {
     console.log("Deposit Amount:", ethers.utils.formatUnits(depositAmount, 6), "USDC");
     console.log("CCTP Fee:", ethers.utils.formatUnits(fee, 6), "USDC");
     console.log("Minted Amount:", ethers.utils.formatUnits(mintedAmount, 6), "USDC");
-    console.log("CCTP Nonce:", nonceStr);
     console.log("TX Hash:", txHash);
     console.log("═".repeat(60));
     
@@ -316,17 +323,25 @@ async function handleCctpDeposit(event, provider, connection, payer) {
     
     try {
         // Step 1: Fetch attestation
-        console.log("⏳ Step 1/2: Fetching attestation from Circle Iris API...");
+        console.log("⏳ Step 1/3: Fetching attestation from Circle Iris API...");
         const attestationData = await fetchAttestation(txHash);
+        // CCTP V2 nonce: bytes32 at message bytes 12..44, assigned by the attester
+        const cctpNonce = "0x" + attestationData.message.slice(2).slice(24, 88);
+        const attestedMint = attestedMintAmount(attestationData.message);
         console.log("✅ Attestation received");
+        console.log("   CCTP Nonce:", cctpNonce);
+        console.log("   Attested Mint:", ethers.utils.formatUnits(attestedMint, 6), "USDC");
         
         // Step 2: Submit to Solana
-        console.log("⏳ Step 2/2: Submitting attestation to Solana...");
+        console.log("⏳ Step 2/3: Submitting attestation to Solana...");
         const signature = await submitAttestationToSolana(attestationData, connection, payer);
         console.log("✅ USDC minted on Solana!");
         console.log("   Transaction:", signature);
         console.log("   Explorer: https://explorer.solana.com/tx/" + signature + "?cluster=devnet");
         
+        // Step 3: Record the nonce so the user's requestDeposit carries it
+        await recordCctpNonce(myOApp, user, cctpNonce, attestedMint);
+        
         // Mark as processed by transaction hash
         processedTxs.add(txHash);
         saveState();
@@ -340,27 +355,63 @@ async function handleCctpDeposit(event, provider, connection, payer) {
         
         // Check if it's a nonce reuse error (already processed by someone else)
         if (error.logs && error.logs.some(log => log.includes('already in use'))) {
-            console.log("ℹ️  Transaction already claimed - marking as processed");
-            processedTxs.add(txHash);
-            saveState();
+            console.log("ℹ️  Transaction already claimed - recording nonce and marking as processed");
+            try {
+                const attestationData = await fetchAttestation(txHash);
+                await recordCctpNonce(
+                    myOApp,
+                    user,
+                    "0x" + attestationData.message.slice(2).slice(24, 88),
+                    attestedMintAmount(attestationData.message)
+                );
+                processedTxs.add(txHash);
+                saveState();
+            } catch (recordError) {
+                console.error("   Failed to record CCTP nonce:", recordError.message);
+            }
         } else {
             console.error("   This deposit will be retried on bot restart\n");
         }
     }
 }
 
+// ==================== Nonce Recording ====================
+
+// CCTP V2 burn message: a 148-byte header, then the burn body with amount at body bytes 68..100
+// and feeExecuted at 164..196. The attested message mints amount - feeExecuted.
+function attestedMintAmount(messageHex) {
+    const message = messageHex.slice(2);
+    const word = (offset) =>
+        ethers.BigNumber.from("0x" + message.slice((148 + offset) * 2, (148 + offset + 32) * 2));
+    return word(68).sub(word(164));
+}
+
+async function recordCctpNonce(myOApp, user, cctpNonce, mintedAmount) {
+    const recorded = await myOApp.cctpNonces(user);
+    if (recorded === cctpNonce) {
+        console.log("ℹ️  CCTP nonce already recorded");
+        return;
+    }
+    console.log("⏳ Recording CCTP nonce on MyOApp...");
+    const tx = await myOApp.recordCctpNonce(user, cctpNonce, mintedAmount);
+    await tx.wait();
+    console.log("✅ CCTP nonce recorded:", tx.hash);
+}
+
 // ==================== Main Event Loop ====================
 
 async function main() {
-    const { provider, connection, payer } = await init();
+    const { provider, relayer, connection, payer } = await init();
     
     // Get MyOApp contract
     const myOApp = new ethers.Contract(
         MYOAPP_ADDRESS,
         [
-            "event CctpDepositInitiated(address indexed user, uint256 depositAmount, uint256 mintedAmount, uint256 fee, uint64 indexed cctpNonce, uint32 destinationDomain)"
+            "event CctpDepositInitiated(address indexed user, uint256 depositAmount, uint256 mintedAmount, uint256 fee, uint32 destinationDomain)",
+            "function cctpNonces(address user) view returns (bytes32)",
+            "function recordCctpNonce(address _user, bytes32 _nonce, uint256 _mintedAmount)"
         ],
-        provider
+        relayer
     );
     
     // Get current block to start watching from
@@ -369,7 +420,7 @@ async function main() {
     console.log("⏰ " + new Date().toISOString() + "\n");
     
     // Watch for new events
-    myOApp.on("CctpDepositInitiated", async (user, depositAmount, mintedAmount, fee, cctpNonce, destinationDomain, event) => {
+    myOApp.on("CctpDepositInitiated", async (user, depositAmount, mintedAmount, fee, destinationDomain, event) => {
         // Only process deposits to Solana (domain 5)
         const domain = destinationDomain.toNumber ? destinationDomain.toNumber() : destinationDomain;
         if (domain !== 5) {
@@ -377,7 +428,7 @@ async function main() {
             return;
         }
         
-        await handleCctpDeposit(event, provider, connection, payer);
+        await handleCctpDeposit(event, myOApp, connection, payer);
     });
     
     console.log("✅ Bot is running! Press Ctrl+C to stop.\n");
@@ -11,6 +11,12 @@ fi
 
 export MYOAPP_ADDRESS=$1
 
+# Base key of MyOApp's cctpRelayer, used to record attested CCTP nonces
+if [ -z "$RELAYER_PRIVATE_KEY" ]; then
+    echo "❌ Error: RELAYER_PRIVATE_KEY required (MyOApp cctpRelayer key)"
+    exit 1
+fi
+
 # Check for bot keypair
 BOT_KEYPAIR_PATH="./bot/bot-keypair.json"
 
@@ -8,6 +8,7 @@ import { OApp, MessagingFee, Origin } from "@layerzerolabs/oapp-evm/contracts/oa
 import { MessagingReceipt } from "@layerzerolabs/oapp-evm/contracts/oapp/OAppSender.sol";
 import { OAppOptionsType3 } from "@layerzerolabs/oapp-evm/contracts/oapp/libs/OAppOptionsType3.sol";
 import { CounterMsgCodec } from "./libs/CounterMsgCodec.sol";
+import { VaultMsgCodec } from "./libs/VaultMsgCodec.sol";
 
 /**
  * @title CCTP V2 Token Messenger Interface
@@ -24,7 +25,7 @@ interface ICCTPTokenMessengerV2 {
      * @param destinationCaller Address that can call receiveMessage on destination (bytes32(0) = anyone)
      * @param maxFee Maximum fee paid for fast burn in units of burnToken
      * @param minFinalityThreshold Minimum finality threshold (1000 = Fast Transfer, 2000 = Standard)
-     * @return nonce Unique nonce reserved by message
+     * @dev V2 returns nothing: the bytes32 message nonce is assigned by the attester off-chain
      */
     function depositForBurn(
         uint256 amount,
@@ -34,7 +35,7 @@ interface ICCTPTokenMessengerV2 {
         bytes32 destinationCaller,
         uint256 maxFee,
         uint32 minFinalityThreshold
-    ) external returns (uint64 nonce);
+    ) external;
 }
 
 contract MyOApp is OApp, OAppOptionsType3 {
@@ -51,6 +52,11 @@ contract MyOApp is OApp, OAppOptionsType3 {
     // Correlate outgoing message GUID -> original sender to credit on ACK
     mapping(bytes32 => address) public pendingSender;
 
+    // Latest NAV reported by the Solana vault (`report_nav`); older reports are ignored
+    uint64 public solanaNav;
+    int64 public solanaNavTimestamp;
+    uint64 public solanaNavSlot;
+
     // ==================== CCTP V2 Configuration ====================
     
     /// @notice Circle's CCTP V2 TokenMessenger contract on Base Sepolia
@@ -65,6 +71,10 @@ contract MyOApp is OApp, OAppOptionsType3 {
     /// @notice Solana domain identifier for CCTP V2
     /// @dev Solana domain: 5
     uint32 public constant SOLANA_DOMAIN = 5;
+
+    /// @notice CCTP V2 domain deposits are burned on (Base / Base Sepolia: 6)
+    /// @dev Sent with the nonce so Solana can check the mint's source domain
+    uint32 public constant SOURCE_DOMAIN = 6;
     
     /// @notice Fast Transfer finality threshold for CCTP V2
     /// @dev 1000 = Confirmed (~10-30 seconds), 2000 = Finalized (~13-19 minutes)
@@ -88,8 +98,16 @@ contract MyOApp is OApp, OAppOptionsType3 {
     mapping(address => uint256) public cctpDeposits;
     
     /// @notice Track CCTP nonces for each user's deposit
-    /// @dev Used for bot to correlate CCTP attestation with user
-    mapping(address => uint64) public cctpNonces;
+    /// @dev CCTP V2 nonces are bytes32 values assigned by the attester, so the relayer records the
+    /// @dev nonce from the attested message; requestDeposit binds the deposit to it on Solana
+    mapping(address => bytes32) public cctpNonces;
+
+    /// @notice Relayer allowed to record attested CCTP nonces (the attestation bot)
+    address public cctpRelayer;
+
+    /// @notice Vault each user's Solana balance is held for, bound by their first deposit or withdrawal
+    /// @dev Solana keeps a user's shares in one vault position and rejects messages naming another vault
+    mapping(address => address) public userVault;
     
     // ==================== Events ====================
     
@@ -98,29 +116,78 @@ contract MyOApp is OApp, OAppOptionsType3 {
     /// @param depositAmount Amount of USDC deposited by user (before fees)
     /// @param mintedAmount Amount of USDC that will be minted on Solana (after fees)
     /// @param fee CCTP Fast Transfer fee charged
-    /// @param cctpNonce CCTP nonce for attestation tracking
     /// @param destinationDomain CCTP destination domain (Solana = 5)
     event CctpDepositInitiated(
         address indexed user,
         uint256 depositAmount,
         uint256 mintedAmount,
         uint256 fee,
-        uint64 indexed cctpNonce,
         uint32 destinationDomain
     );
+
+    /// @notice Emitted when the relayer records the attested nonce of a user's CCTP deposit
+    /// @param user Address of the depositor
+    /// @param cctpNonce CCTP V2 nonce from the attested message
+    event CctpNonceRecorded(address indexed user, bytes32 indexed cctpNonce);
+
+    /// @notice Emitted when the owner changes the CCTP relayer
+    /// @param relayer New relayer address
+    event CctpRelayerSet(address indexed relayer);
     
     /// @notice Emitted when user finalizes deposit by sending LayerZero message
     /// @param user Address of the depositor
     /// @param amount Amount sent via LayerZero
     /// @param dstEid LayerZero destination endpoint ID
     /// @param guid LayerZero message GUID
+    /// @param vault Vault whose Solana position is credited
     event DepositFinalized(
         address indexed user,
         uint256 amount,
         uint32 indexed dstEid,
-        bytes32 indexed guid
+        bytes32 indexed guid,
+        address vault
     );
 
+    /// @notice Emitted when a withdrawal request is sent to Solana
+    /// @param user Address whose Solana balance is debited
+    /// @param amount USDC amount to withdraw
+    /// @param dstEid LayerZero destination endpoint ID
+    /// @param guid LayerZero message GUID
+    /// @param vault Vault whose Solana position is debited and that receives the USDC on Base
+    event WithdrawRequested(
+        address indexed user,
+        uint64 amount,
+        uint32 indexed dstEid,
+        bytes32 indexed guid,
+        address vault
+    );
+
+    /// @notice Emitted when Solana reports a withdrawal as paid out
+    /// @param user EVM address the withdrawal was for
+    /// @param amount USDC amount paid out
+    /// @param guid GUID of the withdraw message
+    event WithdrawCompleted(address indexed user, uint64 amount, bytes32 indexed guid);
+
+    /// @notice Emitted when Solana sends a user's balance snapshot
+    /// @param user EVM address of the user
+    /// @param shares Vault shares held on Solana
+    /// @param totalDeposited Cumulative deposits
+    /// @param totalWithdrawn Cumulative withdrawals
+    /// @param timestamp Solana timestamp of the user's last activity
+    event BalanceSnapshot(
+        address indexed user,
+        uint64 shares,
+        uint64 totalDeposited,
+        uint64 totalWithdrawn,
+        int64 timestamp
+    );
+
+    /// @notice Emitted when Solana reports the vault's NAV
+    /// @param value USDC value of the Solana vault (idle buffer and strategies)
+    /// @param timestamp Solana timestamp the value was read at
+    /// @param slot Solana slot the value was read at
+    event NavReported(uint64 value, int64 timestamp, uint64 slot);
+
     // Note: OApp inherits ownership; pass through Ownable initializer via the most-derived constructor
     constructor(
         address _endpoint,
@@ -156,27 +223,18 @@ contract MyOApp is OApp, OAppOptionsType3 {
         return _quote(_dstEid, payload, options, _payInLzToken);
     }
 
-    // Build 8-byte little-endian payload from uint64
-    function _toLeBytes8(uint64 x) internal pure returns (bytes memory out) {
-        out = new bytes(8);
-        uint64 v = x;
-        for (uint256 i = 0; i < 8; i++) {
-            out[i] = bytes1(uint8(v & 0xFF));
-            v >>= 8;
-        }
-    }
-
     // ==================== CCTP Functions ====================
     
     /**
      * @notice Complete CCTP deposit flow in one transaction
      * @dev Combines: transferFrom → approve → depositForBurn
      * @dev Calculates actual minted amount after CCTP Fast Transfer fee (1 bps = 0.01%)
+     * @dev The CCTP nonce is only known once attested; the relayer records it with recordCctpNonce
      * @param _amount Amount of USDC to bridge to Solana (before fees)
-     * @return nonce CCTP nonce for tracking the cross-chain message
      */
-    function depositViaCCTP(uint256 _amount) external returns (uint64) {
+    function depositViaCCTP(uint256 _amount) external {
         require(_amount > 0, "Amount must be nonzero");
+        require(cctpDeposits[msg.sender] == 0, "Pending CCTP deposit. Call requestDeposit first.");
         
         // Calculate CCTP Fast Transfer fee (1 bps = 0.01%)
         // Fee = (amount * MIN_FEE) / MIN_FEE_MULTIPLIER
@@ -206,21 +264,40 @@ contract MyOApp is OApp, OAppOptionsType3 {
             FAST_TRANSFER_THRESHOLD
         );
         
-        (bool success, bytes memory result) = address(cctpTokenMessenger).call(data);
+        (bool success, ) = address(cctpTokenMessenger).call(data);
         require(success, "depositForBurn failed");
         
-        // CCTP V2 depositForBurn returns uint64 nonce
-        uint64 nonce = 0;
-        if (result.length > 0) {
-            nonce = abi.decode(result, (uint64));
-        }
-        
         // Store the ACTUAL minted amount (after fees) for this user
         cctpDeposits[msg.sender] = mintedAmount;
-        cctpNonces[msg.sender] = nonce;
         
-        emit CctpDepositInitiated(msg.sender, _amount, mintedAmount, cctpFee, nonce, SOLANA_DOMAIN);
-        return nonce;
+        emit CctpDepositInitiated(msg.sender, _amount, mintedAmount, cctpFee, SOLANA_DOMAIN);
+    }
+
+    /**
+     * @notice Set the relayer allowed to record attested CCTP nonces
+     * @param _relayer Relayer address (address(0) disables recording)
+     */
+    function setCctpRelayer(address _relayer) external onlyOwner {
+        cctpRelayer = _relayer;
+        emit CctpRelayerSet(_relayer);
+    }
+
+    /**
+     * @notice Record the CCTP V2 nonce of a user's pending deposit, read from its attested message
+     * @dev Called by the relayer once Circle has attested the burn of the user's depositViaCCTP tx.
+     * @dev The deposit amount sent to Solana must be covered by what the attested message mints.
+     * @param _user Depositor the burn belongs to
+     * @param _nonce bytes32 nonce of the attested message (message bytes 12..44)
+     * @param _mintedAmount USDC the attested message mints: its burn body's amount minus feeExecuted
+     */
+    function recordCctpNonce(address _user, bytes32 _nonce, uint256 _mintedAmount) external {
+        require(msg.sender == cctpRelayer, "Only CCTP relayer");
+        require(_nonce != bytes32(0), "Invalid CCTP nonce");
+        require(cctpDeposits[_user] > 0, "No pending CCTP deposit");
+        require(_mintedAmount >= cctpDeposits[_user], "Attested mint below deposit amount");
+        require(cctpNonces[_user] == bytes32(0), "CCTP nonce already recorded");
+        cctpNonces[_user] = _nonce;
+        emit CctpNonceRecorded(_user, _nonce);
     }
 
     // ==================== LayerZero Functions ====================
@@ -229,9 +306,9 @@ contract MyOApp is OApp, OAppOptionsType3 {
      * @notice Get the pending CCTP deposit amount for a user
      * @param _user User address to check
      * @return amount Pending CCTP deposit amount
-     * @return nonce CCTP nonce of the deposit
+     * @return nonce CCTP nonce of the deposit (zero until the relayer records it)
      */
-    function getPendingDeposit(address _user) external view returns (uint256 amount, uint64 nonce) {
+    function getPendingDeposit(address _user) external view returns (uint256 amount, bytes32 nonce) {
         return (cctpDeposits[_user], cctpNonces[_user]);
     }
 
@@ -239,31 +316,122 @@ contract MyOApp is OApp, OAppOptionsType3 {
 
     /**
      * @notice Step 2: Send LayerZero message to finalize deposit on Solana
-     * @dev User signs this transaction AFTER bot has submitted CCTP attestation on Solana.
-     * @dev This function uses the amount from the user's CCTP deposit (security: prevents amount mismatch).
-     * @dev If user has NO pending CCTP deposit, this function acts as legacy direct deposit (for testing).
+     * @dev User signs this transaction AFTER bot has submitted CCTP attestation on Solana and
+     * @dev recorded its nonce. This function uses the amount and nonce from the user's CCTP
+     * @dev deposit (security: prevents amount mismatch and binds the deposit to its mint).
      * @param _dstEid LayerZero destination endpoint ID (Solana = 40168)
+     * @param _vault Vault whose Solana position is credited (StrategyAdapterSolana passes its vault)
      * @param _options LayerZero execution options (gas, etc.)
      * @return receipt LayerZero messaging receipt
      */
     function requestDeposit(
         uint32 _dstEid,
+        address _vault,
         bytes calldata _options
     ) external payable returns (MessagingReceipt memory receipt) {
+        bytes memory payload = _depositPayload(_vault, false, address(0));
+        return _sendDeposit(_dstEid, _vault, VaultMsgCodec.MSG_TYPE_DEPOSIT, payload, _options);
+    }
+
+    /**
+     * @notice Step 2 variant: finalize the deposit and run a follow-up action via lz_compose
+     * @dev Same preconditions as requestDeposit. A zero `_referrer` allocates the deposit across
+     * @dev the Solana strategies; otherwise `_referrer` is registered for the depositor.
+     * @param _dstEid LayerZero destination endpoint ID (Solana = 40168)
+     * @param _vault Vault whose Solana position is credited
+     * @param _referrer Referrer to register, or address(0) to allocate
+     * @param _options LayerZero execution options (must cover the compose call)
+     * @return receipt LayerZero messaging receipt
+     */
+    function requestDepositAndCall(
+        uint32 _dstEid,
+        address _vault,
+        address _referrer,
+        bytes calldata _options
+    ) external payable returns (MessagingReceipt memory receipt) {
+        bytes memory payload = _depositPayload(_vault, true, _referrer);
+        return _sendDeposit(_dstEid, _vault, VaultMsgCodec.MSG_TYPE_DEPOSIT_AND_CALL, payload, _options);
+    }
+
+    /**
+     * @notice Quote the LayerZero fee for requestDeposit
+     * @dev Quotes based on the user's pending CCTP deposit amount
+     * @param _dstEid LayerZero destination endpoint ID
+     * @param _vault Vault whose Solana position is credited
+     * @param _options LayerZero execution options
+     * @param _payInLzToken Whether to pay fee in LZ token
+     * @return fee Estimated messaging fee
+     */
+    function quoteDeposit(
+        uint32 _dstEid,
+        address _vault,
+        bytes calldata _options,
+        bool _payInLzToken
+    ) external view returns (MessagingFee memory fee) {
+        bytes memory payload = _depositPayload(_vault, false, address(0));
+        bytes memory options = combineOptions(_dstEid, VaultMsgCodec.MSG_TYPE_DEPOSIT, _options);
+        return _quote(_dstEid, payload, options, _payInLzToken);
+    }
+
+    /**
+     * @notice Quote the LayerZero fee for requestDepositAndCall
+     * @param _dstEid LayerZero destination endpoint ID
+     * @param _vault Vault whose Solana position is credited
+     * @param _referrer Referrer to register, or address(0) to allocate
+     * @param _options LayerZero execution options
+     * @param _payInLzToken Whether to pay fee in LZ token
+     * @return fee Estimated messaging fee
+     */
+    function quoteDepositAndCall(
+        uint32 _dstEid,
+        address _vault,
+        address _referrer,
+        bytes calldata _options,
+        bool _payInLzToken
+    ) external view returns (MessagingFee memory fee) {
+        bytes memory payload = _depositPayload(_vault, true, _referrer);
+        bytes memory options = combineOptions(_dstEid, VaultMsgCodec.MSG_TYPE_DEPOSIT_AND_CALL, _options);
+        return _quote(_dstEid, payload, options, _payInLzToken);
+    }
+
+    // Builds the deposit payload for msg.sender's pending CCTP deposit:
+    // [version:1][msgType:1][amount:8][evm_address:20][vault:20][cctp_source_domain:4][cctp_nonce:32][action]
+    function _depositPayload(
+        address _vault,
+        bool _andCall,
+        address _referrer
+    ) internal view returns (bytes memory) {
+        require(_vault != address(0), "Invalid vault");
+        require(userVault[msg.sender] == address(0) || userVault[msg.sender] == _vault, "Vault mismatch");
         // Get amount from user's CCTP deposit
         uint256 amount = cctpDeposits[msg.sender];
         require(amount > 0, "No pending CCTP deposit. Call depositViaCCTP first.");
         require(amount <= type(uint64).max, "Amount exceeds uint64 max");
-        
-        // Build 36-byte payload: [amount:8][evm_address:20][cctp_nonce:8]
-        bytes memory payload = abi.encodePacked(
-            _toLeBytes8(uint64(amount)),           // 8 bytes: amount (little-endian)
-            msg.sender,                             // 20 bytes: EVM address
-            _toLeBytes8(cctpNonces[msg.sender])    // 8 bytes: CCTP nonce (little-endian)
+        bytes32 cctpNonce = cctpNonces[msg.sender];
+        require(cctpNonce != bytes32(0), "CCTP nonce not recorded yet");
+
+        if (!_andCall) {
+            return VaultMsgCodec.encDeposit(uint64(amount), msg.sender, _vault, SOURCE_DOMAIN, cctpNonce);
+        }
+        if (_referrer == address(0)) {
+            return VaultMsgCodec.encDepositAndAllocate(uint64(amount), msg.sender, _vault, SOURCE_DOMAIN, cctpNonce);
+        }
+        return VaultMsgCodec.encDepositAndRegisterReferrer(
+            uint64(amount), msg.sender, _vault, SOURCE_DOMAIN, cctpNonce, _referrer
         );
-        
-        bytes memory options = combineOptions(_dstEid, /*msgType*/ 1, _options);
-        receipt = _lzSend(_dstEid, payload, options, MessagingFee(msg.value, 0), payable(msg.sender));
+    }
+
+    function _sendDeposit(
+        uint32 _dstEid,
+        address _vault,
+        uint16 _msgType,
+        bytes memory _payload,
+        bytes calldata _options
+    ) internal returns (MessagingReceipt memory receipt) {
+        uint256 amount = cctpDeposits[msg.sender];
+        _bindVault(_vault);
+        bytes memory options = combineOptions(_dstEid, _msgType, _options);
+        receipt = _lzSend(_dstEid, _payload, options, MessagingFee(msg.value, 0), payable(msg.sender));
         
         // Track sender by GUID for bot to correlate with Solana DepositEvent
         pendingSender[receipt.guid] = msg.sender;
@@ -272,40 +440,75 @@ contract MyOApp is OApp, OAppOptionsType3 {
         delete cctpDeposits[msg.sender];
         delete cctpNonces[msg.sender];
         
-        emit DepositFinalized(msg.sender, amount, _dstEid, receipt.guid);
+        emit DepositFinalized(msg.sender, amount, _dstEid, receipt.guid, _vault);
         
         return receipt;
     }
 
+    // Binds msg.sender's Solana balance to `_vault` on first use; later messages must name the same vault
+    function _bindVault(address _vault) internal {
+        address bound = userVault[msg.sender];
+        if (bound == address(0)) {
+            userVault[msg.sender] = _vault;
+        } else {
+            require(bound == _vault, "Vault mismatch");
+        }
+    }
+
     /**
-     * @notice Quote the LayerZero fee for requestDeposit
-     * @dev Quotes based on the user's pending CCTP deposit amount
+     * @notice Ask Solana to redeem `_amount` USDC and burn it back to `_vault` via CCTP
+     * @dev Debits msg.sender's Solana balance and `_vault`'s position, which must be the vault the
+     * @dev balance is bound to (see `userVault`); the USDC is minted to
+     * @dev `_vault` on Base once the CCTP burn is attested (StrategyAdapterSolana passes its vault)
+     * @param _dstEid LayerZero destination endpoint ID (Solana = 40168)
+     * @param _amount USDC amount to withdraw
+     * @param _vault Vault whose Solana position is debited and that receives the USDC
+     * @param _options LayerZero execution options (gas, etc.)
+     * @return receipt LayerZero messaging receipt
+     */
+    function requestWithdraw(
+        uint32 _dstEid,
+        uint64 _amount,
+        address _vault,
+        bytes calldata _options
+    ) external payable returns (MessagingReceipt memory receipt) {
+        require(_amount > 0, "Amount must be greater than 0");
+        require(_vault != address(0), "Invalid vault");
+        _bindVault(_vault);
+
+        // [version:1][msgType:1][amount:8][evm_address:20][vault:20]
+        bytes memory payload = VaultMsgCodec.encWithdraw(_amount, msg.sender, _vault);
+        bytes memory options = combineOptions(_dstEid, VaultMsgCodec.MSG_TYPE_WITHDRAW, _options);
+        receipt = _lzSend(_dstEid, payload, options, MessagingFee(msg.value, 0), payable(msg.sender));
+
+        emit WithdrawRequested(msg.sender, _amount, _dstEid, receipt.guid, _vault);
+
+        return receipt;
+    }
+
+    /**
+     * @notice Quote the LayerZero fee for requestWithdraw
      * @param _dstEid LayerZero destination endpoint ID
+     * @param _amount USDC amount to withdraw
+     * @param _vault Vault whose Solana position is debited
      * @param _options LayerZero execution options
      * @param _payInLzToken Whether to pay fee in LZ token
      * @return fee Estimated messaging fee
      */
-    function quoteDeposit(
+    function quoteWithdraw(
         uint32 _dstEid,
+        uint64 _amount,
+        address _vault,
         bytes calldata _options,
         bool _payInLzToken
     ) external view returns (MessagingFee memory fee) {
-        // Get user's pending CCTP deposit amount
-        uint256 amount = cctpDeposits[msg.sender];
-        require(amount > 0, "No pending CCTP deposit");
-        require(amount <= type(uint64).max, "Amount exceeds uint64 max");
-        
-        // Build same 36-byte payload for accurate fee quote
-        bytes memory payload = abi.encodePacked(
-            _toLeBytes8(uint64(amount)),
-            msg.sender,
-            _toLeBytes8(cctpNonces[msg.sender])
-        );
-        bytes memory options = combineOptions(_dstEid, /*msgType*/ 1, _options);
+        bytes memory payload = VaultMsgCodec.encWithdraw(_amount, msg.sender, _vault);
+        bytes memory options = combineOptions(_dstEid, VaultMsgCodec.MSG_TYPE_WITHDRAW, _options);
         return _quote(_dstEid, payload, options, _payInLzToken);
     }
 
-    // Not used for this flow; kept for compatibility with message-only paths
+    // Handles the typed deposit ACK sent by the Solana `send_deposit_ack` crank;
+    // other payloads keep the legacy string behavior
     function _lzReceive(
         Origin calldata /*origin*/,
         bytes32 /*guid*/,
@@ -313,8 +516,38 @@ contract MyOApp is OApp, OAppOptionsType3 {
         address /*executor*/,
         bytes calldata /*extra*/
     ) internal override {
+        if (VaultMsgCodec.isAck(payload)) {
+            (uint64 amount, bytes32 depositGuid) = VaultMsgCodec.decAck(payload);
+            // Credit the original sender of the acknowledged deposit
+            address beneficiary = pendingSender[depositGuid];
+            if (beneficiary != address(0)) {
+                delete pendingSender[depositGuid];
+                balances[beneficiary] += amount;
+            }
+            return;
+        }
+        if (VaultMsgCodec.isWithdrawComplete(payload)) {
+            (uint64 amount, address user, bytes32 withdrawGuid) = VaultMsgCodec.decWithdrawComplete(payload);
+            emit WithdrawCompleted(user, amount, withdrawGuid);
+            return;
+        }
+        if (VaultMsgCodec.isBalanceSnapshot(payload)) {
+            (address user, uint64 shares, uint64 deposited, uint64 withdrawn, int64 timestamp) =
+                VaultMsgCodec.decBalanceSnapshot(payload);
+            emit BalanceSnapshot(user, shares, deposited, withdrawn, timestamp);
+            return;
+        }
+        if (VaultMsgCodec.isNav(payload)) {
+            (uint64 value, int64 timestamp, uint64 slot) = VaultMsgCodec.decNav(payload);
+            if (slot > solanaNavSlot) {
+                solanaNav = value;
+                solanaNavTimestamp = timestamp;
+                solanaNavSlot = slot;
+                emit NavReported(value, timestamp, slot);
+            }
+            return;
+        }
         // Decode legacy string if present
-        // no-op for counter flow
         data = string(payload);
     }
 
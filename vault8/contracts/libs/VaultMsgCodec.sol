@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.22;
+
+/// @notice Versioned payload codec shared with the Solana `my_oapp` program (msg_codec.rs)
+/// @dev Layout: [version:1][msgType:1][body]; integers are little-endian
+library VaultMsgCodec {
+    uint8 internal constant VERSION = 1;
+
+    uint8 internal constant MSG_TYPE_DEPOSIT = 1;       // body: [amount:8][evmAddress:20][vault:20][cctpSourceDomain:4][cctpNonce:32]
+    uint8 internal constant MSG_TYPE_WITHDRAW = 2;      // body: [amount:8][evmAddress:20][vault:20]
+    uint8 internal constant MSG_TYPE_ACK = 3;           // body: [amount:8][guid:32]
+    uint8 internal constant MSG_TYPE_REBALANCE = 4;     // body: [fromStrategy:1][toStrategy:1][amount:8]
+    uint8 internal constant MSG_TYPE_ADMIN_COMMAND = 5; // body: [command:1][value:8]
+    uint8 internal constant MSG_TYPE_DEPOSIT_AND_CALL = 6; // body: [amount:8][evmAddress:20][vault:20][cctpSourceDomain:4][cctpNonce:32][action]
+    uint8 internal constant MSG_TYPE_WITHDRAW_COMPLETE = 7; // body: [amount:8][evmAddress:20][guid:32]
+    uint8 internal constant MSG_TYPE_BALANCE_SNAPSHOT = 8;  // body: [evmAddress:20][shares:8][totalDeposited:8][totalWithdrawn:8][timestamp:8]
+    uint8 internal constant MSG_TYPE_NAV = 9;               // body: [value:8][timestamp:8][slot:8]
+
+    // Compose actions executed by the Solana `lz_compose` after a DepositAndCall
+    uint8 internal constant COMPOSE_ALLOCATE = 1;           // body: none
+    uint8 internal constant COMPOSE_REGISTER_REFERRER = 2;  // body: [referrer:20]
+
+    // vault keys the Solana VaultPosition credited (or debited). cctpSourceDomain / cctpNonce identify
+    // the CCTP V2 mint backing the deposit; the nonce is the attested message's bytes32 nonce, as-is
+    function encDeposit(
+        uint64 amount,
+        address evmAddress,
+        address vault,
+        uint32 cctpSourceDomain,
+        bytes32 cctpNonce
+    ) internal pure returns (bytes memory) {
+        return abi.encodePacked(
+            VERSION,
+            MSG_TYPE_DEPOSIT,
+            toLeBytes8(amount),
+            evmAddress,
+            vault,
+            toLeBytes4(cctpSourceDomain),
+            cctpNonce
+        );
+    }
+
+    function encDepositAndAllocate(
+        uint64 amount,
+        address evmAddress,
+        address vault,
+        uint32 cctpSourceDomain,
+        bytes32 cctpNonce
+    ) internal pure returns (bytes memory) {
+        return abi.encodePacked(
+            VERSION,
+            MSG_TYPE_DEPOSIT_AND_CALL,
+            toLeBytes8(amount),
+            evmAddress,
+            vault,
+            toLeBytes4(cctpSourceDomain),
+            cctpNonce,
+            COMPOSE_ALLOCATE
+        );
+    }
+
+    function encDepositAndRegisterReferrer(
+        uint64 amount,
+        address evmAddress,
+        address vault,
+        uint32 cctpSourceDomain,
+        bytes32 cctpNonce,
+        address referrer
+    ) internal pure returns (bytes memory) {
+        return abi.encodePacked(
+            VERSION,
+            MSG_TYPE_DEPOSIT_AND_CALL,
+            toLeBytes8(amount),
+            evmAddress,
+            vault,
+            toLeBytes4(cctpSourceDomain),
+            cctpNonce,
+            COMPOSE_REGISTER_REFERRER,
+            referrer
+        );
+    }
+
+    function encWithdraw(uint64 amount, address evmAddress, address vault) internal pure returns (bytes memory) {
+        return abi.encodePacked(VERSION, MSG_TYPE_WITHDRAW, toLeBytes8(amount), evmAddress, vault);
+    }
+
+    function isAck(bytes calldata payload) internal pure returns (bool) {
+        return payload.length == 42 && uint8(payload[0]) == VERSION && uint8(payload[1]) == MSG_TYPE_ACK;
+    }
+
+    // Assumes isAck(payload)
+    function decAck(bytes calldata payload) internal pure returns (uint64 amount, bytes32 guid) {
+        amount = fromLeBytes8(payload[2:10]);
+        guid = bytes32(payload[10:42]);
+    }
+
+    function isWithdrawComplete(bytes calldata payload) internal pure returns (bool) {
+        return payload.length == 62 && uint8(payload[0]) == VERSION && uint8(payload[1]) == MSG_TYPE_WITHDRAW_COMPLETE;
+    }
+
+    // Assumes isWithdrawComplete(payload)
+    function decWithdrawComplete(
+        bytes calldata payload
+    ) internal pure returns (uint64 amount, address evmAddress, bytes32 guid) {
+        amount = fromLeBytes8(payload[2:10]);
+        evmAddress = address(bytes20(payload[10:30]));
+        guid = bytes32(payload[30:62]);
+    }
+
+    function isBalanceSnapshot(bytes calldata payload) internal pure returns (bool) {
+        return payload.length == 54 && uint8(payload[0]) == VERSION && uint8(payload[1]) == MSG_TYPE_BALANCE_SNAPSHOT;
+    }
+
+    // Assumes isBalanceSnapshot(payload)
+    function decBalanceSnapshot(
+        bytes calldata payload
+    )
+        internal
+        pure
+        returns (address evmAddress, uint64 shares, uint64 totalDeposited, uint64 totalWithdrawn, int64 timestamp)
+    {
+        evmAddress = address(bytes20(payload[2:22]));
+        shares = fromLeBytes8(payload[22:30]);
+        totalDeposited = fromLeBytes8(payload[30:38]);
+        totalWithdrawn = fromLeBytes8(payload[38:46]);
+        timestamp = int64(fromLeBytes8(payload[46:54]));
+    }
+
+    function isNav(bytes calldata payload) internal pure returns (bool) {
+        return payload.length == 26 && uint8(payload[0]) == VERSION && uint8(payload[1]) == MSG_TYPE_NAV;
+    }
+
+    // Assumes isNav(payload)
+    function decNav(bytes calldata payload) internal pure returns (uint64 value, int64 timestamp, uint64 slot) {
+        value = fromLeBytes8(payload[2:10]);
+        timestamp = int64(fromLeBytes8(payload[10:18]));
+        slot = fromLeBytes8(payload[18:26]);
+    }
+
+    // Build 4-byte little-endian payload from uint32
+    function toLeBytes4(uint32 x) internal pure returns (bytes memory out) {
+        out = new bytes(4);
+        uint32 v = x;
+        for (uint256 i = 0; i < 4; i++) {
+            out[i] = bytes1(uint8(v & 0xFF));
+            v >>= 8;
+        }
+    }
+
+    // Build 8-byte little-endian payload from uint64
+    function toLeBytes8(uint64 x) internal pure returns (bytes memory out) {
+        out = new bytes(8);
+        uint64 v = x;
+        for (uint256 i = 0; i < 8; i++) {
+            out[i] = bytes1(uint8(v & 0xFF));
+            v >>= 8;
+        }
+    }
+
+    // Read uint64 from 8 little-endian bytes
+    function fromLeBytes8(bytes calldata b) internal pure returns (uint64 x) {
+        for (uint256 i = 0; i < 8; i++) {
+            x |= uint64(uint8(b[i])) << uint64(8 * i);
+        }
+    }
+}
@@ -8,9 +8,10 @@ const RPC_URL = 'https://api.devnet.solana.com';
 const PROGRAM_ID = '6xiE44rs5ft5PAxfJv1Pq4iXvsLrGbmEo4FbS9eLTfHV';
 const ALT_ADDRESS = 'EseuZ8NLNVpnarbUQNxtxRRvD9cWCTAyhgZcSDJFy4RB';
 const ENDPOINT_ID = '76y77prsiCMvXMjuoZ5VRrhG5qYBrUMYTE5WgHqgjEn6';
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111');
 
 async function main() {
-  // Load payer keypair
+  // Load payer keypair; init_store requires it to be the program's upgrade authority
   const keypath = path.join(process.env.HOME, '.config/solana/id.json');
   const payer = Keypair.fromSecretKey(Buffer.from(JSON.parse(fs.readFileSync(keypath, 'utf8'))));
   console.log('Payer:', payer.publicKey.toBase58());
@@ -29,9 +30,21 @@ async function main() {
     [Buffer.from('LzReceiveTypes'), store.toBuffer()],
     program.programId
   );
+  const [lzComposeTypesAccounts] = PublicKey.findProgramAddressSync(
+    [Buffer.from('LzComposeTypes'), store.toBuffer()],
+    program.programId
+  );
+  const [roles] = PublicKey.findProgramAddressSync([Buffer.from('Roles')], program.programId);
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID
+  );
 
   console.log(`\n📦 Store PDA: ${store.toBase58()}`);
   console.log(`📦 LzReceiveTypesAccounts PDA: ${lzReceiveTypesAccounts.toBase58()}`);
+  console.log(`📦 LzComposeTypesAccounts PDA: ${lzComposeTypesAccounts.toBase58()}`);
+  console.log(`📦 Roles PDA: ${roles.toBase58()}`);
+  console.log(`📦 ProgramData: ${programData.toBase58()}`);
   console.log(`📍 ALT: ${ALT_ADDRESS}\n`);
 
   try {
@@ -40,11 +53,16 @@ async function main() {
       .initStore({
         admin: payer.publicKey,
         endpoint: new PublicKey(ENDPOINT_ID),
+        orderedNonce: false,
       })
       .accounts({
         payer: payer.publicKey,
+        program: program.programId,
+        programData,
         store,
         lzReceiveTypesAccounts,
+        lzComposeTypesAccounts,
+        roles,
         alt: new PublicKey(ALT_ADDRESS),
         systemProgram: SystemProgram.programId,
       })
@@ -0,0 +1,116 @@
+// Upgrade a Store created with the original layout (Jupiter Lend pubkeys inlined) in place.
+// Run right after upgrading the program: until migrate_store lands, every instruction that loads
+// the Store fails to deserialize it. migrate_store issues the Store's fTokens as vault shares held
+// for the UserBalances in the original layout, and records the sum of their deposits counted here;
+// migrate_user_balance later hands each record its pro rata part. Peers and user balances are
+// migrated afterwards with migrate_peer_config / migrate_user_balance.
+const { Connection, Keypair, PublicKey, SystemProgram } = require('@solana/web3.js');
+const { Program, AnchorProvider, Wallet, BorshAccountsCoder, BN } = require('@coral-xyz/anchor');
+const bs58 = require('bs58');
+const fs = require('fs');
+const path = require('path');
+
+const RPC_URL = 'https://api.devnet.solana.com';
+const PROGRAM_ID = '6xiE44rs5ft5PAxfJv1Pq4iXvsLrGbmEo4FbS9eLTfHV';
+
+// Original layout: [discriminator:8][admin:32][bump:1][endpoint:32][usdc_mint, token_program,
+// associated_token_program, system_program:4*32][11 Jupiter Lend pubkeys:11*32]
+const STORE_V0_SIZE = 8 + 32 + 1 + 32 + 4 * 32 + 11 * 32;
+const TOKEN_PROGRAM_OFFSET = 8 + 32 + 1 + 32 + 32;
+const ATA_PROGRAM_OFFSET = TOKEN_PROGRAM_OFFSET + 32;
+const JL_F_TOKEN_MINT_OFFSET = 8 + 32 + 1 + 32 + 4 * 32 + 4 * 32;
+const JUPITER_LEND_STRATEGY = 0;
+// Original UserBalance layout: [discriminator:8][evm_address:20][4 u64 totals][deposit_count:4][bump:1]
+const USER_BALANCE_V0_SIZE = 8 + 20 + 4 * 8 + 4 + 1;
+const TOTAL_DEPOSITED_V0_OFFSET = 8 + 20;
+
+function readPubkey(data, offset) {
+  return new PublicKey(data.subarray(offset, offset + 32));
+}
+
+async function main() {
+  // Load admin keypair; it must be the admin recorded in the old Store
+  const keypath = path.join(process.env.HOME, '.config/solana/id.json');
+  const admin = Keypair.fromSecretKey(Buffer.from(JSON.parse(fs.readFileSync(keypath, 'utf8'))));
+  console.log('Admin:', admin.publicKey.toBase58());
+
+  // Load IDL
+  const idlPath = path.join(__dirname, 'target/idl/my_oapp.json');
+  const idl = JSON.parse(fs.readFileSync(idlPath, 'utf8'));
+
+  const connection = new Connection(RPC_URL, 'confirmed');
+  const provider = new AnchorProvider(connection, new Wallet(admin), { commitment: 'confirmed' });
+  const program = new Program(idl, PROGRAM_ID, provider);
+
+  // Derive PDAs
+  const [store] = PublicKey.findProgramAddressSync([Buffer.from('Store')], program.programId);
+  const storeInfo = await connection.getAccountInfo(store);
+  if (!storeInfo) throw new Error(`Store ${store.toBase58()} not found`);
+  if (storeInfo.data.length !== STORE_V0_SIZE) {
+    console.log(`Store is ${storeInfo.data.length} bytes, not the original layout; nothing to migrate.`);
+    return;
+  }
+
+  const [strategy] = PublicKey.findProgramAddressSync(
+    [Buffer.from('Strategy'), Buffer.from([JUPITER_LEND_STRATEGY])],
+    program.programId
+  );
+  const [roles] = PublicKey.findProgramAddressSync([Buffer.from('Roles')], program.programId);
+  const [lzComposeTypesAccounts] = PublicKey.findProgramAddressSync(
+    [Buffer.from('LzComposeTypes'), store.toBuffer()],
+    program.programId
+  );
+  const tokenProgram = readPubkey(storeInfo.data, TOKEN_PROGRAM_OFFSET);
+  const ataProgram = readPubkey(storeInfo.data, ATA_PROGRAM_OFFSET);
+  const fTokenMint = readPubkey(storeInfo.data, JL_F_TOKEN_MINT_OFFSET);
+  const [storeFTokenAta] = PublicKey.findProgramAddressSync(
+    [store.toBuffer(), tokenProgram.toBuffer(), fTokenMint.toBuffer()],
+    ataProgram
+  );
+
+  console.log(`\n📦 Store PDA: ${store.toBase58()}`);
+  console.log(`📦 JL StrategyConfig PDA: ${strategy.toBase58()}`);
+  console.log(`📦 Roles PDA: ${roles.toBase58()}`);
+  console.log(`📦 LzComposeTypesAccounts PDA: ${lzComposeTypesAccounts.toBase58()}`);
+  console.log(`📍 Store fToken ATA: ${storeFTokenAta.toBase58()}`);
+
+  // Every UserBalance still in the original layout; their deposits size the legacy share pool
+  const legacyBalances = await connection.getProgramAccounts(program.programId, {
+    filters: [
+      { dataSize: USER_BALANCE_V0_SIZE },
+      { memcmp: { offset: 0, bytes: bs58.encode(BorshAccountsCoder.accountDiscriminator('UserBalance')) } },
+    ],
+  });
+  const legacyTotalDeposited = legacyBalances.reduce(
+    (sum, { account }) => sum + account.data.readBigUInt64LE(TOTAL_DEPOSITED_V0_OFFSET),
+    0n
+  );
+  console.log(`👥 Legacy UserBalances: ${legacyBalances.length}`);
+  console.log(`💵 Legacy total deposited: ${legacyTotalDeposited}\n`);
+
+  try {
+    const tx = await program.methods
+      .migrateStore({ legacyTotalDeposited: new BN(legacyTotalDeposited.toString()) })
+      .accounts({
+        admin: admin.publicKey,
+        store,
+        strategy,
+        roles,
+        lzComposeTypesAccounts,
+        storeFTokenAta,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log(`✅ Store migrated!`);
+    console.log(`   Transaction: https://solscan.io/tx/${tx}?cluster=devnet`);
+    console.log(`\n🚀 Next steps:`);
+    console.log(`   1. migrate_peer_config for every peer`);
+    console.log(`   2. migrate_user_balance for every UserBalance under the old seeds`);
+  } catch (err) {
+    console.error('Error:', err);
+    if (err.logs) console.error('Logs:', err.logs);
+  }
+}
+
+main().catch(console.error);
@@ -41,6 +41,9 @@ async function main() {
     program.programId
   );
 
+  // Roles PDA; the signer must hold the config admin role
+  const [roles] = PublicKey.findProgramAddressSync([Buffer.from('Roles')], program.programId);
+
   console.log(`\n📦 Program ID: ${PROGRAM_ID}`);
   console.log(`📦 Store PDA: ${store.toBase58()}`);
   console.log(`📦 LzReceiveTypesAccounts PDA: ${lzReceiveTypesAccounts.toBase58()}`);
@@ -53,7 +56,8 @@ async function main() {
         store,
         lzReceiveTypesAccounts,
         alt: new PublicKey(altAddress),
-        admin: admin.publicKey,
+        configAdmin: admin.publicKey,
+        roles,
       })
       .rpc();
 
@@ -25,19 +25,19 @@ class MyOAppSDK {
             "function usdc() view returns (address)",
             "function cctpTokenMessenger() view returns (address)",
             "function cctpDeposits(address user) view returns (uint256)",
-            "function cctpNonces(address user) view returns (uint64)",
-            "function getPendingDeposit(address user) view returns (uint256 amount, uint64 nonce)",
+            "function cctpNonces(address user) view returns (bytes32)",
+            "function getPendingDeposit(address user) view returns (uint256 amount, bytes32 nonce)",
             "function balances(address user) view returns (uint256)",
             "function counter() view returns (uint64)",
             
             // Write functions
-            "function depositViaCCTP(uint256 _amount) returns (uint64)",
-            "function requestDeposit(uint32 _dstEid, bytes _options) payable returns (tuple(bytes32 guid, uint64 nonce, tuple(uint256 nativeFee, uint256 lzTokenFee) fee))",
-            "function quoteDeposit(uint32 _dstEid, bytes _options, bool _payInLzToken) view returns (tuple(uint256 nativeFee, uint256 lzTokenFee))",
+            "function depositViaCCTP(uint256 _amount)",
+            "function requestDeposit(uint32 _dstEid, address _vault, bytes _options) payable returns (tuple(bytes32 guid, uint64 nonce, tuple(uint256 nativeFee, uint256 lzTokenFee) fee))",
+            "function quoteDeposit(uint32 _dstEid, address _vault, bytes _options, bool _payInLzToken) view returns (tuple(uint256 nativeFee, uint256 lzTokenFee))",
             
             // Events
-            "event CctpDepositInitiated(address indexed user, uint256 depositAmount, uint256 mintedAmount, uint256 fee, uint64 indexed cctpNonce, uint32 destinationDomain)",
-            "event DepositFinalized(address indexed user, uint256 amount, uint32 dstEid, bytes32 guid)"
+            "event CctpDepositInitiated(address indexed user, uint256 depositAmount, uint256 mintedAmount, uint256 fee, uint32 destinationDomain)",
+            "event DepositFinalized(address indexed user, uint256 amount, uint32 indexed dstEid, bytes32 indexed guid, address vault)"
         ];
         
         this.contract = new ethers.Contract(contractAddress, abi, providerOrSigner);
@@ -55,7 +55,8 @@ class MyOAppSDK {
         const [amount, nonce] = await this.contract.getPendingDeposit(userAddress);
         return {
             amount: amount.toString(),
-            nonce: nonce.toString(),
+            // CCTP V2 nonce (bytes32); zero until the relayer records it from the attestation
+            nonce,
             amountFormatted: ethers.utils.formatUnits(amount, 6)
         };
     }
@@ -93,11 +94,12 @@ class MyOAppSDK {
     /**
      * Quote LayerZero fee for requestDeposit
      * @param {number} dstEid - Destination endpoint ID (40168 for Solana Devnet)
+     * @param {string} vault - Vault whose Solana position is credited
      * @param {string} options - LayerZero options (hex string)
      * @returns {Promise<{nativeFee: string, nativeFeeFormatted: string}>}
      */
-    async quoteDeposit(dstEid, options) {
-        const fee = await this.contract.quoteDeposit(dstEid, options, false);
+    async quoteDeposit(dstEid, vault, options) {
+        const fee = await this.contract.quoteDeposit(dstEid, vault, options, false);
         return {
             nativeFee: fee.nativeFee.toString(),
             nativeFeeFormatted: ethers.utils.formatEther(fee.nativeFee)
@@ -141,7 +143,6 @@ class MyOAppSDK {
             depositAmount: ethers.utils.formatUnits(event.args.depositAmount, 6),
             mintedAmount: ethers.utils.formatUnits(event.args.mintedAmount, 6),
             fee: ethers.utils.formatUnits(event.args.fee, 6),
-            cctpNonce: event.args.cctpNonce.toString(),
             destinationDomain: event.args.destinationDomain,
             gasUsed: receipt.gasUsed.toString()
         };
@@ -152,6 +153,7 @@ class MyOAppSDK {
      * Call this AFTER bot has submitted CCTP attestation on Solana
      * 
      * @param {number} dstEid - Destination endpoint ID (40168 for Solana Devnet)
+     * @param {string} vault - Vault whose Solana position is credited
      * @param {string} options - LayerZero options (hex string)
      * @returns {Promise<{
      *   txHash: string,
@@ -161,12 +163,12 @@ class MyOAppSDK {
      *   gasUsed: string
      * }>}
      */
-    async requestDeposit(dstEid, options) {
+    async requestDeposit(dstEid, vault, options) {
         // Quote fee first
-        const feeQuote = await this.quoteDeposit(dstEid, options);
+        const feeQuote = await this.quoteDeposit(dstEid, vault, options);
         
         // Send transaction with fee
-        const tx = await this.contract.requestDeposit(dstEid, options, {
+        const tx = await this.contract.requestDeposit(dstEid, vault, options, {
             value: feeQuote.nativeFee,
             gasLimit: 500000
         });
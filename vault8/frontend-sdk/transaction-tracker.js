@@ -81,7 +81,7 @@ class DepositTracker {
             
             // Parse CctpDepositInitiated event
             const myoappInterface = new ethers.utils.Interface([
-                "event CctpDepositInitiated(address indexed user, uint256 depositAmount, uint256 mintedAmount, uint256 fee, uint64 indexed cctpNonce, uint32 destinationDomain)"
+                "event CctpDepositInitiated(address indexed user, uint256 depositAmount, uint256 mintedAmount, uint256 fee, uint32 destinationDomain)"
             ]);
             
             const log = receipt.logs.find(log => {
@@ -98,11 +98,9 @@ class DepositTracker {
             }
             
             const event = myoappInterface.parseLog(log);
-            result.cctpNonce = event.args.cctpNonce.toString();
             
             result.status = TxStatus.CCTP_CONFIRMED;
             onStatusUpdate(TxStatus.CCTP_CONFIRMED, { 
-                cctpNonce: result.cctpNonce,
                 depositAmount: ethers.utils.formatUnits(event.args.depositAmount, 6),
                 mintedAmount: ethers.utils.formatUnits(event.args.mintedAmount, 6),
                 fee: ethers.utils.formatUnits(event.args.fee, 6)
@@ -116,6 +114,8 @@ class DepositTracker {
             });
             
             result.attestation = attestation;
+            // CCTP V2 nonce: bytes32 at message bytes 12..44, assigned by the attester
+            result.cctpNonce = "0x" + attestation.message.slice(2).slice(24, 88);
             result.status = TxStatus.CCTP_ATTESTED;
             onStatusUpdate(TxStatus.CCTP_ATTESTED, { attestation });
             
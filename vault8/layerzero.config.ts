@@ -28,6 +28,23 @@ const EVM_ENFORCED_OPTIONS: OAppEnforcedOption[] = [
         gas: 60_000,
         value: 0,
     },
+    {
+        msgType: 2,
+        optionType: ExecutorOptionType.LZ_RECEIVE,
+        gas: 800_000, // Withdraw: strategy redemption CPIs + CCTP deposit_for_burn
+    },
+    {
+        msgType: 6,
+        optionType: ExecutorOptionType.LZ_RECEIVE,
+        gas: 800_000, // DepositAndCall: same lzReceive budget as a deposit
+    },
+    {
+        msgType: 6,
+        optionType: ExecutorOptionType.COMPOSE,
+        index: 0,
+        gas: 400_000, // Compute units for the lz_compose Allocate / RegisterReferrer action
+        value: 0,
+    },
 ]
 
 const SOLANA_ENFORCED_OPTIONS: OAppEnforcedOption[] = [
@@ -9,5 +9,6 @@
 export * from './endpointSettings';
 export * from './lzReceiveTypesAccounts';
 export * from './peerConfig';
+export * from './roles';
 export * from './store';
 export * from './userBalance';
@@ -21,7 +21,9 @@ import {
 } from '@metaplex-foundation/umi';
 import {
   Serializer,
+  bool,
   bytes,
+  i64,
   mapSerializer,
   struct,
   u8,
@@ -29,7 +31,10 @@ import {
 import {
   EnforcedOptions,
   EnforcedOptionsArgs,
+  RateLimit,
+  RateLimitArgs,
   getEnforcedOptionsSerializer,
+  getRateLimitSerializer,
 } from '../types';
 
 export type PeerConfig = Account<PeerConfigAccountData>;
@@ -39,12 +44,24 @@ export type PeerConfigAccountData = {
   peerAddress: Uint8Array;
   enforcedOptions: EnforcedOptions;
   bump: number;
+  inboundPaused: boolean;
+  outboundPaused: boolean;
+  inboundRateLimit: RateLimit;
+  lastNavReport: bigint;
+  version: number;
+  reserved: Uint8Array;
 };
 
 export type PeerConfigAccountDataArgs = {
   peerAddress: Uint8Array;
   enforcedOptions: EnforcedOptionsArgs;
   bump: number;
+  inboundPaused: boolean;
+  outboundPaused: boolean;
+  inboundRateLimit: RateLimitArgs;
+  lastNavReport: number | bigint;
+  version: number;
+  reserved: Uint8Array;
 };
 
 export function getPeerConfigAccountDataSerializer(): Serializer<
@@ -58,6 +75,12 @@ export function getPeerConfigAccountDataSerializer(): Serializer<
         ['peerAddress', bytes({ size: 32 })],
         ['enforcedOptions', getEnforcedOptionsSerializer()],
         ['bump', u8()],
+        ['inboundPaused', bool()],
+        ['outboundPaused', bool()],
+        ['inboundRateLimit', getRateLimitSerializer()],
+        ['lastNavReport', i64()],
+        ['version', u8()],
+        ['reserved', bytes({ size: 56 })],
       ],
       { description: 'PeerConfigAccountData' }
     ),
@@ -139,11 +162,23 @@ export function getPeerConfigGpaBuilder(
       peerAddress: Uint8Array;
       enforcedOptions: EnforcedOptionsArgs;
       bump: number;
+      inboundPaused: boolean;
+      outboundPaused: boolean;
+      inboundRateLimit: RateLimitArgs;
+      lastNavReport: number | bigint;
+      version: number;
+      reserved: Uint8Array;
     }>({
       discriminator: [0, bytes({ size: 8 })],
       peerAddress: [8, bytes({ size: 32 })],
       enforcedOptions: [40, getEnforcedOptionsSerializer()],
       bump: [null, u8()],
+      inboundPaused: [null, bool()],
+      outboundPaused: [null, bool()],
+      inboundRateLimit: [null, getRateLimitSerializer()],
+      lastNavReport: [null, i64()],
+      version: [null, u8()],
+      reserved: [null, bytes({ size: 56 })],
     })
     .deserializeUsing<PeerConfig>((account) => deserializePeerConfig(account))
     .whereField(
@@ -0,0 +1,175 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import {
+  Account,
+  Context,
+  Pda,
+  PublicKey,
+  RpcAccount,
+  RpcGetAccountOptions,
+  RpcGetAccountsOptions,
+  assertAccountExists,
+  deserializeAccount,
+  gpaBuilder,
+  publicKey as toPublicKey,
+} from '@metaplex-foundation/umi';
+import {
+  Serializer,
+  bytes,
+  mapSerializer,
+  publicKey as publicKeySerializer,
+  struct,
+  u8,
+} from '@metaplex-foundation/umi/serializers';
+
+/**
+ * Operational keys, each gating a class of instructions. `Store.admin` owns this account: it
+ * assigns every role with `set_role` and is the only key that can transfer itself.
+ */
+export type Roles = Account<RolesAccountData>;
+
+export type RolesAccountData = {
+  discriminator: Uint8Array;
+  configAdmin: PublicKey;
+  strategyKeeper: PublicKey;
+  pauser: PublicKey;
+  feeCollector: PublicKey;
+  bump: number;
+  version: number;
+  reserved: Uint8Array;
+};
+
+export type RolesAccountDataArgs = {
+  configAdmin: PublicKey;
+  strategyKeeper: PublicKey;
+  pauser: PublicKey;
+  feeCollector: PublicKey;
+  bump: number;
+  version: number;
+  reserved: Uint8Array;
+};
+
+export function getRolesAccountDataSerializer(): Serializer<
+  RolesAccountDataArgs,
+  RolesAccountData
+> {
+  return mapSerializer<RolesAccountDataArgs, any, RolesAccountData>(
+    struct<RolesAccountData>(
+      [
+        ['discriminator', bytes({ size: 8 })],
+        ['configAdmin', publicKeySerializer()],
+        ['strategyKeeper', publicKeySerializer()],
+        ['pauser', publicKeySerializer()],
+        ['feeCollector', publicKeySerializer()],
+        ['bump', u8()],
+        ['version', u8()],
+        ['reserved', bytes({ size: 64 })],
+      ],
+      { description: 'RolesAccountData' }
+    ),
+    (value) => ({
+      ...value,
+      discriminator: new Uint8Array([177, 37, 17, 201, 242, 158, 212, 65]),
+    })
+  ) as Serializer<RolesAccountDataArgs, RolesAccountData>;
+}
+
+export function deserializeRoles(rawAccount: RpcAccount): Roles {
+  return deserializeAccount(rawAccount, getRolesAccountDataSerializer());
+}
+
+export async function fetchRoles(
+  context: Pick<Context, 'rpc'>,
+  publicKey: PublicKey | Pda,
+  options?: RpcGetAccountOptions
+): Promise<Roles> {
+  const maybeAccount = await context.rpc.getAccount(
+    toPublicKey(publicKey, false),
+    options
+  );
+  assertAccountExists(maybeAccount, 'Roles');
+  return deserializeRoles(maybeAccount);
+}
+
+export async function safeFetchRoles(
+  context: Pick<Context, 'rpc'>,
+  publicKey: PublicKey | Pda,
+  options?: RpcGetAccountOptions
+): Promise<Roles | null> {
+  const maybeAccount = await context.rpc.getAccount(
+    toPublicKey(publicKey, false),
+    options
+  );
+  return maybeAccount.exists ? deserializeRoles(maybeAccount) : null;
+}
+
+export async function fetchAllRoles(
+  context: Pick<Context, 'rpc'>,
+  publicKeys: Array<PublicKey | Pda>,
+  options?: RpcGetAccountsOptions
+): Promise<Roles[]> {
+  const maybeAccounts = await context.rpc.getAccounts(
+    publicKeys.map((key) => toPublicKey(key, false)),
+    options
+  );
+  return maybeAccounts.map((maybeAccount) => {
+    assertAccountExists(maybeAccount, 'Roles');
+    return deserializeRoles(maybeAccount);
+  });
+}
+
+export async function safeFetchAllRoles(
+  context: Pick<Context, 'rpc'>,
+  publicKeys: Array<PublicKey | Pda>,
+  options?: RpcGetAccountsOptions
+): Promise<Roles[]> {
+  const maybeAccounts = await context.rpc.getAccounts(
+    publicKeys.map((key) => toPublicKey(key, false)),
+    options
+  );
+  return maybeAccounts
+    .filter((maybeAccount) => maybeAccount.exists)
+    .map((maybeAccount) => deserializeRoles(maybeAccount as RpcAccount));
+}
+
+export function getRolesGpaBuilder(context: Pick<Context, 'rpc' | 'programs'>) {
+  const programId = context.programs.getPublicKey(
+    'myOapp',
+    'HFyiETGKEUS9tr87K1HXmVJHkqQRtw8wShRNTMkKKxay'
+  );
+  return gpaBuilder(context, programId)
+    .registerFields<{
+      discriminator: Uint8Array;
+      configAdmin: PublicKey;
+      strategyKeeper: PublicKey;
+      pauser: PublicKey;
+      feeCollector: PublicKey;
+      bump: number;
+      version: number;
+      reserved: Uint8Array;
+    }>({
+      discriminator: [0, bytes({ size: 8 })],
+      configAdmin: [8, publicKeySerializer()],
+      strategyKeeper: [40, publicKeySerializer()],
+      pauser: [72, publicKeySerializer()],
+      feeCollector: [104, publicKeySerializer()],
+      bump: [136, u8()],
+      version: [137, u8()],
+      reserved: [138, bytes({ size: 64 })],
+    })
+    .deserializeUsing<Roles>((account) => deserializeRoles(account))
+    .whereField(
+      'discriminator',
+      new Uint8Array([177, 37, 17, 201, 242, 158, 212, 65])
+    );
+}
+
+export function getRolesSize(): number {
+  return 202;
+}
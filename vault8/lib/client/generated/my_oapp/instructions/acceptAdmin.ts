@@ -0,0 +1,106 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import {
+  Context,
+  Pda,
+  PublicKey,
+  Signer,
+  TransactionBuilder,
+  transactionBuilder,
+} from '@metaplex-foundation/umi';
+import {
+  Serializer,
+  bytes,
+  mapSerializer,
+  struct,
+} from '@metaplex-foundation/umi/serializers';
+import {
+  ResolvedAccount,
+  ResolvedAccountsWithIndices,
+  getAccountMetasAndSigners,
+} from '../shared';
+
+// Accounts.
+export type AcceptAdminInstructionAccounts = {
+  pendingAdmin: Signer;
+  store: PublicKey | Pda;
+};
+
+// Data.
+export type AcceptAdminInstructionData = { discriminator: Uint8Array };
+
+export type AcceptAdminInstructionDataArgs = {};
+
+export function getAcceptAdminInstructionDataSerializer(): Serializer<
+  AcceptAdminInstructionDataArgs,
+  AcceptAdminInstructionData
+> {
+  return mapSerializer<
+    AcceptAdminInstructionDataArgs,
+    any,
+    AcceptAdminInstructionData
+  >(
+    struct<AcceptAdminInstructionData>(
+      [['discriminator', bytes({ size: 8 })]],
+      { description: 'AcceptAdminInstructionData' }
+    ),
+    (value) => ({
+      ...value,
+      discriminator: new Uint8Array([112, 42, 45, 90, 116, 181, 13, 170]),
+    })
+  ) as Serializer<AcceptAdminInstructionDataArgs, AcceptAdminInstructionData>;
+}
+
+// Instruction.
+export function acceptAdmin(
+  context: Pick<Context, 'programs'>,
+  input: AcceptAdminInstructionAccounts
+): TransactionBuilder {
+  // Program ID.
+  const programId = context.programs.getPublicKey(
+    'myOapp',
+    'HFyiETGKEUS9tr87K1HXmVJHkqQRtw8wShRNTMkKKxay'
+  );
+
+  // Accounts.
+  const resolvedAccounts = {
+    pendingAdmin: {
+      index: 0,
+      isWritable: false as boolean,
+      value: input.pendingAdmin ?? null,
+    },
+    store: {
+      index: 1,
+      isWritable: true as boolean,
+      value: input.store ?? null,
+    },
+  } satisfies ResolvedAccountsWithIndices;
+
+  // Accounts in order.
+  const orderedAccounts: ResolvedAccount[] = Object.values(
+    resolvedAccounts
+  ).sort((a, b) => a.index - b.index);
+
+  // Keys and Signers.
+  const [keys, signers] = getAccountMetasAndSigners(
+    orderedAccounts,
+    'programId',
+    programId
+  );
+
+  // Data.
+  const data = getAcceptAdminInstructionDataSerializer().serialize({});
+
+  // Bytes Created On Chain.
+  const bytesCreatedOnChain = 0;
+
+  return transactionBuilder([
+    { instruction: { keys, programId, data }, signers, bytesCreatedOnChain },
+  ]);
+}
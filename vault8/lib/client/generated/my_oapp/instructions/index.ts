@@ -6,14 +6,17 @@
  * @see https://github.com/kinobi-so/kinobi
  */
 
+export * from './acceptAdmin';
 export * from './initStore';
 export * from './initStoreAtas';
 export * from './lzReceive';
 export * from './lzReceiveTypes';
 export * from './lzReceiveTypesInfo';
 export * from './lzReceiveTypesV2';
+export * from './proposeAdmin';
 export * from './quoteSend';
 export * from './send';
 export * from './setAlt';
 export * from './setJlConfig';
 export * from './setPeerConfig';
+export * from './setRole';
@@ -16,6 +16,7 @@ import {
 } from '@metaplex-foundation/umi';
 import {
   Serializer,
+  bool,
   bytes,
   mapSerializer,
   publicKey as publicKeySerializer,
@@ -30,8 +31,13 @@ import {
 // Accounts.
 export type InitStoreInstructionAccounts = {
   payer?: Signer;
+  program?: PublicKey | Pda;
+  /** ProgramData of this program; only its upgrade authority may initialize the Store */
+  programData: PublicKey | Pda;
   store: PublicKey | Pda;
   lzReceiveTypesAccounts: PublicKey | Pda;
+  lzComposeTypesAccounts: PublicKey | Pda;
+  roles: PublicKey | Pda;
   alt?: PublicKey | Pda;
   systemProgram?: PublicKey | Pda;
 };
@@ -41,11 +47,13 @@ export type InitStoreInstructionData = {
   discriminator: Uint8Array;
   admin: PublicKey;
   endpoint: PublicKey;
+  orderedNonce: boolean;
 };
 
 export type InitStoreInstructionDataArgs = {
   admin: PublicKey;
   endpoint: PublicKey;
+  orderedNonce: boolean;
 };
 
 export function getInitStoreInstructionDataSerializer(): Serializer<
@@ -62,6 +70,7 @@ export function getInitStoreInstructionDataSerializer(): Serializer<
         ['discriminator', bytes({ size: 8 })],
         ['admin', publicKeySerializer()],
         ['endpoint', publicKeySerializer()],
+        ['orderedNonce', bool()],
       ],
       { description: 'InitStoreInstructionData' }
     ),
@@ -93,19 +102,39 @@ export function initStore(
       isWritable: true as boolean,
       value: input.payer ?? null,
     },
-    store: {
+    program: {
       index: 1,
+      isWritable: false as boolean,
+      value: input.program ?? null,
+    },
+    programData: {
+      index: 2,
+      isWritable: false as boolean,
+      value: input.programData ?? null,
+    },
+    store: {
+      index: 3,
       isWritable: true as boolean,
       value: input.store ?? null,
     },
     lzReceiveTypesAccounts: {
-      index: 2,
+      index: 4,
       isWritable: true as boolean,
       value: input.lzReceiveTypesAccounts ?? null,
     },
-    alt: { index: 3, isWritable: false as boolean, value: input.alt ?? null },
+    lzComposeTypesAccounts: {
+      index: 5,
+      isWritable: true as boolean,
+      value: input.lzComposeTypesAccounts ?? null,
+    },
+    roles: {
+      index: 6,
+      isWritable: true as boolean,
+      value: input.roles ?? null,
+    },
+    alt: { index: 7, isWritable: false as boolean, value: input.alt ?? null },
     systemProgram: {
-      index: 4,
+      index: 8,
       isWritable: false as boolean,
       value: input.systemProgram ?? null,
     },
@@ -118,6 +147,10 @@ export function initStore(
   if (!resolvedAccounts.payer.value) {
     resolvedAccounts.payer.value = context.payer;
   }
+  if (!resolvedAccounts.program.value) {
+    resolvedAccounts.program.value = programId;
+    resolvedAccounts.program.isWritable = false;
+  }
   if (!resolvedAccounts.systemProgram.value) {
     resolvedAccounts.systemProgram.value = context.programs.getPublicKey(
       'splSystem',
@@ -18,7 +18,6 @@ import {
   bool,
   bytes,
   mapSerializer,
-  string,
   struct,
   u32,
 } from '@metaplex-foundation/umi/serializers';
@@ -27,12 +26,19 @@ import {
   ResolvedAccountsWithIndices,
   getAccountMetasAndSigners,
 } from '../shared';
+import {
+  OutboundMessage,
+  OutboundMessageArgs,
+  getOutboundMessageSerializer,
+} from '../types';
 
 // Accounts.
 export type QuoteSendInstructionAccounts = {
   store: PublicKey | Pda;
   peer: PublicKey | Pda;
   endpoint: PublicKey | Pda;
+  /** Required to quote a `BalanceSnapshot` */
+  userBalance?: PublicKey | Pda;
 };
 
 // Data.
@@ -40,7 +46,7 @@ export type QuoteSendInstructionData = {
   discriminator: Uint8Array;
   dstEid: number;
   receiver: Uint8Array;
-  message: string;
+  message: OutboundMessage;
   options: Uint8Array;
   payInLzToken: boolean;
 };
@@ -48,7 +54,7 @@ export type QuoteSendInstructionData = {
 export type QuoteSendInstructionDataArgs = {
   dstEid: number;
   receiver: Uint8Array;
-  message: string;
+  message: OutboundMessageArgs;
   options: Uint8Array;
   payInLzToken: boolean;
 };
@@ -67,7 +73,7 @@ export function getQuoteSendInstructionDataSerializer(): Serializer<
         ['discriminator', bytes({ size: 8 })],
         ['dstEid', u32()],
         ['receiver', bytes({ size: 32 })],
-        ['message', string()],
+        ['message', getOutboundMessageSerializer()],
         ['options', bytes({ size: u32() })],
         ['payInLzToken', bool()],
       ],
@@ -107,6 +113,11 @@ export function quoteSend(
       isWritable: false as boolean,
       value: input.endpoint ?? null,
     },
+    userBalance: {
+      index: 3,
+      isWritable: false as boolean,
+      value: input.userBalance ?? null,
+    },
   } satisfies ResolvedAccountsWithIndices;
 
   // Arguments.
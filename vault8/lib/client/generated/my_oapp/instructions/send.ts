@@ -10,6 +10,7 @@ import {
   Context,
   Pda,
   PublicKey,
+  Signer,
   TransactionBuilder,
   transactionBuilder,
 } from '@metaplex-foundation/umi';
@@ -17,7 +18,6 @@ import {
   Serializer,
   bytes,
   mapSerializer,
-  string,
   struct,
   u32,
   u64,
@@ -27,6 +27,11 @@ import {
   ResolvedAccountsWithIndices,
   getAccountMetasAndSigners,
 } from '../shared';
+import {
+  OutboundMessage,
+  OutboundMessageArgs,
+  getOutboundMessageSerializer,
+} from '../types';
 
 // Accounts.
 export type SendInstructionAccounts = {
@@ -39,13 +44,18 @@ export type SendInstructionAccounts = {
   /** OApp Store PDA that signs the send instruction */
   store: PublicKey | Pda;
   endpoint: PublicKey | Pda;
+  /** Required unless the message is a `WithdrawComplete` */
+  keeper?: Signer;
+  roles: PublicKey | Pda;
+  /** Required for a `BalanceSnapshot` */
+  userBalance?: PublicKey | Pda;
 };
 
 // Data.
 export type SendInstructionData = {
   discriminator: Uint8Array;
   dstEid: number;
-  message: string;
+  message: OutboundMessage;
   options: Uint8Array;
   nativeFee: bigint;
   lzTokenFee: bigint;
@@ -53,7 +63,7 @@ export type SendInstructionData = {
 
 export type SendInstructionDataArgs = {
   dstEid: number;
-  message: string;
+  message: OutboundMessageArgs;
   options: Uint8Array;
   nativeFee: number | bigint;
   lzTokenFee: number | bigint;
@@ -68,7 +78,7 @@ export function getSendInstructionDataSerializer(): Serializer<
       [
         ['discriminator', bytes({ size: 8 })],
         ['dstEid', u32()],
-        ['message', string()],
+        ['message', getOutboundMessageSerializer()],
         ['options', bytes({ size: u32() })],
         ['nativeFee', u64()],
         ['lzTokenFee', u64()],
@@ -109,6 +119,21 @@ export function send(
       isWritable: false as boolean,
       value: input.endpoint ?? null,
     },
+    keeper: {
+      index: 3,
+      isWritable: false as boolean,
+      value: input.keeper ?? null,
+    },
+    roles: {
+      index: 4,
+      isWritable: false as boolean,
+      value: input.roles ?? null,
+    },
+    userBalance: {
+      index: 5,
+      isWritable: false as boolean,
+      value: input.userBalance ?? null,
+    },
   } satisfies ResolvedAccountsWithIndices;
 
   // Arguments.
@@ -31,7 +31,8 @@ export type SetAltInstructionAccounts = {
   store: PublicKey | Pda;
   lzReceiveTypesAccounts: PublicKey | Pda;
   alt: PublicKey | Pda;
-  admin: Signer;
+  configAdmin: Signer;
+  roles: PublicKey | Pda;
 };
 
 // Data.
@@ -78,10 +79,15 @@ export function setAlt(
       value: input.lzReceiveTypesAccounts ?? null,
     },
     alt: { index: 2, isWritable: false as boolean, value: input.alt ?? null },
-    admin: {
+    configAdmin: {
       index: 3,
       isWritable: true as boolean,
-      value: input.admin ?? null,
+      value: input.configAdmin ?? null,
+    },
+    roles: {
+      index: 4,
+      isWritable: false as boolean,
+      value: input.roles ?? null,
     },
   } satisfies ResolvedAccountsWithIndices;
 
@@ -30,7 +30,17 @@ import {
 // Accounts.
 export type SetJlConfigInstructionAccounts = {
   store: PublicKey | Pda;
-  admin: Signer;
+  /** Jupiter Lend StrategyConfig PDA */
+  strategy: PublicKey | Pda;
+  /** Holder of the config admin role */
+  configAdmin: Signer;
+  roles: PublicKey | Pda;
+  jlLendingAdmin: PublicKey | Pda;
+  jlLending: PublicKey | Pda;
+  jlFTokenMint: PublicKey | Pda;
+  jlVault: PublicKey | Pda;
+  usdcMint: PublicKey | Pda;
+  systemProgram?: PublicKey | Pda;
 };
 
 // Data.
@@ -129,16 +139,65 @@ export function setJlConfig(
       isWritable: true as boolean,
       value: input.store ?? null,
     },
-    admin: {
+    strategy: {
       index: 1,
+      isWritable: true as boolean,
+      value: input.strategy ?? null,
+    },
+    configAdmin: {
+      index: 2,
+      isWritable: true as boolean,
+      value: input.configAdmin ?? null,
+    },
+    roles: {
+      index: 3,
       isWritable: false as boolean,
-      value: input.admin ?? null,
+      value: input.roles ?? null,
+    },
+    jlLendingAdmin: {
+      index: 4,
+      isWritable: false as boolean,
+      value: input.jlLendingAdmin ?? null,
+    },
+    jlLending: {
+      index: 5,
+      isWritable: false as boolean,
+      value: input.jlLending ?? null,
+    },
+    jlFTokenMint: {
+      index: 6,
+      isWritable: false as boolean,
+      value: input.jlFTokenMint ?? null,
+    },
+    jlVault: {
+      index: 7,
+      isWritable: false as boolean,
+      value: input.jlVault ?? null,
+    },
+    usdcMint: {
+      index: 8,
+      isWritable: false as boolean,
+      value: input.usdcMint ?? null,
+    },
+    systemProgram: {
+      index: 9,
+      isWritable: false as boolean,
+      value: input.systemProgram ?? null,
     },
   } satisfies ResolvedAccountsWithIndices;
 
   // Arguments.
   const resolvedArgs: SetJlConfigInstructionArgs = { ...input };
 
+  // Default values.
+  if (!resolvedAccounts.systemProgram.value) {
+    resolvedAccounts.systemProgram.value = context.programs.getPublicKey(
+      'splSystem',
+      '11111111111111111111111111111111'
+    );
+    resolvedAccounts.systemProgram.isWritable = false;
+  }
+
   // Accounts in order.
   const orderedAccounts: ResolvedAccount[] = Object.values(
     resolvedAccounts
@@ -34,10 +34,13 @@ import {
 
 // Accounts.
 export type SetPeerConfigInstructionAccounts = {
-  /** Admin of the OApp store */
-  admin: Signer;
+  /** Holder of the config admin role */
+  configAdmin: Signer;
+  roles: PublicKey | Pda;
   /** Peer configuration PDA for a specific remote chain */
   peer: PublicKey | Pda;
+  /** Inbound nonce PDA for the (remote_eid, peer) path; required when setting the peer address */
+  nonceAccount?: PublicKey | Pda;
   /** Store PDA of this OApp */
   store: PublicKey | Pda;
   systemProgram?: PublicKey | Pda;
@@ -98,19 +101,29 @@ export function setPeerConfig(
 
   // Accounts.
   const resolvedAccounts = {
-    admin: {
+    configAdmin: {
       index: 0,
       isWritable: true as boolean,
-      value: input.admin ?? null,
+      value: input.configAdmin ?? null,
+    },
+    roles: {
+      index: 1,
+      isWritable: false as boolean,
+      value: input.roles ?? null,
+    },
+    peer: { index: 2, isWritable: true as boolean, value: input.peer ?? null },
+    nonceAccount: {
+      index: 3,
+      isWritable: true as boolean,
+      value: input.nonceAccount ?? null,
     },
-    peer: { index: 1, isWritable: true as boolean, value: input.peer ?? null },
     store: {
-      index: 2,
+      index: 4,
       isWritable: false as boolean,
       value: input.store ?? null,
     },
     systemProgram: {
-      index: 3,
+      index: 5,
       isWritable: false as boolean,
       value: input.systemProgram ?? null,
     },
@@ -0,0 +1,141 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import {
+  Context,
+  Pda,
+  PublicKey,
+  Signer,
+  TransactionBuilder,
+  transactionBuilder,
+} from '@metaplex-foundation/umi';
+import {
+  Serializer,
+  bytes,
+  mapSerializer,
+  publicKey as publicKeySerializer,
+  struct,
+} from '@metaplex-foundation/umi/serializers';
+import {
+  ResolvedAccount,
+  ResolvedAccountsWithIndices,
+  getAccountMetasAndSigners,
+} from '../shared';
+import { Role, RoleArgs, getRoleSerializer } from '../types';
+
+// Accounts.
+export type SetRoleInstructionAccounts = {
+  admin: Signer;
+  store: PublicKey | Pda;
+  roles: PublicKey | Pda;
+  systemProgram?: PublicKey | Pda;
+};
+
+// Data.
+export type SetRoleInstructionData = {
+  discriminator: Uint8Array;
+  role: Role;
+  account: PublicKey;
+};
+
+export type SetRoleInstructionDataArgs = { role: RoleArgs; account: PublicKey };
+
+export function getSetRoleInstructionDataSerializer(): Serializer<
+  SetRoleInstructionDataArgs,
+  SetRoleInstructionData
+> {
+  return mapSerializer<SetRoleInstructionDataArgs, any, SetRoleInstructionData>(
+    struct<SetRoleInstructionData>(
+      [
+        ['discriminator', bytes({ size: 8 })],
+        ['role', getRoleSerializer()],
+        ['account', publicKeySerializer()],
+      ],
+      { description: 'SetRoleInstructionData' }
+    ),
+    (value) => ({
+      ...value,
+      discriminator: new Uint8Array([77, 78, 62, 233, 192, 61, 199, 190]),
+    })
+  ) as Serializer<SetRoleInstructionDataArgs, SetRoleInstructionData>;
+}
+
+// Args.
+export type SetRoleInstructionArgs = SetRoleInstructionDataArgs;
+
+// Instruction.
+export function setRole(
+  context: Pick<Context, 'programs'>,
+  input: SetRoleInstructionAccounts & SetRoleInstructionArgs
+): TransactionBuilder {
+  // Program ID.
+  const programId = context.programs.getPublicKey(
+    'myOapp',
+    'HFyiETGKEUS9tr87K1HXmVJHkqQRtw8wShRNTMkKKxay'
+  );
+
+  // Accounts.
+  const resolvedAccounts = {
+    admin: {
+      index: 0,
+      isWritable: true as boolean,
+      value: input.admin ?? null,
+    },
+    store: {
+      index: 1,
+      isWritable: false as boolean,
+      value: input.store ?? null,
+    },
+    roles: {
+      index: 2,
+      isWritable: true as boolean,
+      value: input.roles ?? null,
+    },
+    systemProgram: {
+      index: 3,
+      isWritable: false as boolean,
+      value: input.systemProgram ?? null,
+    },
+  } satisfies ResolvedAccountsWithIndices;
+
+  // Arguments.
+  const resolvedArgs: SetRoleInstructionArgs = { ...input };
+
+  // Default values.
+  if (!resolvedAccounts.systemProgram.value) {
+    resolvedAccounts.systemProgram.value = context.programs.getPublicKey(
+      'splSystem',
+      '11111111111111111111111111111111'
+    );
+    resolvedAccounts.systemProgram.isWritable = false;
+  }
+
+  // Accounts in order.
+  const orderedAccounts: ResolvedAccount[] = Object.values(
+    resolvedAccounts
+  ).sort((a, b) => a.index - b.index);
+
+  // Keys and Signers.
+  const [keys, signers] = getAccountMetasAndSigners(
+    orderedAccounts,
+    'programId',
+    programId
+  );
+
+  // Data.
+  const data = getSetRoleInstructionDataSerializer().serialize(
+    resolvedArgs as SetRoleInstructionDataArgs
+  );
+
+  // Bytes Created On Chain.
+  const bytesCreatedOnChain = 0;
+
+  return transactionBuilder([
+    { instruction: { keys, programId, data }, signers, bytesCreatedOnChain },
+  ]);
+}
@@ -6,26 +6,23 @@
  * @see https://github.com/kinobi-so/kinobi
  */
 
+import { Serializer, array, struct } from '@metaplex-foundation/umi/serializers';
 import {
-  Serializer,
-  bytes,
-  struct,
-  u32,
-} from '@metaplex-foundation/umi/serializers';
+  MsgTypeOptions,
+  MsgTypeOptionsArgs,
+  getMsgTypeOptionsSerializer,
+} from '.';
 
-export type EnforcedOptions = { send: Uint8Array; sendAndCall: Uint8Array };
+export type EnforcedOptions = { entries: Array<MsgTypeOptions> };
 
-export type EnforcedOptionsArgs = EnforcedOptions;
+export type EnforcedOptionsArgs = { entries: Array<MsgTypeOptionsArgs> };
 
 export function getEnforcedOptionsSerializer(): Serializer<
   EnforcedOptionsArgs,
   EnforcedOptions
 > {
   return struct<EnforcedOptions>(
-    [
-      ['send', bytes({ size: u32() })],
-      ['sendAndCall', bytes({ size: u32() })],
-    ],
+    [['entries', array(getMsgTypeOptionsSerializer())]],
     { description: 'EnforcedOptions' }
   ) as Serializer<EnforcedOptionsArgs, EnforcedOptions>;
 }
@@ -14,4 +14,8 @@ export * from './lzAccount';
 export * from './lzReceiveParams';
 export * from './lzReceiveTypesV2Result';
 export * from './messagingFee';
-export * from './peerConfigParam';
\ No newline at end of file
+export * from './msgTypeOptions';
+export * from './outboundMessage';
+export * from './peerConfigParam';
+export * from './rateLimit';
+export * from './role';
\ No newline at end of file
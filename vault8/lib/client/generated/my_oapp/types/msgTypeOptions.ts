@@ -0,0 +1,32 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import {
+  Serializer,
+  bytes,
+  struct,
+  u32,
+  u8,
+} from '@metaplex-foundation/umi/serializers';
+
+export type MsgTypeOptions = { msgType: number; options: Uint8Array };
+
+export type MsgTypeOptionsArgs = MsgTypeOptions;
+
+export function getMsgTypeOptionsSerializer(): Serializer<
+  MsgTypeOptionsArgs,
+  MsgTypeOptions
+> {
+  return struct<MsgTypeOptions>(
+    [
+      ['msgType', u8()],
+      ['options', bytes({ size: u32() })],
+    ],
+    { description: 'MsgTypeOptions' }
+  ) as Serializer<MsgTypeOptionsArgs, MsgTypeOptions>;
+}
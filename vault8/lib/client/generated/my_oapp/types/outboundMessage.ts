@@ -0,0 +1,112 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import {
+  GetDataEnumKind,
+  GetDataEnumKindContent,
+  Serializer,
+  bytes,
+  dataEnum,
+  i64,
+  struct,
+  u64,
+  unit,
+} from '@metaplex-foundation/umi/serializers';
+
+export type OutboundMessage =
+  | {
+      __kind: 'WithdrawComplete';
+      amount: bigint;
+      evmAddress: Uint8Array;
+      guid: Uint8Array;
+    }
+  | { __kind: 'BalanceSnapshot' }
+  | { __kind: 'Ack'; amount: bigint; guid: Uint8Array }
+  | { __kind: 'Nav'; value: bigint; timestamp: bigint; slot: bigint };
+
+export type OutboundMessageArgs =
+  | {
+      __kind: 'WithdrawComplete';
+      amount: number | bigint;
+      evmAddress: Uint8Array;
+      guid: Uint8Array;
+    }
+  | { __kind: 'BalanceSnapshot' }
+  | { __kind: 'Ack'; amount: number | bigint; guid: Uint8Array }
+  | {
+      __kind: 'Nav';
+      value: number | bigint;
+      timestamp: number | bigint;
+      slot: number | bigint;
+    };
+
+export function getOutboundMessageSerializer(): Serializer<
+  OutboundMessageArgs,
+  OutboundMessage
+> {
+  return dataEnum<OutboundMessage>(
+    [
+      [
+        'WithdrawComplete',
+        struct<GetDataEnumKindContent<OutboundMessage, 'WithdrawComplete'>>([
+          ['amount', u64()],
+          ['evmAddress', bytes({ size: 20 })],
+          ['guid', bytes({ size: 32 })],
+        ]),
+      ],
+      ['BalanceSnapshot', unit()],
+      [
+        'Ack',
+        struct<GetDataEnumKindContent<OutboundMessage, 'Ack'>>([
+          ['amount', u64()],
+          ['guid', bytes({ size: 32 })],
+        ]),
+      ],
+      [
+        'Nav',
+        struct<GetDataEnumKindContent<OutboundMessage, 'Nav'>>([
+          ['value', u64()],
+          ['timestamp', i64()],
+          ['slot', u64()],
+        ]),
+      ],
+    ],
+    { description: 'OutboundMessage' }
+  ) as Serializer<OutboundMessageArgs, OutboundMessage>;
+}
+
+// Data Enum Helpers.
+export function outboundMessage(
+  kind: 'WithdrawComplete',
+  data: GetDataEnumKindContent<OutboundMessageArgs, 'WithdrawComplete'>
+): GetDataEnumKind<OutboundMessageArgs, 'WithdrawComplete'>;
+export function outboundMessage(
+  kind: 'BalanceSnapshot'
+): GetDataEnumKind<OutboundMessageArgs, 'BalanceSnapshot'>;
+export function outboundMessage(
+  kind: 'Ack',
+  data: GetDataEnumKindContent<OutboundMessageArgs, 'Ack'>
+): GetDataEnumKind<OutboundMessageArgs, 'Ack'>;
+export function outboundMessage(
+  kind: 'Nav',
+  data: GetDataEnumKindContent<OutboundMessageArgs, 'Nav'>
+): GetDataEnumKind<OutboundMessageArgs, 'Nav'>;
+export function outboundMessage<K extends OutboundMessageArgs['__kind']>(
+  kind: K,
+  data?: any
+): Extract<OutboundMessageArgs, { __kind: K }> {
+  return Array.isArray(data)
+    ? { __kind: kind, fields: data }
+    : { __kind: kind, ...(data ?? {}) };
+}
+export function isOutboundMessage<K extends OutboundMessage['__kind']>(
+  kind: K,
+  value: OutboundMessage
+): value is OutboundMessage & { __kind: K } {
+  return value.__kind === kind;
+}
@@ -15,13 +15,23 @@ import {
   struct,
   tuple,
   u32,
+  u64,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 
 export type PeerConfigParam =
   | { __kind: 'PeerAddress'; fields: [Uint8Array] }
-  | { __kind: 'EnforcedOptions'; send: Uint8Array; sendAndCall: Uint8Array };
+  | { __kind: 'EnforcedOptions'; msgType: number; options: Uint8Array }
+  | { __kind: 'InboundRateLimit'; limit: bigint; window: bigint };
 
-export type PeerConfigParamArgs = PeerConfigParam;
+export type PeerConfigParamArgs =
+  | { __kind: 'PeerAddress'; fields: [Uint8Array] }
+  | { __kind: 'EnforcedOptions'; msgType: number; options: Uint8Array }
+  | {
+      __kind: 'InboundRateLimit';
+      limit: number | bigint;
+      window: number | bigint;
+    };
 
 export function getPeerConfigParamSerializer(): Serializer<
   PeerConfigParamArgs,
@@ -38,8 +48,15 @@ export function getPeerConfigParamSerializer(): Serializer<
       [
         'EnforcedOptions',
         struct<GetDataEnumKindContent<PeerConfigParam, 'EnforcedOptions'>>([
-          ['send', bytes({ size: u32() })],
-          ['sendAndCall', bytes({ size: u32() })],
+          ['msgType', u8()],
+          ['options', bytes({ size: u32() })],
+        ]),
+      ],
+      [
+        'InboundRateLimit',
+        struct<GetDataEnumKindContent<PeerConfigParam, 'InboundRateLimit'>>([
+          ['limit', u64()],
+          ['window', u64()],
         ]),
       ],
     ],
@@ -56,6 +73,10 @@ export function peerConfigParam(
   kind: 'EnforcedOptions',
   data: GetDataEnumKindContent<PeerConfigParamArgs, 'EnforcedOptions'>
 ): GetDataEnumKind<PeerConfigParamArgs, 'EnforcedOptions'>;
+export function peerConfigParam(
+  kind: 'InboundRateLimit',
+  data: GetDataEnumKindContent<PeerConfigParamArgs, 'InboundRateLimit'>
+): GetDataEnumKind<PeerConfigParamArgs, 'InboundRateLimit'>;
 export function peerConfigParam<K extends PeerConfigParamArgs['__kind']>(
   kind: K,
   data?: any
@@ -0,0 +1,40 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import {
+  Serializer,
+  i64,
+  struct,
+  u64,
+} from '@metaplex-foundation/umi/serializers';
+
+export type RateLimit = {
+  limit: bigint;
+  window: bigint;
+  amountInFlight: bigint;
+  lastUpdated: bigint;
+};
+
+export type RateLimitArgs = {
+  limit: number | bigint;
+  window: number | bigint;
+  amountInFlight: number | bigint;
+  lastUpdated: number | bigint;
+};
+
+export function getRateLimitSerializer(): Serializer<RateLimitArgs, RateLimit> {
+  return struct<RateLimit>(
+    [
+      ['limit', u64()],
+      ['window', u64()],
+      ['amountInFlight', u64()],
+      ['lastUpdated', i64()],
+    ],
+    { description: 'RateLimit' }
+  ) as Serializer<RateLimitArgs, RateLimit>;
+}
@@ -0,0 +1,25 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import { Serializer, scalarEnum } from '@metaplex-foundation/umi/serializers';
+
+export enum Role {
+  ConfigAdmin,
+  StrategyKeeper,
+  Pauser,
+  FeeCollector,
+}
+
+export type RoleArgs = Role;
+
+export function getRoleSerializer(): Serializer<RoleArgs, Role> {
+  return scalarEnum<Role>(Role, { description: 'Role' }) as Serializer<
+    RoleArgs,
+    Role
+  >;
+}
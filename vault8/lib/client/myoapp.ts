@@ -32,13 +32,21 @@ import * as errors from './generated/my_oapp/errors'
 import * as instructions from './generated/my_oapp/instructions'
 import * as types from './generated/my_oapp/types'
 import { MyOAppPDA as MyOAppPDA } from './pda'
-import { SetPeerAddressParam, SetPeerEnforcedOptionsParam } from './types'
+import { SetPeerAddressParam, SetPeerEnforcedOptionsParam, SetPeerInboundRateLimitParam } from './types'
 
 export { accounts, errors, instructions, types }
 export { MY_OAPP_PROGRAM_ID } from './generated/my_oapp'
 
 const ENDPOINT_PROGRAM_ID: PublicKey = EndpointProgram.ENDPOINT_PROGRAM_ID
 
+// StrategyKind discriminants, the seed of each StrategyConfig PDA
+export enum StrategyKind {
+    JupiterLend = 0,
+    Kamino = 1,
+    MarginFi = 2,
+    Save = 3,
+}
+
 export enum MessageType {
     VANILLA = 1,
     COMPOSED_TYPE = 2,
@@ -79,10 +87,16 @@ export class MyOApp {
         this.endpointSDK = new EndpointProgram.Endpoint(endpointProgramId)
     }
 
-    async getEnforcedOptions(rpc: RpcInterface, remoteEid: number): Promise<types.EnforcedOptions> {
+    // Enforced options for sends of `msgType` to `remoteEid` (empty when none are set)
+    async getEnforcedOptions(rpc: RpcInterface, remoteEid: number, msgType: number): Promise<Uint8Array> {
         const [peer] = this.pda.peer(remoteEid)
         const peerInfo = await accounts.fetchPeerConfig({ rpc }, peer)
-        return peerInfo.enforcedOptions
+        const entry = peerInfo.enforcedOptions.entries.find((e) => e.msgType === msgType)
+        return entry?.options ?? new Uint8Array(0)
+    }
+
+    async getRoles(rpc: RpcInterface, commitment: Commitment = 'confirmed'): Promise<accounts.Roles | null> {
+        return accounts.safeFetchRoles({ rpc }, this.pda.roles()[0], { commitment })
     }
 
     getProgram(clusterFilter: ClusterFilter = 'custom'): Program {
@@ -94,7 +108,7 @@ export class MyOApp {
         return accounts.safeFetchStore({ rpc }, count, { commitment })
     }
 
-    initStore(payer: Signer, admin: PublicKey): WrappedInstruction {
+    initStore(payer: Signer, admin: PublicKey, orderedNonce = false): WrappedInstruction {
         const [oapp] = this.pda.oapp()
         const remainingAccounts = this.endpointSDK.getRegisterOappIxAccountMetaForCPI(payer.publicKey, oapp)
         return instructions
@@ -102,28 +116,36 @@ export class MyOApp {
                 { payer: payer, programs: this.programRepo },
                 {
                     payer,
+                    programData: this.pda.programData()[0],
                     store: oapp,
+                    lzReceiveTypesAccounts: this.pda.lzReceiveTypesAccounts()[0],
+                    lzComposeTypesAccounts: this.pda.lzComposeTypesAccounts()[0],
+                    roles: this.pda.roles()[0],
                     // args
                     admin: admin,
                     endpoint: this.endpointSDK.programId,
+                    orderedNonce,
                 }
             )
             .addRemainingAccounts(remainingAccounts).items[0]
     }
 
+    // `keeper` (the Roles strategy keeper) must sign unless `message` is a WithdrawComplete;
+    // `userBalance` is the account a BalanceSnapshot reports
     async send(
         rpc: RpcInterface,
         payer: PublicKey,
         params: EndpointProgram.types.MessagingFee & {
             dstEid: number
-            message: string
+            message: types.OutboundMessageArgs
             options: Uint8Array
-            composeMsg?: Uint8Array
+            keeper?: Signer
+            userBalance?: PublicKey
         },
         remainingAccounts?: AccountMeta[],
         commitment: Commitment = 'confirmed'
     ): Promise<WrappedInstruction> {
-        const { dstEid, nativeFee, lzTokenFee, message, options, composeMsg } = params
+        const { dstEid, nativeFee, lzTokenFee, message, options, keeper, userBalance } = params
         const msgLibProgram = await this.getSendLibraryProgram(rpc, payer, dstEid)
         const [oapp] = this.pda.oapp()
         const [peer] = this.pda.peer(dstEid)
@@ -154,10 +176,12 @@ export class MyOApp {
                     store: oapp,
                     peer: peer,
                     endpoint: this.endpointSDK.pda.setting()[0],
+                    keeper,
+                    roles: this.pda.roles()[0],
+                    userBalance,
                     // args
                     dstEid,
                     message,
-                    composeMsg: composeMsg ?? null,
                     options,
                     nativeFee: nativeFee,
                     lzTokenFee: lzTokenFee ?? 0,
@@ -166,28 +190,37 @@ export class MyOApp {
             .addRemainingAccounts(remainingAccounts).items[0]
     }
 
+    // `configAdmin` must hold the config admin role; setting the peer address also creates the
+    // inbound nonce account of the (remote, peer) path
     setPeerConfig(
         accounts: {
-            admin: Signer
+            configAdmin: Signer
         },
-        param: (SetPeerAddressParam | SetPeerEnforcedOptionsParam) & {
+        param: (SetPeerAddressParam | SetPeerEnforcedOptionsParam | SetPeerInboundRateLimitParam) & {
             remote: number
         }
     ): WrappedInstruction {
-        const { admin } = accounts
+        const { configAdmin } = accounts
         const { remote } = param
+        const [store] = this.pda.oapp()
         let config: types.PeerConfigParamArgs
+        let nonceAccount: PublicKey | undefined
         if (param.__kind === 'PeerAddress') {
             if (param.peer.length !== 32) {
                 throw new Error('Peer must be 32 bytes (left-padded with zeroes)')
             }
             config = types.peerConfigParam('PeerAddress', [param.peer])
+            nonceAccount = this.pda.nonce(store, remote, param.peer)[0]
         } else if (param.__kind === 'EnforcedOptions') {
-            config = {
-                __kind: 'EnforcedOptions',
-                send: param.send,
-                sendAndCall: param.sendAndCall,
-            }
+            config = types.peerConfigParam('EnforcedOptions', {
+                msgType: param.msgType,
+                options: param.options,
+            })
+        } else if (param.__kind === 'InboundRateLimit') {
+            config = types.peerConfigParam('InboundRateLimit', {
+                limit: param.limit,
+                window: param.window,
+            })
         } else {
             throw new Error('Invalid peer config')
         }
@@ -195,9 +228,11 @@ export class MyOApp {
         return instructions.setPeerConfig(
             { programs: this.programRepo },
             {
-                admin,
-                store: this.pda.oapp()[0],
+                configAdmin,
+                roles: this.pda.roles()[0],
                 peer: this.pda.peer(remote)[0],
+                nonceAccount,
+                store,
                 // args
                 remoteEid: remote,
                 config,
@@ -205,20 +240,91 @@ export class MyOApp {
         ).items[0]
     }
 
+    // `configAdmin` must hold the config admin role; the JL accounts are checked on-chain against `params`
+    setJlConfig(configAdmin: Signer, params: instructions.SetJlConfigInstructionDataArgs): WrappedInstruction {
+        return instructions.setJlConfig(
+            { programs: this.programRepo },
+            {
+                store: this.pda.oapp()[0],
+                strategy: this.pda.strategy(StrategyKind.JupiterLend)[0],
+                configAdmin,
+                roles: this.pda.roles()[0],
+                ...params,
+            }
+        ).items[0]
+    }
+
+    setAlt(configAdmin: Signer, alt: PublicKey): WrappedInstruction {
+        return instructions.setAlt(
+            { programs: this.programRepo },
+            {
+                store: this.pda.oapp()[0],
+                lzReceiveTypesAccounts: this.pda.lzReceiveTypesAccounts()[0],
+                alt,
+                configAdmin,
+                roles: this.pda.roles()[0],
+            }
+        ).items[0]
+    }
+
+    // First step of an admin transfer; proposing the default pubkey cancels a pending one
+    proposeAdmin(admin: Signer, pendingAdmin: PublicKey): WrappedInstruction {
+        return instructions.proposeAdmin(
+            { programs: this.programRepo },
+            {
+                admin,
+                store: this.pda.oapp()[0],
+                // args
+                pendingAdmin,
+            }
+        ).items[0]
+    }
+
+    // Second step, signed by the proposed admin; the Endpoint delegate moves with it
+    acceptAdmin(pendingAdmin: Signer): WrappedInstruction {
+        const [oapp] = this.pda.oapp()
+        const endpoint = this.endpointSDK.programId
+        // Accounts of Endpoint::set_delegate, led by the Endpoint program the CPI goes to
+        const remainingAccounts: AccountMeta[] = [
+            { pubkey: endpoint, isSigner: false, isWritable: false },
+            { pubkey: oapp, isSigner: false, isWritable: false },
+            { pubkey: this.endpointSDK.pda.oappRegistry(oapp)[0], isSigner: false, isWritable: true },
+            { pubkey: new EventPDA(endpoint).eventAuthority()[0], isSigner: false, isWritable: false },
+            { pubkey: endpoint, isSigner: false, isWritable: false },
+        ]
+        return instructions
+            .acceptAdmin({ programs: this.programRepo }, { pendingAdmin, store: oapp })
+            .addRemainingAccounts(remainingAccounts).items[0]
+    }
+
+    setRole(admin: Signer, role: types.Role, account: PublicKey): WrappedInstruction {
+        return instructions.setRole(
+            { programs: this.programRepo },
+            {
+                admin,
+                store: this.pda.oapp()[0],
+                roles: this.pda.roles()[0],
+                // args
+                role,
+                account,
+            }
+        ).items[0]
+    }
+
     async quote(
         rpc: RpcInterface,
         payer: PublicKey,
         params: {
             dstEid: number
-            message: string
+            message: types.OutboundMessageArgs
             options: Uint8Array
-            composeMsg?: Uint8Array
             payInLzToken: boolean
+            userBalance?: PublicKey
         },
         remainingAccounts?: AccountMeta[],
         commitment: Commitment = 'confirmed'
     ): Promise<EndpointProgram.types.MessagingFee> {
-        const { dstEid, message, options, payInLzToken, composeMsg } = params
+        const { dstEid, message, options, payInLzToken, userBalance } = params
         const msgLibProgram = await this.getSendLibraryProgram(rpc, payer, dstEid)
         const [oapp] = this.pda.oapp()
 
@@ -248,10 +354,10 @@ export class MyOApp {
                     store: oapp,
                     peer,
                     endpoint: this.endpointSDK.pda.setting()[0],
+                    userBalance,
                     // args
                     dstEid,
                     message,
-                    composeMsg: composeMsg ?? null,
                     options,
                     payInLzToken,
                     receiver: packetPath.receiver,
@@ -1,4 +1,4 @@
-import { Pda, PublicKey, publicKeyBytes } from '@metaplex-foundation/umi'
+import { Pda, PublicKey, publicKey, publicKeyBytes } from '@metaplex-foundation/umi'
 import { Endian, u32 } from '@metaplex-foundation/umi/serializers'
 import { createWeb3JsEddsa } from '@metaplex-foundation/umi-eddsa-web3js'
 
@@ -6,9 +6,15 @@ import { OmniAppPDA } from '@layerzerolabs/lz-solana-sdk-v2/umi'
 
 const eddsa = createWeb3JsEddsa()
 
+const BPF_LOADER_UPGRADEABLE_ID = publicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+
 export class MyOAppPDA extends OmniAppPDA {
     static STORE_SEED = 'Store'
     static NONCE_SEED = 'Nonce'
+    static LZ_RECEIVE_TYPES_SEED = 'LzReceiveTypes'
+    static LZ_COMPOSE_TYPES_SEED = 'LzComposeTypes'
+    static ROLES_SEED = 'Roles'
+    static STRATEGY_SEED = 'Strategy'
 
     constructor(public readonly programId: PublicKey) {
         super(programId)
@@ -19,6 +25,39 @@ export class MyOAppPDA extends OmniAppPDA {
         return eddsa.findPda(this.programId, [Buffer.from(MyOAppPDA.STORE_SEED, 'utf8')])
     }
 
+    // seeds = [LZ_RECEIVE_TYPES_SEED, &store.key().as_ref()],
+    lzReceiveTypesAccounts(): Pda {
+        const [store] = this.oapp()
+        return eddsa.findPda(this.programId, [
+            Buffer.from(MyOAppPDA.LZ_RECEIVE_TYPES_SEED, 'utf8'),
+            publicKeyBytes(store),
+        ])
+    }
+
+    // seeds = [LZ_COMPOSE_TYPES_SEED, &store.key().as_ref()],
+    lzComposeTypesAccounts(): Pda {
+        const [store] = this.oapp()
+        return eddsa.findPda(this.programId, [
+            Buffer.from(MyOAppPDA.LZ_COMPOSE_TYPES_SEED, 'utf8'),
+            publicKeyBytes(store),
+        ])
+    }
+
+    // seeds = [ROLES_SEED],
+    roles(): Pda {
+        return eddsa.findPda(this.programId, [Buffer.from(MyOAppPDA.ROLES_SEED, 'utf8')])
+    }
+
+    // seeds = [STRATEGY_SEED, &[kind]], kind being the StrategyKind discriminant (0 = Jupiter Lend)
+    strategy(kind: number): Pda {
+        return eddsa.findPda(this.programId, [Buffer.from(MyOAppPDA.STRATEGY_SEED, 'utf8'), Buffer.from([kind])])
+    }
+
+    // ProgramData account of the upgradeable program, checked by init_store
+    programData(): Pda {
+        return eddsa.findPda(BPF_LOADER_UPGRADEABLE_ID, [publicKeyBytes(this.programId)])
+    }
+
     // seeds = [PEER_SEED, &count.key().to_bytes(), &params.dst_eid.to_be_bytes()],
     peer(dstChainId: number): Pda {
         const [count] = this.oapp()
@@ -29,7 +68,7 @@ export class MyOAppPDA extends OmniAppPDA {
         ])
     }
 
-    // seeds = [NONCE_SEED, &params.receiver, &params.src_eid.to_be_bytes(), &params.sender]
+    // seeds = [NONCE_SEED, &store.key().to_bytes(), &remote_eid.to_be_bytes(), &peer_address]
     nonce(receiver: PublicKey, remoteEid: number, sender: Uint8Array): Pda {
         return eddsa.findPda(this.programId, [
             Buffer.from(MyOAppPDA.NONCE_SEED, 'utf8'),
@@ -4,7 +4,13 @@ export interface SetPeerAddressParam {
 }
 
 export interface SetPeerEnforcedOptionsParam {
-    send: Uint8Array
-    sendAndCall: Uint8Array
+    msgType: number
+    options: Uint8Array
     __kind: 'EnforcedOptions'
 }
+
+export interface SetPeerInboundRateLimitParam {
+    limit: bigint
+    window: bigint
+    __kind: 'InboundRateLimit'
+}
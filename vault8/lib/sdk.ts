@@ -46,11 +46,17 @@ interface SetPeerAddressParam {
 }
 
 interface SetPeerEnforcedOptionsParam {
-    send: Uint8Array
-    sendAndCall: Uint8Array
+    msgType: number
+    options: Uint8Array
     __kind: 'EnforcedOptions'
 }
 
+interface SetPeerInboundRateLimitParam {
+    limit: bigint
+    window: bigint
+    __kind: 'InboundRateLimit'
+}
+
 /*
  * refer to the OFT wrapper SDK in the devtools repo at packages/ua-devtools-solana/src/oft/sdk.ts to understand why this wrapper SDK is needed.
  */
@@ -162,9 +168,7 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
 
         this.logger.debug(`Setting peer for eid ${eid} (${eidLabel}) to address ${peerAsBytes32}`)
         const umiTxs = [
-            await this._createSetPeerAddressIx(normalizedPeer, eid), // admin
-            // this.umiMyOAppSdk.setPeer(delegate, { peer: normalizedPeer, remoteEid: eid }),TODO: remove, since things are now handled by _createSetPeerAddressIx and _setPeerEnforcedOptionsIx
-            await this._setPeerEnforcedOptionsIx(new Uint8Array([0, 3]), new Uint8Array([0, 3]), eid), // admin
+            await this._createSetPeerAddressIx(normalizedPeer, eid), // config admin
             myoapp.initOAppNonce({ admin: delegate, oapp }, eid, normalizedPeer), // delegate
         ]
 
@@ -181,7 +185,7 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
 
         return {
             ...(await this.createTransaction(this._umiToWeb3Tx(umiTxs))),
-            description: `Setting peer for eid ${eid} (${eidLabel}) to address ${peerAsBytes32} ${delegate.publicKey} ${(await this._getAdmin()).publicKey}`,
+            description: `Setting peer for eid ${eid} (${eidLabel}) to address ${peerAsBytes32} ${delegate.publicKey} ${(await this._getConfigAdmin()).publicKey}`,
         }
     }
 
@@ -221,10 +225,9 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
         const eidLabel = `eid ${eid} (${formatEid(eid)})`
         this.logger.verbose(`Getting enforced options for ${eidLabel} and message type ${msgType}`)
         try {
-            const options = await this.umiMyOAppSdk.getEnforcedOptions(this.umi.rpc, eid)
-            const optionsForMsgType = msgType === MSG_TYPE_SEND ? options.send : options.sendAndCall
+            const options = await this.umiMyOAppSdk.getEnforcedOptions(this.umi.rpc, eid, msgType)
 
-            return toHex(optionsForMsgType)
+            return toHex(options)
         } catch (error) {
             if (String(error).match(/was not found at the provided address/)) {
                 return toHex(new Uint8Array(0))
@@ -242,35 +245,30 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
     ): Promise<OmniTransaction> {
         this.logger.verbose(`Setting outbound rate limit for ${eid} to ${printJson(rateLimit)}`)
 
-        return {
-            ...(await this.createTransaction(
-                this._umiToWeb3Tx([await this._setPeerOutboundRateLimit(eid, rateLimit)])
-            )),
-            description: `Setting outbound rate limit for ${eid} to ${printJson(rateLimit)}`,
-        }
+        throw new TypeError(`setOutboundRateLimit() not implemented on Solana OApp SDK`)
     }
 
     async setInboundRateLimit(
         eid: EndpointId,
         rateLimit: { refillPerSecond: bigint; capacity: bigint }
     ): Promise<OmniTransaction> {
-        this.logger.verbose(`Setting outbound rate limit for ${eid} to ${printJson(rateLimit)}`)
+        this.logger.verbose(`Setting inbound rate limit for ${eid} to ${printJson(rateLimit)}`)
 
         return {
-            ...(await this.createTransaction(this._umiToWeb3Tx([await this._setPeerInboundRateLimit(eid, rateLimit)]))),
-            description: `Setting outbound rate limit for ${eid} to ${printJson(rateLimit)}`,
+            ...(await this.createTransaction(this._umiToWeb3Tx([await this._setPeerInboundRateLimitIx(eid, rateLimit)]))),
+            description: `Setting inbound rate limit for ${eid} to ${printJson(rateLimit)}`,
         }
     }
 
     async setEnforcedOptions(enforcedOptions: OAppEnforcedOptionParam[]): Promise<OmniTransaction> {
         this.logger.verbose(`Setting enforced options to ${printJson(enforcedOptions)}`)
         const optionsByEidAndMsgType = this.reduceEnforcedOptions(enforcedOptions)
-        const emptyOptions = Options.newOptions().toBytes()
         const ixs: WrappedInstruction[] = []
         for (const [eid, optionsByMsgType] of optionsByEidAndMsgType) {
-            const sendOption = optionsByMsgType.get(MSG_TYPE_SEND) ?? emptyOptions
-            const sendAndCallOption = optionsByMsgType.get(MSG_TYPE_SEND_AND_CALL) ?? emptyOptions
-            ixs.push(await this._setPeerEnforcedOptionsIx(sendOption, sendAndCallOption, eid))
+            // The program keeps options per message type, so each one is set on its own
+            for (const [msgType, options] of optionsByMsgType) {
+                ixs.push(await this._setPeerEnforcedOptionsIx(msgType, options, eid))
+            }
         }
 
         return {
@@ -366,7 +364,7 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
     private assertMsgType(value: unknown): asserts value is MsgType {
         assert(
             isMsgType(value),
-            `${this.label}: Invalid msgType received: ${value}. Expected one of ${MSG_TYPE_SEND} (send), ${MSG_TYPE_SEND_AND_CALL} (send and call)`
+            `${this.label}: Invalid msgType received: ${value}. Expected a message type between ${MSG_TYPE_MIN} and ${MSG_TYPE_MAX}`
         )
     }
 
@@ -422,9 +420,9 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
     }
 
     protected async _setPeerConfigIx(
-        param: (SetPeerAddressParam & { remote: number }) | (SetPeerEnforcedOptionsParam & { remote: number })
+        param: (SetPeerAddressParam | SetPeerEnforcedOptionsParam | SetPeerInboundRateLimitParam) & { remote: number }
     ) {
-        return this.umiMyOAppSdk.setPeerConfig({ admin: await this._getAdmin() }, param)
+        return this.umiMyOAppSdk.setPeerConfig({ configAdmin: await this._getConfigAdmin() }, param)
     }
 
     protected async _createSetPeerAddressIx(normalizedPeer: Uint8Array, eid: EndpointId) {
@@ -435,11 +433,33 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
         })
     }
 
-    protected async _setPeerEnforcedOptionsIx(send: Uint8Array, sendAndCall: Uint8Array, eid: EndpointId) {
+    protected async _setPeerEnforcedOptionsIx(msgType: MsgType, options: Uint8Array, eid: EndpointId) {
         return this._setPeerConfigIx({
             __kind: 'EnforcedOptions',
-            send,
-            sendAndCall,
+            msgType,
+            options,
+            remote: eid,
+        })
+    }
+
+    // The program limit decays linearly over its window, refilling `capacity / window` per second
+    protected async _setPeerInboundRateLimitIx(
+        eid: EndpointId,
+        rateLimit: { refillPerSecond: bigint; capacity: bigint }
+    ) {
+        const { refillPerSecond, capacity } = rateLimit
+        // A zero capacity disables the limit; otherwise the window is at least a second
+        let window = 0n
+        if (capacity > 0n) {
+            if (refillPerSecond === 0n) {
+                throw new Error(`Inbound rate limit for ${formatEid(eid)} must refill (refillPerSecond > 0)`)
+            }
+            window = capacity / refillPerSecond > 0n ? capacity / refillPerSecond : 1n
+        }
+        return this._setPeerConfigIx({
+            __kind: 'InboundRateLimit',
+            limit: capacity,
+            window,
             remote: eid,
         })
     }
@@ -477,11 +497,22 @@ export class CustomOAppSDK extends OmniSDK implements IOApp {
         const owner = await this.getOwner()
         return createNoopSigner(publicKey(owner))
     }
+
+    // Holder of the config admin role, which signs every peer config update
+    protected async _getConfigAdmin(): Promise<Signer> {
+        const roles = await this.umiMyOAppSdk.getRoles(this.umi.rpc)
+        if (!roles) {
+            throw new Error(`Roles account not found for ${this.label}`)
+        }
+        return createNoopSigner(roles.configAdmin)
+    }
 }
 
-type MsgType = 1 | 2
+// `msg_codec::MsgType` values; enforced options are kept per message type
+type MsgType = number
 
-const MSG_TYPE_SEND = 1 satisfies MsgType
-const MSG_TYPE_SEND_AND_CALL = 2 satisfies MsgType
+const MSG_TYPE_MIN = 1
+const MSG_TYPE_MAX = 9
 
-const isMsgType = (value: unknown): value is MsgType => value === MSG_TYPE_SEND || value === MSG_TYPE_SEND_AND_CALL
+const isMsgType = (value: unknown): value is MsgType =>
+    typeof value === 'number' && Number.isInteger(value) && value >= MSG_TYPE_MIN && value <= MSG_TYPE_MAX
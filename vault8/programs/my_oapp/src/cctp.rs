@@ -0,0 +1,249 @@
+use crate::errors::MyOAppError;
+use crate::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    system_program,
+};
+use oapp::endpoint_cpi::LzAccount;
+
+/// Circle CCTP V2 MessageTransmitter program (same address on devnet and mainnet)
+pub mod message_transmitter {
+    use super::*;
+    declare_id!("CCTPV2Sm4AdWt5296sk4P66VBZ7bEhcARwFaaS9YPbeC");
+}
+
+/// Circle CCTP V2 TokenMessengerMinter program (same address on devnet and mainnet)
+pub mod token_messenger_minter {
+    use super::*;
+    declare_id!("CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe");
+}
+
+/// CCTP domain of Base, where deposits are burned on and withdrawals are burned to
+pub const BASE_DOMAIN: u32 = 6;
+/// `min_finality_threshold` for a standard (finalized, fee-free) transfer
+pub const FINALIZED_THRESHOLD: u32 = 2000;
+
+/// Number of accounts the TokenMessengerMinter `deposit_for_burn` CPI expects (see `deposit_for_burn`)
+pub const DEPOSIT_FOR_BURN_ACCOUNTS_LEN: usize = 18;
+/// TokenMessengerMinterV2 deposit_for_burn discriminator from IDL
+pub const DEPOSIT_FOR_BURN_DISCRIMINATOR: [u8; 8] = [215, 60, 61, 46, 114, 55, 128, 176];
+
+/// MessageTransmitter seed for the per-nonce PDA created by `receive_message`
+pub const USED_NONCE_SEED: &[u8] = b"used_nonce";
+
+/// Identifies one CCTP V2 mint. V2 nonces are bytes32 values assigned off-chain by the attester
+/// (`depositForBurn` returns nothing), so the EVM relayer records the nonce from the attested
+/// message and the deposit payload carries it together with the message's source domain.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CctpNonce {
+    pub source_domain: u32,        // CCTP domain the USDC was burned on
+    pub nonce: [u8; 32],           // Attested V2 message nonce
+}
+
+/// MessageTransmitter `used_nonce` PDA. It only exists once the mint for `nonce` has landed.
+/// V2 nonces are unique across source domains, so MessageTransmitter seeds on the nonce alone.
+pub fn used_nonce_address(nonce: &CctpNonce) -> Pubkey {
+    Pubkey::find_program_address(&[USED_NONCE_SEED, &nonce.nonce], &message_transmitter::ID).0
+}
+
+/// Deposits are only backed by mints burned on Base.
+pub fn check_source_domain(nonce: &CctpNonce) -> Result<()> {
+    require!(nonce.source_domain == BASE_DOMAIN, MyOAppError::InvalidCctpDomain);
+    Ok(())
+}
+
+/// CCTP `mint_recipient` for an EVM address: the 20 bytes left-padded to bytes32.
+pub fn evm_mint_recipient(evm_address: &[u8; 20]) -> [u8; 32] {
+    let mut recipient = [0u8; 32];
+    recipient[12..].copy_from_slice(evm_address);
+    recipient
+}
+
+/// True once MessageTransmitter has created `used_nonce`, i.e. the mint has been received.
+pub fn is_nonce_used(used_nonce: &AccountInfo) -> bool {
+    *used_nonce.owner == message_transmitter::ID && !used_nonce.data_is_empty()
+}
+
+#[derive(AnchorSerialize)]
+struct DepositForBurnParams {
+    amount: u64,
+    destination_domain: u32,
+    mint_recipient: Pubkey,
+    destination_caller: Pubkey,
+    max_fee: u64,
+    min_finality_threshold: u32,
+}
+
+fn token_messenger_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &token_messenger_minter::ID).0
+}
+
+/// Burns `amount` USDC from the Store USDC ATA towards `mint_recipient` on Base.
+/// `event_data_seeds` sign for the fresh `message_sent_event_data` PDA MessageTransmitter creates.
+///
+/// `burn_accounts` must be in the order resolved by `lz_receive_types`:
+/// 0: owner (store PDA, signer via invoke_signed)
+/// 1: eventRentPayer (Executor)
+/// 2: senderAuthorityPda
+/// 3: burnTokenAccount (USDC ATA)
+/// 4: denylistAccount
+/// 5: messageTransmitter
+/// 6: tokenMessenger
+/// 7: remoteTokenMessenger
+/// 8: tokenMinter
+/// 9: localToken
+/// 10: burnTokenMint (USDC)
+/// 11: messageSentEventData
+/// 12: messageTransmitterProgram
+/// 13: tokenMessengerMinterProgram
+/// 14: tokenProgram
+/// 15: systemProgram
+/// 16: eventAuthority
+/// 17: program (tokenMessengerMinterProgram, for event CPI)
+pub fn deposit_for_burn(
+    store: &Store,
+    burn_accounts: &[AccountInfo],
+    amount: u64,
+    mint_recipient: [u8; 32],
+    event_data_seeds: &[&[u8]],
+) -> Result<()> {
+    require!(burn_accounts.len() == DEPOSIT_FOR_BURN_ACCOUNTS_LEN, MyOAppError::InvalidAccount);
+
+    // Verify mint and programs
+    require_keys_eq!(burn_accounts[10].key(), store.usdc_mint, MyOAppError::InvalidAccount);
+    require_keys_eq!(burn_accounts[12].key(), message_transmitter::ID, MyOAppError::InvalidAccount);
+    require_keys_eq!(burn_accounts[13].key(), token_messenger_minter::ID, MyOAppError::InvalidAccount);
+    require_keys_eq!(burn_accounts[14].key(), store.token_program, MyOAppError::InvalidAccount);
+    require_keys_eq!(burn_accounts[17].key(), token_messenger_minter::ID, MyOAppError::InvalidAccount);
+
+    let mut data: Vec<u8> = DEPOSIT_FOR_BURN_DISCRIMINATOR.to_vec();
+    DepositForBurnParams {
+        amount,
+        destination_domain: BASE_DOMAIN,
+        mint_recipient: Pubkey::new_from_array(mint_recipient),
+        // Anyone may relay the attested message on Base
+        destination_caller: Pubkey::default(),
+        max_fee: 0,
+        min_finality_threshold: FINALIZED_THRESHOLD,
+    }
+    .serialize(&mut data)?;
+
+    let metas = vec![
+        AccountMeta::new_readonly(burn_accounts[0].key(), true),  // owner
+        AccountMeta::new(burn_accounts[1].key(), true),           // eventRentPayer
+        AccountMeta::new_readonly(burn_accounts[2].key(), false), // senderAuthorityPda
+        AccountMeta::new(burn_accounts[3].key(), false),          // burnTokenAccount
+        AccountMeta::new_readonly(burn_accounts[4].key(), false), // denylistAccount
+        AccountMeta::new(burn_accounts[5].key(), false),          // messageTransmitter
+        AccountMeta::new_readonly(burn_accounts[6].key(), false), // tokenMessenger
+        AccountMeta::new_readonly(burn_accounts[7].key(), false), // remoteTokenMessenger
+        AccountMeta::new_readonly(burn_accounts[8].key(), false), // tokenMinter
+        AccountMeta::new(burn_accounts[9].key(), false),          // localToken
+        AccountMeta::new(burn_accounts[10].key(), false),         // burnTokenMint
+        AccountMeta::new(burn_accounts[11].key(), true),          // messageSentEventData
+        AccountMeta::new_readonly(burn_accounts[12].key(), false), // messageTransmitterProgram
+        AccountMeta::new_readonly(burn_accounts[13].key(), false), // tokenMessengerMinterProgram
+        AccountMeta::new_readonly(burn_accounts[14].key(), false), // tokenProgram
+        AccountMeta::new_readonly(burn_accounts[15].key(), false), // systemProgram
+        AccountMeta::new_readonly(burn_accounts[16].key(), false), // eventAuthority
+        AccountMeta::new_readonly(burn_accounts[17].key(), false), // program
+    ];
+
+    let ix = Instruction { program_id: token_messenger_minter::ID, accounts: metas, data };
+    let store_seeds: &[&[u8]] = &[STORE_SEED, &[store.bump]];
+    invoke_signed(&ix, burn_accounts, &[store_seeds, event_data_seeds])?;
+    Ok(())
+}
+
+/// Accounts for `deposit_for_burn`, in CPI order, as returned by `lz_receive_types`.
+/// The event rent payer is the Executor payer sentinel (`Pubkey::default()`, signer).
+pub fn deposit_for_burn_accounts(
+    store: &Account<Store>,
+    message_sent_event_data: Pubkey,
+) -> Vec<LzAccount> {
+    let s = store;
+    let domain = BASE_DOMAIN.to_string();
+    let usdc_ata = s.associated_token_address(&s.key(), &s.usdc_mint);
+    let message_transmitter =
+        Pubkey::find_program_address(&[b"message_transmitter"], &message_transmitter::ID).0;
+    vec![
+        LzAccount { pubkey: s.key(), is_signer: false, is_writable: false },
+        LzAccount { pubkey: Pubkey::default(), is_signer: true, is_writable: true },
+        LzAccount { pubkey: token_messenger_pda(&[b"sender_authority"]), is_signer: false, is_writable: false },
+        LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+        LzAccount {
+            pubkey: token_messenger_pda(&[b"denylist_account", &s.key().to_bytes()]),
+            is_signer: false,
+            is_writable: false,
+        },
+        LzAccount { pubkey: message_transmitter, is_signer: false, is_writable: true },
+        LzAccount { pubkey: token_messenger_pda(&[b"token_messenger"]), is_signer: false, is_writable: false },
+        LzAccount {
+            pubkey: token_messenger_pda(&[b"remote_token_messenger", domain.as_bytes()]),
+            is_signer: false,
+            is_writable: false,
+        },
+        LzAccount { pubkey: token_messenger_pda(&[b"token_minter"]), is_signer: false, is_writable: false },
+        LzAccount {
+            pubkey: token_messenger_pda(&[b"local_token", &s.usdc_mint.to_bytes()]),
+            is_signer: false,
+            is_writable: true,
+        },
+        LzAccount { pubkey: s.usdc_mint, is_signer: false, is_writable: true },
+        LzAccount { pubkey: message_sent_event_data, is_signer: false, is_writable: true },
+        LzAccount { pubkey: message_transmitter::ID, is_signer: false, is_writable: false },
+        LzAccount { pubkey: token_messenger_minter::ID, is_signer: false, is_writable: false },
+        LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+        LzAccount { pubkey: system_program::ID, is_signer: false, is_writable: false },
+        LzAccount { pubkey: token_messenger_pda(&[b"__event_authority"]), is_signer: false, is_writable: false },
+        LzAccount { pubkey: token_messenger_minter::ID, is_signer: false, is_writable: false },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Header of the devnet Base Sepolia -> Solana receive recorded in cctp-attestation.json:
+    // [version:4][source_domain:4][destination_domain:4][nonce:32], big-endian
+    const DEVNET_MESSAGE_HEADER: &str = "000000010000000600000005\
+        a94c6e3c81dcf08263acf8f62032d48696df2c63acb36a70e1359c5ef9514c87";
+
+    fn devnet_nonce() -> CctpNonce {
+        let header: Vec<u8> = (0..DEVNET_MESSAGE_HEADER.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&DEVNET_MESSAGE_HEADER[i..i + 2], 16).unwrap())
+            .collect();
+        CctpNonce {
+            source_domain: u32::from_be_bytes(header[4..8].try_into().unwrap()),
+            nonce: header[12..44].try_into().unwrap(),
+        }
+    }
+
+    #[test]
+    fn used_nonce_address_matches_devnet_receive() {
+        let nonce = devnet_nonce();
+        assert_eq!(nonce.source_domain, BASE_DOMAIN);
+        assert_eq!(
+            used_nonce_address(&nonce),
+            "EnawkRTGXHwfCqXSkaareZvU1fNuiDsn7PxYwHuiAVS9".parse::<Pubkey>().unwrap()
+        );
+    }
+
+    #[test]
+    fn source_domain_must_be_base() {
+        let mut nonce = devnet_nonce();
+        assert!(check_source_domain(&nonce).is_ok());
+        nonce.source_domain = 0;
+        assert!(check_source_domain(&nonce).is_err());
+    }
+
+    #[test]
+    fn evm_mint_recipient_left_pads() {
+        let vault = [0xab; 20];
+        let recipient = evm_mint_recipient(&vault);
+        assert_eq!(recipient[..12], [0u8; 12]);
+        assert_eq!(recipient[12..], vault);
+    }
+}
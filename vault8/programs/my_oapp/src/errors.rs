@@ -5,4 +5,43 @@ pub enum MyOAppError {
     InvalidMessageType,
     Overflow,
     InvalidAccount,
+    CctpNonceNotUsed,
+    CctpNonceAlreadyConsumed,
+    InsufficientStoreBalance,
+    InsufficientUserBalance,
+    ZeroAmount,
+    DepositNotCredited,
+    DepositAlreadyAcked,
+    InvalidNonce,
+    InvalidStrategyConfig,
+    InvalidStrategyWeights,
+    SlippageExceeded,
+    InvalidIdleTarget,
+    NotUpgradeAuthority,
+    InvalidEndpoint,
+    Paused,
+    PeerInboundPaused,
+    PeerOutboundPaused,
+    InboundRateLimitExceeded,
+    InvalidRateLimit,
+    UserDepositCapExceeded,
+    TvlCapExceeded,
+    ConfigTimelocked,
+    ConfigChangeNotReady,
+    InvalidConfigDelay,
+    InvalidJlAccountOwner,
+    JlFTokenMintMismatch,
+    JlUnderlyingMintMismatch,
+    AccountAlreadyMigrated,
+    InvalidEnforcedOptions,
+    UnauthorizedSender,
+    InvalidNavReportInterval,
+    NavReportTooSoon,
+    InsufficientVaultShares,
+    VaultMismatch,
+    JlConfigRequired,
+    InvalidCctpDomain,
+    LegacyDepositsExceeded,
+    LzReceiveTypesV2Required,
+    ZeroShares,
 }
@@ -0,0 +1,33 @@
+use crate::*;
+use oapp::endpoint::instructions::SetDelegateParams;
+
+/// Second step of an admin transfer, signed by the proposed admin. The Endpoint delegate moves
+/// with it; remaining accounts are those of `Endpoint::set_delegate`.
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(address = store.pending_admin)]
+    pub pending_admin: Signer<'info>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl AcceptAdmin<'_> {
+    pub fn apply(ctx: &mut Context<AcceptAdmin>) -> Result<()> {
+        let store = &mut ctx.accounts.store;
+        let previous_admin = store.admin;
+        store.admin = store.pending_admin;
+        store.pending_admin = Pubkey::default();
+
+        let seeds: &[&[u8]] = &[STORE_SEED, &[store.bump]];
+        oapp::endpoint_cpi::set_delegate(
+            store.endpoint_program,
+            store.key(),
+            ctx.remaining_accounts,
+            seeds,
+            SetDelegateParams { delegate: store.admin },
+        )?;
+
+        emit!(AdminTransferredEvent { previous_admin, admin: store.admin });
+        Ok(())
+    }
+}
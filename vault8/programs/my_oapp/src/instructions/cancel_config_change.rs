@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Aborts a queued config change and closes it back to the config admin.
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(
+        mut,
+        close = config_admin,
+        seeds = [CONFIG_CHANGE_SEED, &config_change.id.to_be_bytes()],
+        bump = config_change.bump
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+}
+
+impl CancelConfigChange<'_> {
+    pub fn apply(ctx: &mut Context<CancelConfigChange>) -> Result<()> {
+        emit!(ConfigChangeCancelledEvent {
+            id: ctx.accounts.config_change.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::errors::MyOAppError;
+use crate::instructions::{set_jl_config::write_jl_config, set_peer_config::write_peer_config};
+use crate::instructions::set_strategy_config::{check_generic_kind, write_config};
+use crate::instructions::migrate_store::resize_account;
+use crate::*;
+
+/// Applies a queued config change once its ETA has passed and closes it back to the config admin.
+/// Only the accounts the update writes are passed; the others are omitted (program ID):
+/// - `PeerConfig`: `peer`, plus `nonce_account` when setting the peer address
+/// - `JlConfig` / `StrategyConfig` / `KaminoConfig` / `MarginfiConfig` / `SaveConfig`: `strategy`
+///
+/// A `JlConfig` update is validated again against the live JL accounts, passed as remaining
+/// accounts in the order `jupiter_lend::validate_config` takes, as they may have changed since it
+/// was queued.
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(
+        mut,
+        close = config_admin,
+        seeds = [CONFIG_CHANGE_SEED, &config_change.id.to_be_bytes()],
+        bump = config_change.bump
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = PeerConfig::MIN_SIZE,
+        seeds = [PEER_SEED, &store.key().to_bytes(), &config_change.update.remote_eid()?.to_be_bytes()],
+        bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = Nonce::SIZE,
+        seeds = [
+            NONCE_SEED,
+            &store.key().to_bytes(),
+            &config_change.update.remote_eid()?.to_be_bytes(),
+            &config_change.update.peer_address()?
+        ],
+        bump
+    )]
+    pub nonce_account: Option<Account<'info, Nonce>>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = StrategyConfig::SIZE,
+        seeds = [STRATEGY_SEED, &config_change.update.strategy_seed()?],
+        bump
+    )]
+    pub strategy: Option<Account<'info, StrategyConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ExecuteConfigChange<'_> {
+    pub fn apply(ctx: &mut Context<ExecuteConfigChange>) -> Result<()> {
+        let change = &ctx.accounts.config_change;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= change.eta, MyOAppError::ConfigChangeNotReady);
+
+        match &change.update {
+            ConfigUpdate::JlConfig(params) => {
+                jupiter_lend::validate_config(params, ctx.remaining_accounts)?;
+                let strategy = ctx.accounts.strategy.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+                write_jl_config(&mut ctx.accounts.store, strategy, ctx.bumps.strategy, params)?;
+            },
+            ConfigUpdate::PeerConfig(params) => {
+                let peer = ctx.accounts.peer.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+                write_peer_config(
+                    peer,
+                    ctx.bumps.peer,
+                    ctx.accounts.nonce_account.as_deref_mut(),
+                    ctx.bumps.nonce_account,
+                    &params.config,
+                )?;
+                let space = peer.space()?;
+                resize_account(
+                    &peer.to_account_info(),
+                    &ctx.accounts.config_admin.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    space,
+                )?;
+            },
+            ConfigUpdate::StrategyConfig(params) => {
+                // Also covers changes queued before Jupiter Lend was rejected at queue time
+                check_generic_kind(params)?;
+                let strategy = ctx.accounts.strategy.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+                write_config(strategy, params, ctx.bumps.strategy)?;
+            },
+            ConfigUpdate::KaminoConfig(params) => {
+                let strategy = ctx.accounts.strategy.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+                write_config(strategy, &params.strategy_params(), ctx.bumps.strategy)?;
+            },
+            ConfigUpdate::MarginfiConfig(params) => {
+                let strategy = ctx.accounts.strategy.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+                write_config(strategy, &params.strategy_params(), ctx.bumps.strategy)?;
+            },
+            ConfigUpdate::SaveConfig(params) => {
+                let strategy = ctx.accounts.strategy.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+                write_config(strategy, &params.strategy_params(), ctx.bumps.strategy)?;
+            },
+            ConfigUpdate::ConfigDelay(delay) => {
+                require!(*delay >= 0, MyOAppError::InvalidConfigDelay);
+                ctx.accounts.store.config_delay = *delay;
+            },
+        }
+
+        emit!(ConfigChangeExecutedEvent { id: change.id, timestamp: now });
+        Ok(())
+    }
+}
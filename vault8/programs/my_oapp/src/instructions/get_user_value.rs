@@ -0,0 +1,33 @@
+use crate::*;
+
+/// Read-only view returning what a user's vault shares from one source chain are worth in USDC base
+/// units, priced across the allocated strategies (for Jupiter Lend, the `token_exchange_price` as
+/// of the lending account's last update). Remaining accounts are one pricing leg per allocated strategy, in
+/// `StrategyKind` order: its config PDA followed by the venue's price account.
+#[derive(Accounts)]
+pub struct GetUserValue<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(
+        seeds = [
+            USER_BALANCE_SEED,
+            &user_balance.src_eid.to_be_bytes(),
+            &user_balance.evm_address
+        ],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+}
+
+impl GetUserValue<'_> {
+    pub fn apply(ctx: &Context<GetUserValue>) -> Result<u64> {
+        let store = &ctx.accounts.store;
+        if store.total_shares == 0 {
+            return Ok(0);
+        }
+        let total_assets = strategy::total_assets(store, ctx.remaining_accounts)?;
+        let value = (ctx.accounts.user_balance.shares as u128) * (total_assets as u128)
+            / (store.total_shares as u128);
+        Ok(u64::try_from(value).map_err(|_| errors::MyOAppError::Overflow)?)
+    }
+}
@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Read-only view returning what an EVM vault's position on one source chain is worth in USDC base
+/// units, priced like `get_user_value`. Remaining accounts are one pricing leg per allocated
+/// strategy, in `StrategyKind` order: its config PDA followed by the venue's price account.
+#[derive(Accounts)]
+pub struct GetVaultValue<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(
+        seeds = [
+            VAULT_POSITION_SEED,
+            &vault_position.src_eid.to_be_bytes(),
+            &vault_position.vault
+        ],
+        bump = vault_position.bump
+    )]
+    pub vault_position: Account<'info, VaultPosition>,
+}
+
+impl GetVaultValue<'_> {
+    pub fn apply(ctx: &Context<GetVaultValue>) -> Result<u64> {
+        let store = &ctx.accounts.store;
+        if store.total_shares == 0 {
+            return Ok(0);
+        }
+        let total_assets = strategy::total_assets(store, ctx.remaining_accounts)?;
+        let value = (ctx.accounts.vault_position.shares as u128) * (total_assets as u128)
+            / (store.total_shares as u128);
+        Ok(u64::try_from(value).map_err(|_| errors::MyOAppError::Overflow)?)
+    }
+}
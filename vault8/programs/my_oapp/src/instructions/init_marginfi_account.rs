@@ -0,0 +1,67 @@
+use crate::errors::MyOAppError;
+use crate::strategy::StrategyKind;
+use crate::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+/// Creates the Store's `MarginfiAccount` in the configured group, with the Store PDA as its
+/// authority. The account is a PDA of this program so both it and the Store can sign the CPI.
+/// Call once after `set_marginfi_config`, before giving MarginFi a weight.
+#[derive(Accounts)]
+pub struct InitMarginfiAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(seeds = [STRATEGY_SEED, &StrategyKind::MarginFi.seed()], bump = strategy.bump)]
+    pub strategy: Account<'info, StrategyConfig>,
+    /// CHECK: Store's MarginfiAccount (created by MarginFi)
+    #[account(
+        mut,
+        seeds = [MARGINFI_ACCOUNT_SEED, &strategy.account(marginfi::GROUP).to_bytes()],
+        bump
+    )]
+    pub marginfi_account: UncheckedAccount<'info>,
+    /// CHECK: MarginFi group from the strategy config
+    #[account(address = strategy.account(marginfi::GROUP) @ MyOAppError::InvalidAccount)]
+    pub marginfi_group: UncheckedAccount<'info>,
+    /// CHECK: MarginFi program from the strategy config
+    #[account(address = strategy.program_id @ MyOAppError::InvalidAccount)]
+    pub marginfi_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitMarginfiAccount<'_> {
+    pub fn apply(ctx: &Context<InitMarginfiAccount>) -> Result<()> {
+        let ix = Instruction {
+            program_id: ctx.accounts.marginfi_program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(ctx.accounts.marginfi_group.key(), false),
+                AccountMeta::new(ctx.accounts.marginfi_account.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.store.key(), true),
+                AccountMeta::new(ctx.accounts.payer.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            ],
+            data: marginfi::INITIALIZE_ACCOUNT_DISCRIMINATOR.to_vec(),
+        };
+        let group = ctx.accounts.marginfi_group.key().to_bytes();
+        let store_seeds: &[&[u8]] = &[STORE_SEED, &[ctx.accounts.store.bump]];
+        let account_seeds: &[&[u8]] =
+            &[MARGINFI_ACCOUNT_SEED, &group, &[ctx.bumps.marginfi_account]];
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.marginfi_group.to_account_info(),
+                ctx.accounts.marginfi_account.to_account_info(),
+                ctx.accounts.store.to_account_info(),
+                ctx.accounts.payer.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.marginfi_program.to_account_info(),
+            ],
+            &[store_seeds, account_seeds],
+        )?;
+        Ok(())
+    }
+}
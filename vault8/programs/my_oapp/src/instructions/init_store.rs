@@ -1,3 +1,5 @@
+use crate::errors::MyOAppError;
+use crate::strategy::{StrategyKind, BPS_DENOMINATOR};
 use crate::*;
 use anchor_lang::solana_program::address_lookup_table::program::ID as ALT_PROGRAM_ID;
 use oapp::endpoint::{instructions::RegisterOAppParams, ID as ENDPOINT_ID};
@@ -7,6 +9,15 @@ use oapp::endpoint::{instructions::RegisterOAppParams, ID as ENDPOINT_ID};
 pub struct InitStore<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ MyOAppError::InvalidAccount
+    )]
+    pub program: Program<'info, crate::program::MyOapp>,
+    /// ProgramData of this program; only its upgrade authority may initialize the Store
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @ MyOAppError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
     #[account(
         init,
         payer = payer,
@@ -23,19 +34,40 @@ pub struct InitStore<'info> {
         bump
     )]
     pub lz_receive_types_accounts: Account<'info, LzReceiveTypesAccounts>,
+    #[account(
+        init,
+        payer = payer,
+        space = LzComposeTypesAccounts::SIZE,
+        seeds = [LZ_COMPOSE_TYPES_SEED, store.key().as_ref()],
+        bump
+    )]
+    pub lz_compose_types_accounts: Account<'info, LzComposeTypesAccounts>,
+    #[account(
+        init,
+        payer = payer,
+        space = Roles::SIZE,
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Account<'info, Roles>,
     #[account(owner = ALT_PROGRAM_ID)]
     pub alt: Option<UncheckedAccount<'info>>,
     pub system_program: Program<'info, System>,
 }
 
-// NOTE: This example init_store may be front-run. It can be called by anyone, and can only be called once.
-// The first caller will be able to set the admin and endpoint program for the store. If front-runned, the program will need to be redeployed and re-initialized with the correct parameters.
-// You should modify this instruction accordingly for your use case with the appropriate access control and checks.
+// init_store can only be called once, and only by the program's upgrade authority (checked through
+// the BPF upgradeable loader ProgramData account), so it cannot be front-run after deployment.
 impl InitStore<'_> {
     pub fn apply(ctx: &mut Context<InitStore>, params: &InitStoreParams) -> Result<()> {
+        require_keys_eq!(params.endpoint, ENDPOINT_ID, MyOAppError::InvalidEndpoint);
         ctx.accounts.store.admin = params.admin;
         ctx.accounts.store.bump = ctx.bumps.store;
+        ctx.accounts.store.version = Store::VERSION;
         ctx.accounts.store.endpoint_program = params.endpoint;
+        ctx.accounts.store.ordered_nonce = params.ordered_nonce;
+        // Everything goes to Jupiter Lend until the admin sets other weights
+        ctx.accounts.store.allocation_mut(StrategyKind::JupiterLend).weight_bps =
+            BPS_DENOMINATOR as u16;
 
         // Prepare the delegate address for the OApp registration.
         let register_params = RegisterOAppParams { delegate: ctx.accounts.store.admin };
@@ -56,6 +88,13 @@ impl InitStore<'_> {
         ctx.accounts.lz_receive_types_accounts.alt = ctx.accounts.alt.as_ref().map(|a| a.key()).unwrap_or_default();
         ctx.accounts.lz_receive_types_accounts.bump = ctx.bumps.lz_receive_types_accounts;
 
+        // Initialize LzComposeTypesAccounts PDA for composed deposits
+        ctx.accounts.lz_compose_types_accounts.store = ctx.accounts.store.key();
+        ctx.accounts.lz_compose_types_accounts.bump = ctx.bumps.lz_compose_types_accounts;
+
+        // Every role starts with the admin until reassigned with `set_role`
+        ctx.accounts.roles.init(params.admin, ctx.bumps.roles);
+
         Ok(())
     }
 }
@@ -64,4 +103,5 @@ impl InitStore<'_> {
 pub struct InitStoreParams {
     pub admin: Pubkey,
     pub endpoint: Pubkey,
+    pub ordered_nonce: bool,
 }
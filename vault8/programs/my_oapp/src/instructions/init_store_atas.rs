@@ -1,12 +1,9 @@
 use crate::*;
 use crate::errors::MyOAppError;
-use anchor_lang::solana_program::{
-    program::invoke,
-    system_instruction,
-};
+use anchor_lang::solana_program::program::invoke;
 
-/// Initialize Store's USDC and fToken ATAs
-/// This should be called once after Store initialization, before accepting deposits
+/// Initialize Store's USDC and strategy share (fToken, cToken) ATAs
+/// This should be called once per strategy with a share mint, before it receives deposits
 #[derive(Accounts)]
 pub struct InitStoreAtas<'info> {
     #[account(mut)]
@@ -14,6 +11,14 @@ pub struct InitStoreAtas<'info> {
     
     #[account(seeds = [STORE_SEED], bump = store.bump)]
     pub store: Account<'info, Store>,
+
+    /// Config PDA of the strategy whose share ATA is created
+    #[account(
+        seeds = [STRATEGY_SEED, &strategy.kind.seed()],
+        bump = strategy.bump,
+        constraint = strategy.share_mint != Pubkey::default() @ MyOAppError::InvalidStrategyConfig
+    )]
+    pub strategy: Account<'info, StrategyConfig>,
     
     /// CHECK: Store's USDC ATA (will be created if needed)
     #[account(mut)]
@@ -26,7 +31,7 @@ pub struct InitStoreAtas<'info> {
     /// CHECK: USDC mint (from Store config)
     pub usdc_mint: UncheckedAccount<'info>,
     
-    /// CHECK: fToken mint (from Store config)
+    /// CHECK: fToken mint (share mint from the strategy config)
     pub ftoken_mint: UncheckedAccount<'info>,
     
     /// CHECK: Token program
@@ -56,7 +61,7 @@ impl InitStoreAtas<'_> {
         let ftoken_ata_seeds: &[&[u8]] = &[
             &store.key().to_bytes(),
             &store.token_program.to_bytes(),
-            &store.jl_f_token_mint.to_bytes(),
+            &ctx.accounts.strategy.share_mint.to_bytes(),
         ];
         let (expected_ftoken_ata, _) = Pubkey::find_program_address(
             ftoken_ata_seeds,
@@ -113,7 +118,7 @@ impl InitStoreAtas<'_> {
                     AccountMeta::new(ctx.accounts.payer.key(), true),
                     AccountMeta::new(ctx.accounts.store_ftoken_ata.key(), false),
                     AccountMeta::new_readonly(store.key(), false),
-                    AccountMeta::new_readonly(store.jl_f_token_mint, false),
+                    AccountMeta::new_readonly(ctx.accounts.strategy.share_mint, false),
                     AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
                     AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
                 ],
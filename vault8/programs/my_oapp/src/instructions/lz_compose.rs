@@ -0,0 +1,203 @@
+use crate::*;
+use crate::errors::MyOAppError;
+use crate::instructions::settle_pending_deposit::{settle, Depositor};
+use oapp::{
+    endpoint::{instructions::ClearComposeParams, ID as ENDPOINT_ID},
+    endpoint_cpi::get_accounts_for_clear_compose,
+    LzComposeParams,
+};
+
+/// Executes the compose action of a `DepositAndCall`, queued through `Endpoint::send_compose` once
+/// the deposit is credited: by `lz_receive`, or by `settle_pending_deposit` if it was parked.
+/// Accounts are resolved by `lz_compose_types`; remaining accounts are the
+/// `Endpoint::clear_compose` accounts, followed by the strategy deposit legs for `Allocate`.
+#[derive(Accounts)]
+#[instruction(params: LzComposeParams)]
+pub struct LzCompose<'info> {
+    /// OApp Store PDA. Only composes queued by this OApp's own `lz_receive` are accepted.
+    #[account(
+        mut,
+        seeds = [STORE_SEED],
+        bump = store.bump,
+        constraint = params.from == store.key() @ MyOAppError::InvalidAccount,
+        constraint = !store.paused @ MyOAppError::Paused
+    )]
+    pub store: Account<'info, Store>,
+    /// UserBalance PDA of the depositor, created by `lz_receive`
+    #[account(
+        mut,
+        seeds = [
+            USER_BALANCE_SEED,
+            &msg_codec::ComposeMsg::decode(&params.message)?.src_eid.to_be_bytes(),
+            &msg_codec::ComposeMsg::decode(&params.message)?.evm_address
+        ],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+    /// Receipt of the deposit this compose belongs to (same GUID)
+    #[account(
+        mut,
+        seeds = [DEPOSIT_RECEIPT_SEED, &params.guid],
+        bump = deposit_receipt.bump
+    )]
+    pub deposit_receipt: Account<'info, DepositReceipt>,
+    /// VaultPosition PDA of the depositing vault on the source chain. `Allocate` only.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultPosition::SIZE,
+        seeds = [
+            VAULT_POSITION_SEED,
+            &msg_codec::ComposeMsg::decode(&params.message)?.src_eid.to_be_bytes(),
+            &msg_codec::ComposeMsg::decode(&params.message)?.vault
+        ],
+        bump
+    )]
+    pub vault_position: Option<Account<'info, VaultPosition>>,
+    /// CHECK: Pending deposit PDA keyed by GUID. Only exists if `lz_receive` parked the deposit,
+    /// so it is deserialized in `apply`. `Allocate` only.
+    #[account(mut, seeds = [PENDING_DEPOSIT_SEED, &params.guid], bump)]
+    pub pending_deposit: Option<UncheckedAccount<'info>>,
+    /// CHECK: Circle MessageTransmitter `used_nonce` PDA for the deposit's CCTP nonce. `Allocate` only.
+    #[account(address = cctp::used_nonce_address(&msg_codec::ComposeMsg::decode(&params.message)?.cctp_nonce))]
+    pub cctp_used_nonce: Option<UncheckedAccount<'info>>,
+    /// CHECK: Receives the pending deposit rent back; must match `PendingDeposit.rent_payer`.
+    /// `Allocate` only.
+    #[account(mut)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+    /// Peer the deposit came from, charged against its inbound rate limit. `Allocate` only.
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            &store.key().to_bytes(),
+            &msg_codec::ComposeMsg::decode(&params.message)?.src_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+    /// Referral PDA of the depositor. `RegisterReferrer` only.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Referral::SIZE,
+        seeds = [REFERRAL_SEED, &msg_codec::ComposeMsg::decode(&params.message)?.evm_address],
+        bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+    /// Payer for VaultPosition and Referral PDA creation (Executor)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl LzCompose<'_> {
+    pub fn apply(ctx: &mut Context<LzCompose>, params: &LzComposeParams) -> Result<()> {
+        let store_key = ctx.accounts.store.key();
+        let seeds: &[&[u8]] = &[STORE_SEED, &[ctx.accounts.store.bump]];
+
+        // The Executor appends the Endpoint::clear_compose accounts to remaining_accounts first
+        let clear_len = get_accounts_for_clear_compose(
+            ENDPOINT_ID,
+            &params.from,
+            &store_key,
+            &params.guid,
+            params.index,
+            &params.message,
+        )
+        .len();
+        require!(ctx.remaining_accounts.len() >= clear_len, MyOAppError::InvalidAccount);
+        // Clear the compose message first so it cannot be replayed
+        oapp::endpoint_cpi::clear_compose(
+            ENDPOINT_ID,
+            store_key,
+            &ctx.remaining_accounts[..clear_len],
+            seeds,
+            ClearComposeParams {
+                from: params.from,
+                guid: params.guid,
+                index: params.index,
+                message: params.message.clone(),
+            },
+        )?;
+
+        let compose = msg_codec::ComposeMsg::decode(&params.message)?;
+        match compose.action {
+            msg_codec::ComposeAction::Allocate => Self::allocate(ctx, clear_len),
+            msg_codec::ComposeAction::RegisterReferrer { referrer } => {
+                Self::register_referrer(ctx, params, compose.evm_address, referrer)
+            },
+        }
+    }
+
+    /// Settles the deposit if `lz_receive` had to park it and its mint has since landed.
+    /// Otherwise this is a no-op and `settle_pending_deposit` remains the fallback.
+    fn allocate(ctx: &mut Context<LzCompose>, clear_len: usize) -> Result<()> {
+        if ctx.accounts.deposit_receipt.credited {
+            return Ok(());
+        }
+
+        let pending_info =
+            ctx.accounts.pending_deposit.as_ref().ok_or(MyOAppError::InvalidAccount)?.to_account_info();
+        require_keys_eq!(*pending_info.owner, crate::ID, MyOAppError::InvalidAccount);
+        let pending = PendingDeposit::try_deserialize(&mut &pending_info.try_borrow_data()?[..])?;
+        let cctp_used_nonce =
+            ctx.accounts.cctp_used_nonce.as_ref().ok_or(MyOAppError::InvalidAccount)?;
+        if !cctp::is_nonce_used(cctp_used_nonce) {
+            return Ok(());
+        }
+        let rent_payer =
+            ctx.accounts.rent_payer.as_ref().ok_or(MyOAppError::InvalidAccount)?.to_account_info();
+        require_keys_eq!(rent_payer.key(), pending.rent_payer, MyOAppError::InvalidAccount);
+
+        let store_key = ctx.accounts.store.key();
+        let vault_position =
+            ctx.accounts.vault_position.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+        let peer = ctx.accounts.peer.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+        settle(
+            &mut ctx.accounts.store,
+            store_key,
+            &pending,
+            Depositor {
+                user_balance: &mut ctx.accounts.user_balance,
+                user_balance_bump: ctx.bumps.user_balance,
+                vault_position,
+                vault_position_bump: ctx.bumps.vault_position,
+            },
+            &mut ctx.accounts.deposit_receipt,
+            &mut peer.inbound_rate_limit,
+            &ctx.remaining_accounts[clear_len..],
+        )?;
+
+        // Close the pending deposit back to whoever paid for it in lz_receive
+        let rent = pending_info.lamports();
+        **rent_payer.try_borrow_mut_lamports()? = rent_payer
+            .lamports()
+            .checked_add(rent)
+            .ok_or(MyOAppError::Overflow)?;
+        **pending_info.try_borrow_mut_lamports()? = 0;
+        pending_info.assign(&anchor_lang::solana_program::system_program::ID);
+        pending_info.realloc(0, false)?;
+        Ok(())
+    }
+
+    /// Records the user's referrer. The first registration wins; later ones are ignored.
+    fn register_referrer(
+        ctx: &mut Context<LzCompose>,
+        params: &LzComposeParams,
+        evm_address: [u8; 20],
+        referrer: [u8; 20],
+    ) -> Result<()> {
+        let referral = ctx.accounts.referral.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+        if referral.evm_address != [0u8; 20] || referrer == [0u8; 20] || referrer == evm_address {
+            return Ok(());
+        }
+        referral.evm_address = evm_address;
+        referral.referrer = referrer;
+        referral.bump = ctx.bumps.referral;
+        referral.version = Referral::VERSION;
+
+        emit!(ReferrerRegisteredEvent { guid: params.guid, evm_address, referrer });
+        Ok(())
+    }
+}
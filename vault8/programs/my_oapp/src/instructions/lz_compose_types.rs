@@ -0,0 +1,126 @@
+use crate::*;
+use oapp::endpoint_cpi::{get_accounts_for_clear_compose, LzAccount};
+use oapp::{endpoint::ID as ENDPOINT_ID, LzComposeParams};
+
+/// `lz_compose_types` is queried off-chain by the Executor before calling `lz_compose`.
+/// It returns every account `lz_compose` touches, followed by the `Endpoint::clear_compose`
+/// accounts and, for `Allocate`, the strategy deposit legs. The order must match exactly what
+/// `lz_compose` expects. Remaining accounts are the config PDAs of the allocated strategies, in
+/// `StrategyKind` order.
+#[derive(Accounts)]
+pub struct LzComposeTypes<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl LzComposeTypes<'_> {
+    pub fn apply(
+        ctx: &Context<LzComposeTypes>,
+        params: &LzComposeParams,
+    ) -> Result<Vec<LzAccount>> {
+        let store = ctx.accounts.store.key();
+        let compose = msg_codec::ComposeMsg::decode(&params.message)?;
+
+        // UserBalance and DepositReceipt PDAs written by lz_receive
+        let (user_balance_pda, _) = Pubkey::find_program_address(
+            &[USER_BALANCE_SEED, &compose.src_eid.to_be_bytes(), &compose.evm_address],
+            ctx.program_id,
+        );
+        let (deposit_receipt, _) =
+            Pubkey::find_program_address(&[DEPOSIT_RECEIPT_SEED, &params.guid], ctx.program_id);
+
+        // Action-specific accounts; the program ID stands in for an omitted optional account
+        let placeholder = LzAccount { pubkey: *ctx.program_id, is_signer: false, is_writable: false };
+        let payer = LzAccount { pubkey: Pubkey::default(), is_signer: true, is_writable: true };
+        let (vault_position, pending_deposit, cctp_used_nonce, rent_payer, peer, referral) =
+            match compose.action {
+                msg_codec::ComposeAction::Allocate => {
+                    let (vault_position, _) = Pubkey::find_program_address(
+                        &[
+                            VAULT_POSITION_SEED,
+                            &compose.src_eid.to_be_bytes(),
+                            &compose.vault,
+                        ],
+                        ctx.program_id,
+                    );
+                    let (pending_deposit, _) = Pubkey::find_program_address(
+                        &[PENDING_DEPOSIT_SEED, &params.guid],
+                        ctx.program_id,
+                    );
+                    let (peer, _) = Pubkey::find_program_address(
+                        &[PEER_SEED, &store.to_bytes(), &compose.src_eid.to_be_bytes()],
+                        ctx.program_id,
+                    );
+                    (
+                        // VaultPosition PDA (mutable, created if the deposit predates it)
+                        LzAccount { pubkey: vault_position, is_signer: false, is_writable: true },
+                        // Pending deposit PDA (closed if the deposit settles)
+                        LzAccount { pubkey: pending_deposit, is_signer: false, is_writable: true },
+                        // CCTP used_nonce PDA (read-only, owned by MessageTransmitter)
+                        LzAccount {
+                            pubkey: cctp::used_nonce_address(&compose.cctp_nonce),
+                            is_signer: false,
+                            is_writable: false,
+                        },
+                        // Rent payer: the Executor that paid for the pending deposit in lz_receive
+                        payer.clone(),
+                        // Peer of the source chain (mutable, inbound rate limit)
+                        LzAccount { pubkey: peer, is_signer: false, is_writable: true },
+                        placeholder,
+                    )
+                },
+                msg_codec::ComposeAction::RegisterReferrer { .. } => {
+                    let (referral, _) = Pubkey::find_program_address(
+                        &[REFERRAL_SEED, &compose.evm_address],
+                        ctx.program_id,
+                    );
+                    (
+                        placeholder.clone(),
+                        placeholder.clone(),
+                        placeholder.clone(),
+                        placeholder.clone(),
+                        placeholder,
+                        // Referral PDA (will be created if needed)
+                        LzAccount { pubkey: referral, is_signer: false, is_writable: true },
+                    )
+                },
+            };
+
+        let mut accounts = vec![
+            // store (mutable)
+            LzAccount { pubkey: store, is_signer: false, is_writable: true },
+            // UserBalance PDA (mutable)
+            LzAccount { pubkey: user_balance_pda, is_signer: false, is_writable: true },
+            // Deposit receipt PDA (mutable)
+            LzAccount { pubkey: deposit_receipt, is_signer: false, is_writable: true },
+            vault_position,
+            pending_deposit,
+            cctp_used_nonce,
+            rent_payer,
+            peer,
+            referral,
+            // Payer (Executor) - special sentinel value resolved by Executor
+            payer,
+            // System program
+            LzAccount { pubkey: anchor_lang::solana_program::system_program::ID, is_signer: false, is_writable: false },
+        ];
+
+        // Append the accounts required for `Endpoint::clear_compose`
+        accounts.extend(get_accounts_for_clear_compose(
+            ENDPOINT_ID,
+            &params.from,
+            &store,
+            &params.guid,
+            params.index,
+            &params.message,
+        ));
+
+        // Allocate may settle a parked deposit into the allocated strategies
+        if compose.action == msg_codec::ComposeAction::Allocate {
+            let configs = strategy::load_configs(&ctx.accounts.store, ctx.remaining_accounts)?;
+            accounts.extend(strategy::deposit_accounts(&ctx.accounts.store, &store, &configs));
+        }
+
+        Ok(accounts)
+    }
+}
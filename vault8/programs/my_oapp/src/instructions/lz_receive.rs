@@ -1,15 +1,17 @@
 use crate::*;
 use crate::errors::MyOAppError;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
 use oapp::{
     endpoint::{
-        instructions::ClearParams,
+        instructions::{ClearParams, SendComposeParams},
         ID as ENDPOINT_ID,
     },
     LzReceiveParams,
 };
-use oapp::endpoint_cpi::get_accounts_for_clear;
-use anchor_lang::solana_program::{instruction::{Instruction, AccountMeta}, program::invoke_signed};
+use oapp::endpoint_cpi::{get_accounts_for_clear, get_accounts_for_send_compose};
 
 #[derive(Accounts)]
 #[instruction(params: LzReceiveParams)]
@@ -17,39 +19,88 @@ pub struct LzReceive<'info> {
     /// OApp Store PDA.  This account represents the "address" of your OApp on
     /// Solana and can contain any state relevant to your application.
     /// Customize the fields in `Store` as needed.
-    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump, constraint = !store.paused @ MyOAppError::Paused)]
     pub store: Account<'info, Store>,
     /// Peer config PDA for the sending chain. Ensures `params.sender` can only be the allowed peer from that remote chain.
     #[account(
+        mut,
         seeds = [PEER_SEED, &store.key().to_bytes(), &params.src_eid.to_be_bytes()],
         bump = peer.bump,
-        constraint = params.sender == peer.peer_address
+        constraint = params.sender == peer.peer_address,
+        constraint = !peer.inbound_paused @ MyOAppError::PeerInboundPaused
     )]
     pub peer: Account<'info, PeerConfig>,
-    /// UserBalance PDA tracking this EVM user's deposits
-    /// Seeds derived from EVM address parsed from message
+    /// Inbound nonce PDA for this (src_eid, sender) path, created by `set_peer_config`
+    #[account(
+        mut,
+        seeds = [NONCE_SEED, &store.key().to_bytes(), &params.src_eid.to_be_bytes(), &params.sender],
+        bump = nonce_account.bump
+    )]
+    pub nonce_account: Account<'info, Nonce>,
+    /// UserBalance PDA tracking this EVM user's deposits and withdrawals from the source chain
+    /// Seeds derived from the source EID and the EVM address decoded from message
     #[account(
         init_if_needed,
         payer = payer,
         space = UserBalance::SIZE,
-        seeds = [USER_BALANCE_SEED, &parse_evm_address(&params.message)?],
+        seeds = [
+            USER_BALANCE_SEED,
+            &params.src_eid.to_be_bytes(),
+            &msg_codec::decode(&params.message)?.evm_address()?
+        ],
         bump
     )]
     pub user_balance: Account<'info, UserBalance>,
-    /// Payer for UserBalance PDA creation (Executor)
+    /// VaultPosition PDA holding the shares of the EVM vault named in the message on the source
+    /// chain
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultPosition::SIZE,
+        seeds = [
+            VAULT_POSITION_SEED,
+            &params.src_eid.to_be_bytes(),
+            &msg_codec::decode(&params.message)?.vault()?
+        ],
+        bump
+    )]
+    pub vault_position: Account<'info, VaultPosition>,
+    /// CHECK: Circle MessageTransmitter `used_nonce` PDA for the deposit's CCTP nonce.
+    /// It is only created (and owned by MessageTransmitter) once the backing mint has landed;
+    /// until then the deposit is parked in `pending_deposit`. Deposits only.
+    #[account(address = cctp::used_nonce_address(&msg_codec::decode(&params.message)?.cctp_nonce()?))]
+    pub cctp_used_nonce: Option<UncheckedAccount<'info>>,
+    /// Consumed-nonce PDA binding the CCTP mint to this LZ deposit. Seeded on the V2 nonce alone,
+    /// like `used_nonce`, so a mint can back one deposit whatever domain it claims. Deposits only.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ConsumedCctpNonce::SIZE,
+        seeds = [CCTP_NONCE_SEED, &msg_codec::decode(&params.message)?.cctp_nonce()?.nonce],
+        bump
+    )]
+    pub consumed_cctp_nonce: Option<Account<'info, ConsumedCctpNonce>>,
+    /// CHECK: Pending deposit PDA keyed by GUID. Only created (see `create_pending_deposit`) if
+    /// the deposit cannot be settled yet, so funded deposits pay no rent for it. Deposits only.
+    #[account(mut, seeds = [PENDING_DEPOSIT_SEED, &params.guid], bump)]
+    pub pending_deposit: Option<UncheckedAccount<'info>>,
+    /// Deposit receipt PDA keyed by GUID, later ACKed to the EVM peer by `send_deposit_ack`.
+    /// Deposits only.
+    #[account(
+        init,
+        payer = payer,
+        space = DepositReceipt::SIZE,
+        seeds = [DEPOSIT_RECEIPT_SEED, &params.guid],
+        bump
+    )]
+    pub deposit_receipt: Option<Account<'info, DepositReceipt>>,
+    /// Payer for UserBalance, VaultPosition, consumed-nonce, pending-deposit and receipt PDA
+    /// creation (Executor), and rent payer of the CCTP message account on withdrawals
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// Helper to parse EVM address from message for seeds derivation
-fn parse_evm_address(message: &[u8]) -> Result<[u8; 20]> {
-    require!(message.len() >= 28, MyOAppError::InvalidMessageType);
-    let mut evm_address = [0u8; 20];
-    evm_address.copy_from_slice(&message[8..28]);
-    Ok(evm_address)
-}
-
 impl LzReceive<'_> {
     pub fn apply(ctx: &mut Context<LzReceive>, params: &LzReceiveParams) -> Result<()> {
         // The OApp Store PDA is used to sign the CPI to the Endpoint program.
@@ -85,119 +136,335 @@ impl LzReceive<'_> {
             },
         )?;
 
-        // Parse 28-byte payload: [amount:8][evm_address:20]
-        require!(params.message.len() >= 28, MyOAppError::InvalidMessageType);
-        
-        // Parse amount (8 bytes, little-endian)
-        let mut amt_bytes = [0u8; 8];
-        amt_bytes.copy_from_slice(&params.message[0..8]);
-        let amount: u64 = u64::from_le_bytes(amt_bytes);
-        
-        // Parse EVM address (20 bytes)
-        let mut evm_address = [0u8; 20];
-        evm_address.copy_from_slice(&params.message[8..28]);
-        
-        // Update UserBalance PDA
-        let user_balance = &mut ctx.accounts.user_balance;
+        // Deposits and withdrawals for a user must apply in the order they were sent
+        ctx.accounts.nonce_account.accept(params.nonce, ctx.accounts.store.ordered_nonce)?;
+
+        let message = msg_codec::decode(&params.message)?;
+        let a = &mut ctx.accounts;
+        a.vault_position.open_if_new(
+            params.src_eid,
+            message.vault()?,
+            ctx.bumps.vault_position,
+            &mut a.user_balance,
+        )?;
+
+        match message {
+            msg_codec::Message::Deposit { amount, evm_address, vault, cctp_nonce } => {
+                Self::deposit(ctx, params, clear_len, amount, evm_address, vault, cctp_nonce)?;
+                Ok(())
+            },
+            msg_codec::Message::Withdraw { amount, evm_address, vault } => {
+                Self::withdraw(ctx, params, clear_len, amount, evm_address, vault)
+            },
+            msg_codec::Message::DepositAndCall { amount, evm_address, vault, cctp_nonce, action } => {
+                // `lz_compose` must only act on a credited deposit. A parked one keeps its action
+                // and `settle_pending_deposit` queues the compose once it is credited.
+                if !Self::deposit(ctx, params, clear_len, amount, evm_address, vault, cctp_nonce)? {
+                    return Self::park_compose(ctx, action);
+                }
+                let compose = msg_codec::ComposeMsg {
+                    amount,
+                    evm_address,
+                    vault,
+                    cctp_nonce,
+                    src_eid: params.src_eid,
+                    action,
+                };
+                Self::send_compose(ctx, params, clear_len, compose)
+            },
+            _ => err!(MyOAppError::InvalidMessageType),
+        }
+    }
+
+    /// Credits the deposit, or parks it in `pending_deposit`. Returns whether it was credited.
+    fn deposit(
+        ctx: &mut Context<LzReceive>,
+        params: &LzReceiveParams,
+        clear_len: usize,
+        amount: u64,
+        evm_address: [u8; 20],
+        vault: [u8; 20],
+        cctp_nonce: cctp::CctpNonce,
+    ) -> Result<bool> {
+        cctp::check_source_domain(&cctp_nonce)?;
+        // Bind this deposit to its CCTP nonce up front, even if it ends up parked below.
+        // A nonce already recorded here backed an earlier deposit and cannot be reused.
+        let consumed =
+            ctx.accounts.consumed_cctp_nonce.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+        require!(consumed.guid == [0u8; 32], MyOAppError::CctpNonceAlreadyConsumed);
+        consumed.cctp_nonce = cctp_nonce;
+        consumed.guid = params.guid;
+        consumed.evm_address = evm_address;
+        consumed.amount = amount;
+        consumed.bump = ctx.bumps.consumed_cctp_nonce;
+        consumed.version = ConsumedCctpNonce::VERSION;
+
         let clock = Clock::get()?;
-        
-        // Initialize on first deposit
-        if user_balance.deposit_count == 0 {
-            user_balance.evm_address = evm_address;
-            user_balance.bump = ctx.bumps.user_balance;
+        let store = &ctx.accounts.store;
+        let store_key = store.key();
+        let ra = &ctx.remaining_accounts;
+        // Strategy deposit legs follow immediately after the clear accounts provided by the Executor
+        let deposit_end = clear_len + strategy::deposit_accounts_len(store);
+        require!(ra.len() >= deposit_end, MyOAppError::InvalidMessageType);
+        let strategy_accounts = &ra[clear_len..deposit_end];
+
+        // The LZ message can outrun the CCTP attestation. If the mint has not landed in the
+        // Store USDC ATA yet (on top of the idle buffer and the USDC held for parked deposits),
+        // park the deposit; the message is cleared either way and `settle_pending_deposit`
+        // finishes the job once funds arrive.
+        let store_usdc_ata = strategy::find_account(
+            strategy_accounts,
+            &store.associated_token_address(&store_key, &store.usdc_mint),
+        )?;
+        let cctp_used_nonce =
+            ctx.accounts.cctp_used_nonce.as_ref().ok_or(MyOAppError::InvalidAccount)?;
+        let funded = cctp::is_nonce_used(cctp_used_nonce)
+            && store.unallocated_usdc(strategy::token_amount(store, store_usdc_ata)?) >= amount;
+        // The USDC is already minted, so a deposit over a cap or the peer's inbound rate limit, or
+        // too small to be worth a vault share, is parked rather than reverted (which would strand
+        // the funds and, with ordered execution, block the path). It settles once the cap is
+        // raised or the window has room again.
+        let over_cap = funded && {
+            let (legs, _) = strategy::deposit_legs(store, strategy_accounts)?;
+            let invested = strategy::sum(&strategy::leg_values(store, &legs)?)?;
+            let total_assets = store.idle_assets.checked_add(invested).ok_or(MyOAppError::Overflow)?;
+            store.exceeds_user_deposit_cap(ctx.accounts.user_balance.total_deposited, amount)?
+                || store.exceeds_tvl_cap(total_assets, amount)?
+                || ctx.accounts.peer.inbound_rate_limit.exceeds(amount, clock.unix_timestamp)?
+                || store.shares_for_deposit(amount, total_assets)? == 0
+        };
+        let credited = funded && !over_cap;
+
+        // Record the receipt either way; it can only be ACKed once the deposit is credited
+        let receipt = ctx.accounts.deposit_receipt.as_mut().ok_or(MyOAppError::InvalidAccount)?;
+        receipt.guid = params.guid;
+        receipt.evm_address = evm_address;
+        receipt.amount = amount;
+        receipt.src_eid = params.src_eid;
+        receipt.credited = credited;
+        receipt.bump = ctx.bumps.deposit_receipt;
+        receipt.version = DepositReceipt::VERSION;
+
+        let pending_info = ctx.accounts.pending_deposit.as_ref().ok_or(MyOAppError::InvalidAccount)?;
+        if !credited {
+            // A funded deposit parked over a cap keeps its USDC out of the next deposits' funding
+            if funded {
+                let store = &mut ctx.accounts.store;
+                store.parked_assets =
+                    store.parked_assets.checked_add(amount).ok_or(MyOAppError::Overflow)?;
+            }
+            let pending = PendingDeposit {
+                guid: params.guid,
+                evm_address,
+                vault,
+                amount,
+                cctp_nonce,
+                src_eid: params.src_eid,
+                rent_payer: ctx.accounts.payer.key(),
+                created_at: clock.unix_timestamp,
+                funded,
+                compose_action: Vec::new(),
+                bump: ctx.bumps.pending_deposit,
+                version: PendingDeposit::VERSION,
+            };
+            create_pending_deposit(
+                &pending_info.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &pending,
+                ctx.program_id,
+            )?;
+
+            emit!(PendingDepositEvent {
+                guid: params.guid,
+                evm_address,
+                amount,
+                cctp_nonce,
+                src_eid: params.src_eid,
+                over_cap,
+                timestamp: clock.unix_timestamp,
+            });
+            return Ok(false);
         }
-        
-        // Update cumulative stats
-        user_balance.total_deposited = user_balance.total_deposited.checked_add(amount)
-            .ok_or(MyOAppError::Overflow)?;
-        user_balance.last_updated = clock.unix_timestamp;
-        user_balance.deposit_count = user_balance.deposit_count.checked_add(1)
-            .ok_or(MyOAppError::Overflow)?;
-        
+
+        // Credited deposits count against the peer's rolling inbound limit
+        ctx.accounts.peer.inbound_rate_limit.consume(amount, clock.unix_timestamp)?;
+
+        // Split the deposit across the allocated strategies using the legs provided by
+        // lz_receive_types, crediting the user with the vault shares it is worth.
+        let (shares, _) =
+            strategy::deposit(&mut ctx.accounts.store, &store_key, strategy_accounts, amount)?;
+
+        // Update UserBalance PDA and the vault's position
+        let user_balance = &mut ctx.accounts.user_balance;
+        user_balance.record_deposit(
+            params.src_eid,
+            evm_address,
+            ctx.bumps.user_balance,
+            amount,
+            shares,
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts.vault_position.record_deposit(amount, shares, clock.unix_timestamp)?;
+
         // Emit event for bot indexing with GUID
         emit!(DepositEvent {
             guid: params.guid,
             evm_address,
             amount,
+            shares,
             new_total: user_balance.total_deposited,
             deposit_index: user_balance.deposit_count,
             timestamp: clock.unix_timestamp,
         });
 
-        // Now perform a CPI deposit into Jupiter Lend using remaining accounts provided by lz_receive_types.
-        // Account ordering after Clear accounts should match the JL deposit accounts we expect.
-        // For safety, assert a few critical keys match the Store config (set during init/admin).
-        let store = &ctx.accounts.store;
+        // The ACK back to the EVM peer is sent separately by the `send_deposit_ack` crank.
+
+        Ok(true)
+    }
+
+    /// Queues the compose action of a credited `DepositAndCall` for `lz_compose`. The Endpoint
+    /// `send_compose` accounts follow the strategy deposit legs.
+    fn send_compose(
+        ctx: &mut Context<LzReceive>,
+        params: &LzReceiveParams,
+        clear_len: usize,
+        compose: msg_codec::ComposeMsg,
+    ) -> Result<()> {
+        let start = clear_len + strategy::deposit_accounts_len(&ctx.accounts.store);
+        send_compose(&ctx.accounts.store, params.guid, compose, &ctx.remaining_accounts[start..])
+    }
+
+    /// Stores the compose action of a parked `DepositAndCall` on its `PendingDeposit`
+    fn park_compose(ctx: &mut Context<LzReceive>, action: msg_codec::ComposeAction) -> Result<()> {
+        let pending_info =
+            ctx.accounts.pending_deposit.as_ref().ok_or(MyOAppError::InvalidAccount)?.to_account_info();
+        let mut pending = PendingDeposit::try_deserialize(&mut &pending_info.try_borrow_data()?[..])?;
+        action.encode_into(&mut pending.compose_action);
+        let mut data = pending_info.try_borrow_mut_data()?;
+        pending.try_serialize(&mut &mut data[..])
+    }
+
+    fn withdraw(
+        ctx: &mut Context<LzReceive>,
+        params: &LzReceiveParams,
+        clear_len: usize,
+        amount: u64,
+        evm_address: [u8; 20],
+        vault: [u8; 20],
+    ) -> Result<()> {
+        // Deposit-only accounts must be omitted (program ID placeholders) for withdrawals
+        require!(
+            ctx.accounts.cctp_used_nonce.is_none()
+                && ctx.accounts.consumed_cctp_nonce.is_none()
+                && ctx.accounts.pending_deposit.is_none()
+                && ctx.accounts.deposit_receipt.is_none(),
+            MyOAppError::InvalidAccount
+        );
+
+        let clock = Clock::get()?;
+
+        // After the clear accounts come the strategy withdraw legs, then the CCTP burn accounts
         let ra = &ctx.remaining_accounts;
-        // JL accounts follow immediately after the clear accounts provided by the Executor
-        let base = clear_len;
-
-        // Expected order (as resolved by off-chain types call):
-        // 0: signer (store PDA, writable, signer via invoke_signed)
-        // 1: depositorTokenAccount (USDC ATA)
-        // 2: recipientTokenAccount (jlUSDC ATA)
-        // 3: mint (USDC)
-        // 4: lendingAdmin
-        // 5: lending
-        // 6: fTokenMint
-        // 7: supplyTokenReservesLiquidity
-        // 8: lendingSupplyPositionOnLiquidity
-        // 9: rateModel
-        // 10: vault
-        // 11: liquidity
-        // 12: liquidityProgram
-        // 13: rewardsRateModel
-        // 14: tokenProgram
-        // 15: associatedTokenProgram
-        // 16: systemProgram
-        // 17: jlLendingProgram
-    require!(ra.len() >= base + 18, MyOAppError::InvalidMessageType);
-    let jl_ix_accounts = &ra[base..base+18];
-
-        // Verify mint and programs
-        require_keys_eq!(jl_ix_accounts[3].key(), store.usdc_mint, MyOAppError::InvalidMessageType);
-        require_keys_eq!(jl_ix_accounts[14].key(), store.token_program, MyOAppError::InvalidMessageType);
-        require_keys_eq!(jl_ix_accounts[15].key(), store.associated_token_program, MyOAppError::InvalidMessageType);
-        require_keys_eq!(jl_ix_accounts[17].key(), store.jl_lending_program, MyOAppError::InvalidMessageType);
-
-        // Build the raw deposit instruction data: discriminator for "deposit" and amount as u64.
-        // Jupiter Lend deposit discriminator from IDL: [242, 35, 198, 137, 82, 225, 242, 182]
-        let mut data: Vec<u8> = vec![242, 35, 198, 137, 82, 225, 242, 182];
-        data.extend_from_slice(&amount.to_le_bytes());
-
-        // Build metas in the same order
-        let mut metas: Vec<AccountMeta> = Vec::with_capacity(17); // 14 from accounts + 3 hardcoded
-        metas.push(AccountMeta::new(jl_ix_accounts[0].key(), true));  // signer
-        metas.push(AccountMeta::new(jl_ix_accounts[1].key(), false)); // depositorTokenAccount
-        metas.push(AccountMeta::new(jl_ix_accounts[2].key(), false)); // recipientTokenAccount
-        metas.push(AccountMeta::new_readonly(jl_ix_accounts[3].key(), false)); // mint
-        metas.push(AccountMeta::new_readonly(jl_ix_accounts[4].key(), false)); // lendingAdmin
-        metas.push(AccountMeta::new(jl_ix_accounts[5].key(), false)); // lending
-        metas.push(AccountMeta::new(jl_ix_accounts[6].key(), false)); // fTokenMint
-        metas.push(AccountMeta::new(jl_ix_accounts[7].key(), false)); // supplyTokenReservesLiquidity
-        metas.push(AccountMeta::new(jl_ix_accounts[8].key(), false)); // lendingSupplyPositionOnLiquidity
-        metas.push(AccountMeta::new_readonly(jl_ix_accounts[9].key(), false)); // rateModel
-        metas.push(AccountMeta::new(jl_ix_accounts[10].key(), false)); // vault
-        metas.push(AccountMeta::new(jl_ix_accounts[11].key(), false)); // liquidity
-        metas.push(AccountMeta::new(jl_ix_accounts[12].key(), false)); // liquidityProgram (mutable per Jupiter docs)
-        metas.push(AccountMeta::new_readonly(jl_ix_accounts[13].key(), false)); // rewardsRateModel
-        // Use well-known program accounts from remaining_accounts (passed by Executor)
-        metas.push(AccountMeta::new_readonly(jl_ix_accounts[14].key(), false)); // tokenProgram
-        metas.push(AccountMeta::new_readonly(jl_ix_accounts[15].key(), false)); // associatedTokenProgram
-        metas.push(AccountMeta::new_readonly(jl_ix_accounts[16].key(), false)); // systemProgram
-
-        let jl_program = *jl_ix_accounts[17].key; // Jupiter Lend program from accounts
-        let ix = Instruction { program_id: jl_program, accounts: metas, data };
-
-        // signer = store PDA
-        let signer_seeds: &[&[u8]] = &[STORE_SEED, &[store.bump]];
-        // Pass all JL accounts to invoke_signed
-        invoke_signed(&ix, jl_ix_accounts, &[signer_seeds])?;
-
-        // Note: Compose ACK removed to fit under 1KB account limit.
-        // Bot will handle balance crediting on EVM side off-chain.
+        let strategy_end = clear_len + strategy::withdraw_accounts_len(&ctx.accounts.store);
+        let burn_end = strategy_end + cctp::DEPOSIT_FOR_BURN_ACCOUNTS_LEN;
+        require!(ra.len() >= burn_end, MyOAppError::InvalidAccount);
+        let strategy_accounts = &ra[clear_len..strategy_end];
+        let burn_accounts = &ra[strategy_end..burn_end];
+
+        // Venue shares are redeemed into the Store USDC ATA, which is then the account CCTP burns from.
+        let store = &ctx.accounts.store;
+        let store_key = store.key();
+        let store_usdc_ata = store.associated_token_address(&store_key, &store.usdc_mint);
+        require_keys_eq!(burn_accounts[3].key(), store_usdc_ata, MyOAppError::InvalidAccount);
+
+        // CCTP writes the outgoing message into a fresh account; a GUID-keyed PDA lets the
+        // Store sign for it without an extra keypair from the Executor.
+        let (message_sent_event_data, event_bump) =
+            Pubkey::find_program_address(&[MESSAGE_SENT_EVENT_SEED, &params.guid], ctx.program_id);
+        require_keys_eq!(burn_accounts[11].key(), message_sent_event_data, MyOAppError::InvalidAccount);
+        let event_data_seeds: &[&[u8]] = &[MESSAGE_SENT_EVENT_SEED, &params.guid, &[event_bump]];
+
+        // The USDC is minted to the vault named in the payload, whose position is debited
+        let mint_recipient = cctp::evm_mint_recipient(&vault);
+
+        // Redeem pro rata from the allocated strategies and debit the user the vault shares burned
+        let (shares, _) =
+            strategy::withdraw(&mut ctx.accounts.store, &store_key, strategy_accounts, amount)?;
+        let store = &ctx.accounts.store;
+        cctp::deposit_for_burn(store, burn_accounts, amount, mint_recipient, event_data_seeds)?;
+        ctx.accounts.vault_position.record_withdrawal(amount, shares, clock.unix_timestamp)?;
+        let user_balance = &mut ctx.accounts.user_balance;
+        user_balance.record_withdrawal(amount, shares, clock.unix_timestamp)?;
+
+        emit!(WithdrawEvent {
+            guid: params.guid,
+            evm_address,
+            amount,
+            shares,
+            new_total: user_balance.total_withdrawn,
+            mint_recipient,
+            destination_domain: cctp::BASE_DOMAIN,
+            timestamp: clock.unix_timestamp,
+        });
 
         Ok(())
     }
 }
 
+/// Creates the GUID-keyed `PendingDeposit` PDA for a parked deposit and writes `pending` into it.
+/// Lamports already sitting on the address are topped up rather than rejected, so a pre-funded PDA
+/// cannot block the deposit.
+pub(crate) fn create_pending_deposit<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    pending: &PendingDeposit,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[PENDING_DEPOSIT_SEED, &pending.guid, &[pending.bump]];
+    let signer = &[seeds];
+    let space = PendingDeposit::SIZE;
+    let rent = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        let accounts = CreateAccount { from: payer.clone(), to: account.clone() };
+        let cpi = CpiContext::new_with_signer(system_program.clone(), accounts, signer);
+        create_account(cpi, rent, space as u64, program_id)?;
+    } else {
+        let missing = rent.saturating_sub(account.lamports());
+        if missing > 0 {
+            let accounts = Transfer { from: payer.clone(), to: account.clone() };
+            transfer(CpiContext::new(system_program.clone(), accounts), missing)?;
+        }
+        let accounts = Allocate { account_to_allocate: account.clone() };
+        allocate(CpiContext::new_with_signer(system_program.clone(), accounts, signer), space as u64)?;
+        let accounts = Assign { account_to_assign: account.clone() };
+        assign(CpiContext::new_with_signer(system_program.clone(), accounts, signer), program_id)?;
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    pending.try_serialize(&mut &mut data[..])
+}
+
+/// Queues `compose` for `lz_compose` through `Endpoint::send_compose`, signed by the Store.
+/// `accounts` must start with the `send_compose` accounts. Shared with `settle_pending_deposit`.
+pub(crate) fn send_compose(
+    store: &Account<Store>,
+    guid: [u8; 32],
+    compose: msg_codec::ComposeMsg,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let store_key = store.key();
+    let message = compose.encode();
+    let len =
+        get_accounts_for_send_compose(ENDPOINT_ID, &store_key, &store_key, &guid, 0, &message).len();
+    require!(accounts.len() >= len, MyOAppError::InvalidAccount);
+
+    let seeds: &[&[u8]] = &[STORE_SEED, &[store.bump]];
+    oapp::endpoint_cpi::send_compose(
+        ENDPOINT_ID,
+        store_key,
+        &accounts[..len],
+        seeds,
+        SendComposeParams { to: store_key, guid, index: 0, message },
+    )
+}
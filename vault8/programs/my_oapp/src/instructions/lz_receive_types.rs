@@ -1,16 +1,8 @@
 use crate::*;
 use crate::errors::MyOAppError;
-use oapp::endpoint_cpi::{get_accounts_for_clear, LzAccount};
+use oapp::endpoint_cpi::{get_accounts_for_clear, get_accounts_for_send_compose, LzAccount};
 use oapp::{endpoint::ID as ENDPOINT_ID, LzReceiveParams};
 
-/// Helper to parse EVM address from message
-fn parse_evm_address(message: &[u8]) -> Result<[u8; 20]> {
-    require!(message.len() >= 28, MyOAppError::InvalidMessageType);
-    let mut evm_address = [0u8; 20];
-    evm_address.copy_from_slice(&message[8..28]);
-    Ok(evm_address)
-}
-
 /// `lz_receive_types` is queried off-chain by the Executor before calling
 /// `lz_receive`. It must return **every** account that will be touched by the
 /// actual `lz_receive` instruction as well as the accounts required by
@@ -18,6 +10,11 @@ fn parse_evm_address(message: &[u8]) -> Result<[u8; 20]> {
 ///
 /// The return order must match exactly what `lz_receive` expects or the
 /// cross-program invocation will fail.
+///
+/// A V1 Executor only passes the pubkeys stored in the LzReceiveTypesAccounts PDA (store, alt),
+/// never the strategy config PDAs the strategy legs are resolved from. V1 therefore only resolves
+/// a Store with no strategy allocated and fails with `LzReceiveTypesV2Required` otherwise;
+/// Executors that support it use `lz_receive_types_v2`.
 #[derive(Accounts)]
 pub struct LzReceiveTypes<'info> {
     #[account(seeds = [STORE_SEED], bump = store.bump)]
@@ -29,81 +26,210 @@ impl LzReceiveTypes<'_> {
         ctx: &Context<LzReceiveTypes>,
         params: &LzReceiveParams,
     ) -> Result<Vec<LzAccount>> {
-        // 1. The store PDA is always the first account and is mutable.  If your
-        // program derives the store PDA with additional seeds, ensure the same
-        // seeds are used when providing the store account.
-        let store = ctx.accounts.store.key();
-
-        // 2. The peer PDA for the remote chain needs to be retrieved, for later verification of the `params.sender`.
-        let peer_seeds = [PEER_SEED, &store.to_bytes(), &params.src_eid.to_be_bytes()];
-        let (peer, _) = Pubkey::find_program_address(&peer_seeds, ctx.program_id);
-
-        // Parse EVM address from message to derive UserBalance PDA
-        let evm_address = parse_evm_address(&params.message)?;
-        let (user_balance_pda, _) = Pubkey::find_program_address(
-            &[USER_BALANCE_SEED, &evm_address],
-            ctx.program_id,
-        );
-
-        // Accounts used directly by `lz_receive`
-        let mut accounts = vec![
-            // store (mutable)
-            LzAccount { pubkey: store, is_signer: false, is_writable: true },
-            // peer (read-only)
-            LzAccount { pubkey: peer, is_signer: false, is_writable: false },
-            // UserBalance PDA (will be created if needed)
-            LzAccount { pubkey: user_balance_pda, is_signer: false, is_writable: true },
-            // Payer (Executor) - special sentinel value resolved by Executor
-            LzAccount { pubkey: Pubkey::default(), is_signer: true, is_writable: true },
-            // System program
-            LzAccount { pubkey: anchor_lang::solana_program::system_program::ID, is_signer: false, is_writable: false },
-        ];
-
-        // Append the additional accounts required for `Endpoint::clear`
-        let accounts_for_clear = get_accounts_for_clear(
-            ENDPOINT_ID,
-            &store,
-            params.src_eid,
-            &params.sender,
-            params.nonce,
-        );
-        accounts.extend(accounts_for_clear);
-
-        // Append Jupiter Lend CPI accounts in the exact order expected by lz_receive.
-    let s = &ctx.accounts.store;
-    // signer (store PDA) again as CPI account 0 in our JL slice.
-    accounts.push(LzAccount { pubkey: s.key(), is_signer: false, is_writable: true });
-    // Derive ATAs for the store PDA owner
-    // Official ATA seeds: [owner, token_program, mint]
-    let depositor_ata_seeds: &[&[u8]] = &[&s.key().to_bytes(), &s.token_program.to_bytes(), &s.usdc_mint.to_bytes()];
-    let (depositor_ata, _) = Pubkey::find_program_address(depositor_ata_seeds, &s.associated_token_program);
-    let recipient_ata_seeds: &[&[u8]] = &[&s.key().to_bytes(), &s.token_program.to_bytes(), &s.jl_f_token_mint.to_bytes()];
-    let (recipient_ata, _) = Pubkey::find_program_address(recipient_ata_seeds, &s.associated_token_program);
-    accounts.push(LzAccount { pubkey: depositor_ata, is_signer: false, is_writable: true });
-    accounts.push(LzAccount { pubkey: recipient_ata, is_signer: false, is_writable: true });
-        // Fixed accounts from Store config
-        accounts.push(LzAccount { pubkey: s.usdc_mint, is_signer: false, is_writable: false });
-        accounts.push(LzAccount { pubkey: s.jl_lending_admin, is_signer: false, is_writable: false });
-        accounts.push(LzAccount { pubkey: s.jl_lending, is_signer: false, is_writable: true });
-        accounts.push(LzAccount { pubkey: s.jl_f_token_mint, is_signer: false, is_writable: true });
-        accounts.push(LzAccount { pubkey: s.jl_supply_token_reserves_liquidity, is_signer: false, is_writable: true });
-        accounts.push(LzAccount { pubkey: s.jl_lending_supply_position_on_liquidity, is_signer: false, is_writable: true });
-        accounts.push(LzAccount { pubkey: s.jl_rate_model, is_signer: false, is_writable: false });
-        accounts.push(LzAccount { pubkey: s.jl_vault, is_signer: false, is_writable: true });
-        accounts.push(LzAccount { pubkey: s.jl_liquidity, is_signer: false, is_writable: true });
-        // Jupiter docs specify liquidity_program as mutable
-        accounts.push(LzAccount { pubkey: s.jl_liquidity_program, is_signer: false, is_writable: true });
-        accounts.push(LzAccount { pubkey: s.jl_rewards_rate_model, is_signer: false, is_writable: false });
-        
-        // Well-known programs MUST be included so Solana runtime can find them during CPI
-        accounts.push(LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false });
-        accounts.push(LzAccount { pubkey: s.associated_token_program, is_signer: false, is_writable: false });
-        use anchor_lang::solana_program::system_program;
-        accounts.push(LzAccount { pubkey: system_program::ID, is_signer: false, is_writable: false });
-        
-        // Jupiter Lend program MUST be included for invoke_signed to work
-        accounts.push(LzAccount { pubkey: s.jl_lending_program, is_signer: false, is_writable: false });
-
-        Ok(accounts)
+        lz_receive_accounts_v1(ctx.program_id, &ctx.accounts.store, params)
+    }
+}
+
+/// Accounts of `lz_receive` as resolved from the V1 account set, which has no strategy configs.
+pub(crate) fn lz_receive_accounts_v1(
+    program_id: &Pubkey,
+    store: &Account<Store>,
+    params: &LzReceiveParams,
+) -> Result<Vec<LzAccount>> {
+    require!(store.allocated_strategies().is_empty(), MyOAppError::LzReceiveTypesV2Required);
+    lz_receive_accounts(program_id, store, params, &[])
+}
+
+/// Accounts of `lz_receive` for `params`, in the order it expects them; shared by both
+/// `lz_receive_types` versions. The Executor payer is the signer sentinel `Pubkey::default()`.
+/// `configs` come from `strategy::load_configs`.
+pub(crate) fn lz_receive_accounts(
+    program_id: &Pubkey,
+    s: &Account<Store>,
+    params: &LzReceiveParams,
+    configs: &[StrategyConfig],
+) -> Result<Vec<LzAccount>> {
+    // 1. The store PDA is always the first account and is mutable.  If your
+    // program derives the store PDA with additional seeds, ensure the same
+    // seeds are used when providing the store account.
+    let store = s.key();
+
+    // 2. The peer PDA for the remote chain needs to be retrieved, for later verification of the `params.sender`.
+    let peer_seeds = [PEER_SEED, &store.to_bytes(), &params.src_eid.to_be_bytes()];
+    let (peer, _) = Pubkey::find_program_address(&peer_seeds, program_id);
+
+    // Inbound nonce PDA for ordered execution on this (src_eid, sender) path
+    let nonce_seeds =
+        [NONCE_SEED, &store.to_bytes(), &params.src_eid.to_be_bytes(), &params.sender];
+    let (nonce_account, _) = Pubkey::find_program_address(&nonce_seeds, program_id);
+
+    // Decode message to derive the UserBalance PDA, namespaced by the source chain
+    let message = msg_codec::decode(&params.message)?;
+    let evm_address = message.evm_address()?;
+    let (user_balance_pda, _) = Pubkey::find_program_address(
+        &[USER_BALANCE_SEED, &params.src_eid.to_be_bytes(), &evm_address],
+        program_id,
+    );
+    // VaultPosition PDA of the vault named in the message on the source chain
+    let (vault_position, _) = Pubkey::find_program_address(
+        &[VAULT_POSITION_SEED, &params.src_eid.to_be_bytes(), &message.vault()?],
+        program_id,
+    );
+
+    // Deposit-only accounts; the program ID stands in for an omitted optional account
+    let placeholder = LzAccount { pubkey: *program_id, is_signer: false, is_writable: false };
+    let (cctp_used_nonce, consumed_cctp_nonce, pending_deposit, deposit_receipt) = match message {
+        msg_codec::Message::Deposit { cctp_nonce, .. }
+        | msg_codec::Message::DepositAndCall { cctp_nonce, .. } => {
+            // CCTP nonce accounts binding the deposit to its mint
+            let cctp_used_nonce = cctp::used_nonce_address(&cctp_nonce);
+            let (consumed_cctp_nonce, _) =
+                Pubkey::find_program_address(&[CCTP_NONCE_SEED, &cctp_nonce.nonce], program_id);
+            // Pending deposit PDA keyed by GUID (parked if the mint has not landed yet)
+            let (pending_deposit, _) =
+                Pubkey::find_program_address(&[PENDING_DEPOSIT_SEED, &params.guid], program_id);
+            // Deposit receipt PDA keyed by GUID (ACKed later by send_deposit_ack)
+            let (deposit_receipt, _) =
+                Pubkey::find_program_address(&[DEPOSIT_RECEIPT_SEED, &params.guid], program_id);
+            (
+                // CCTP used_nonce PDA (read-only, owned by MessageTransmitter)
+                LzAccount { pubkey: cctp_used_nonce, is_signer: false, is_writable: false },
+                // Consumed CCTP nonce PDA (will be created)
+                LzAccount { pubkey: consumed_cctp_nonce, is_signer: false, is_writable: true },
+                // Pending deposit PDA (only created if the deposit is parked)
+                LzAccount { pubkey: pending_deposit, is_signer: false, is_writable: true },
+                // Deposit receipt PDA (will be created)
+                LzAccount { pubkey: deposit_receipt, is_signer: false, is_writable: true },
+            )
+        },
+        _ => (placeholder.clone(), placeholder.clone(), placeholder.clone(), placeholder),
+    };
+
+    // Accounts used directly by `lz_receive`
+    let mut accounts = vec![
+        // store (mutable)
+        LzAccount { pubkey: store, is_signer: false, is_writable: true },
+        // peer (mutable, inbound rate limit)
+        LzAccount { pubkey: peer, is_signer: false, is_writable: true },
+        // nonce_account (mutable)
+        LzAccount { pubkey: nonce_account, is_signer: false, is_writable: true },
+        // UserBalance PDA (will be created if needed)
+        LzAccount { pubkey: user_balance_pda, is_signer: false, is_writable: true },
+        // VaultPosition PDA (will be created if needed)
+        LzAccount { pubkey: vault_position, is_signer: false, is_writable: true },
+        cctp_used_nonce,
+        consumed_cctp_nonce,
+        pending_deposit,
+        deposit_receipt,
+        // Payer (Executor) - special sentinel value resolved by Executor
+        LzAccount { pubkey: Pubkey::default(), is_signer: true, is_writable: true },
+        // System program
+        LzAccount { pubkey: anchor_lang::solana_program::system_program::ID, is_signer: false, is_writable: false },
+    ];
+
+    // Append the additional accounts required for `Endpoint::clear`
+    let accounts_for_clear = get_accounts_for_clear(
+        ENDPOINT_ID,
+        &store,
+        params.src_eid,
+        &params.sender,
+        params.nonce,
+    );
+    accounts.extend(accounts_for_clear);
+
+    // Append the CPI accounts in the exact order expected by lz_receive:
+    // strategy deposit legs (plus the send_compose accounts for DepositAndCall), or
+    // strategy withdraw legs followed by the CCTP burn.
+    match message {
+        msg_codec::Message::Deposit { .. } => {
+            accounts.extend(strategy::deposit_accounts(s, &store, configs))
+        },
+        msg_codec::Message::DepositAndCall { amount, evm_address, vault, cctp_nonce, action } => {
+            // Strategy deposits, then Endpoint::send_compose queuing the compose action
+            let compose = msg_codec::ComposeMsg {
+                amount,
+                evm_address,
+                vault,
+                cctp_nonce,
+                src_eid: params.src_eid,
+                action,
+            }
+            .encode();
+            accounts.extend(strategy::deposit_accounts(s, &store, configs));
+            accounts.extend(get_accounts_for_send_compose(
+                ENDPOINT_ID,
+                &store,
+                &store,
+                &params.guid,
+                0,
+                &compose,
+            ));
+        },
+        msg_codec::Message::Withdraw { .. } => {
+            let (message_sent_event_data, _) =
+                Pubkey::find_program_address(&[MESSAGE_SENT_EVENT_SEED, &params.guid], program_id);
+            accounts.extend(strategy::withdraw_accounts(s, &store, configs));
+            accounts.extend(cctp::deposit_for_burn_accounts(s, message_sent_event_data));
+        },
+        _ => return err!(MyOAppError::InvalidMessageType),
+    }
+
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::StrategyKind;
+    use anchor_lang::Discriminator;
+
+    fn deposit_params() -> LzReceiveParams {
+        let message = msg_codec::Message::Deposit {
+            amount: 1_000_000,
+            evm_address: [0x11; 20],
+            vault: [0x22; 20],
+            cctp_nonce: CctpNonce { source_domain: 6, nonce: [0x33; 32] },
+        };
+        LzReceiveParams {
+            src_eid: 30184,
+            sender: [0x44; 32],
+            nonce: 1,
+            guid: [0x55; 32],
+            message: message.encode(),
+            extra_data: vec![],
+        }
+    }
+
+    /// Resolves the accounts from the V1 account set: the store alone, with `weight_bps` on JL
+    fn resolve_v1(jl_weight_bps: u16) -> Result<Vec<LzAccount>> {
+        let mut store = Store::try_from_slice(&[0u8; Store::INIT_SPACE]).unwrap();
+        store.allocation_mut(StrategyKind::JupiterLend).weight_bps = jl_weight_bps;
+        let mut data = Store::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&store.try_to_vec().unwrap());
+        let (key, _) = Pubkey::find_program_address(&[STORE_SEED], &crate::ID);
+        let mut lamports = 0u64;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let store = Account::<Store>::try_from(&info).unwrap();
+        lz_receive_accounts_v1(&crate::ID, &store, &deposit_params())
+    }
+
+    #[test]
+    fn v1_resolves_a_store_without_strategies() {
+        let accounts = resolve_v1(0).unwrap();
+        let (store, _) = Pubkey::find_program_address(&[STORE_SEED], &crate::ID);
+        assert_eq!(accounts[0].pubkey, store);
+        // Payer sentinel right after the deposit accounts
+        assert_eq!(accounts[9].pubkey, Pubkey::default());
+        assert!(accounts[9].is_signer);
+    }
+
+    #[test]
+    fn v1_rejects_allocated_strategies_it_cannot_resolve() {
+        match resolve_v1(10_000).unwrap_err() {
+            Error::AnchorError(e) => {
+                assert_eq!(e.error_code_number, MyOAppError::LzReceiveTypesV2Required.into())
+            },
+            e => panic!("unexpected error {e:?}"),
+        }
     }
 }
@@ -48,16 +48,14 @@ impl LzReceiveTypesInfo<'_> {
     ) -> Result<(u8, LzReceiveTypesV2Accounts)> {
         let receive_types_account = &ctx.accounts.lz_receive_types_accounts;
 
-        let required_accounts = if receive_types_account.alt == Pubkey::default() {
-            vec![
-                receive_types_account.store
-            ]
-        } else {
-            vec![
-                receive_types_account.store,
-                receive_types_account.alt,
-            ]
-        };
+        // lz_receive_types_v2 reads the allocated strategy configs to resolve the strategy legs
+        let mut required_accounts = vec![receive_types_account.store];
+        required_accounts.extend(
+            ctx.accounts.store.allocated_strategies().into_iter().map(|kind| kind.config_address()),
+        );
+        if receive_types_account.alt != Pubkey::default() {
+            required_accounts.push(receive_types_account.alt);
+        }
         Ok((LZ_RECEIVE_TYPES_VERSION, LzReceiveTypesV2Accounts { accounts: required_accounts }))
     }
 }
@@ -0,0 +1,109 @@
+use crate::errors::MyOAppError;
+use crate::instructions::migrate_store::resize_account;
+use crate::*;
+use anchor_lang::Discriminator;
+
+/// Upgrades a PeerConfig created with the original layout (`PeerConfig::V0_SIZE`, single
+/// `send`/`send_and_call` options). The old `send` options applied to every send, so they become
+/// the enforced options of every outbound message type; `send_and_call` had no Solana sender and
+/// is dropped.
+///
+/// Peers this old predate the inbound `Nonce` PDA that `lz_receive` requires, so it is created
+/// here, starting from `params.max_received_nonce` (the highest nonce the peer already delivered,
+/// per the Endpoint) so ordered execution can be turned on for the path later.
+#[derive(Accounts)]
+#[instruction(params: MigratePeerConfigParams)]
+pub struct MigratePeerConfig<'info> {
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    /// CHECK: PeerConfig PDA in the old layout, deserialized by hand
+    #[account(
+        mut,
+        seeds = [PEER_SEED, &store.key().to_bytes(), &params.remote_eid.to_be_bytes()],
+        bump
+    )]
+    pub peer: UncheckedAccount<'info>,
+    /// Inbound nonce PDA for the (remote_eid, peer) path
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = Nonce::SIZE,
+        seeds = [
+            NONCE_SEED,
+            &store.key().to_bytes(),
+            &params.remote_eid.to_be_bytes(),
+            &params.peer_address
+        ],
+        bump
+    )]
+    pub nonce_account: Account<'info, Nonce>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigratePeerConfig<'_> {
+    pub fn apply(ctx: &mut Context<MigratePeerConfig>, params: &MigratePeerConfigParams) -> Result<()> {
+        let info = ctx.accounts.peer.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, MyOAppError::InvalidAccount);
+        let old = {
+            let data = info.try_borrow_data()?;
+            require!(data.len() == PeerConfig::V0_SIZE, MyOAppError::AccountAlreadyMigrated);
+            require!(data[..8] == PeerConfig::DISCRIMINATOR, MyOAppError::InvalidAccount);
+            PeerConfigV0::deserialize(&mut &data[8..])?
+        };
+        require!(old.peer_address == params.peer_address, MyOAppError::InvalidAccount);
+
+        let nonce_account = &mut ctx.accounts.nonce_account;
+        if nonce_account.version == 0 {
+            nonce_account.bump = ctx.bumps.nonce_account;
+            nonce_account.max_received_nonce = params.max_received_nonce;
+            nonce_account.version = Nonce::VERSION;
+        }
+
+        let mut enforced_options = EnforcedOptions::default();
+        // The legacy options applied to every send; carry them over to each outbound type
+        for msg_type in [
+            msg_codec::MsgType::WithdrawComplete,
+            msg_codec::MsgType::BalanceSnapshot,
+            msg_codec::MsgType::Ack,
+        ] {
+            enforced_options.set(msg_type as u8, old.send.clone())?;
+        }
+        let peer = PeerConfig {
+            peer_address: old.peer_address,
+            enforced_options,
+            bump: old.bump,
+            inbound_paused: false,
+            outbound_paused: false,
+            inbound_rate_limit: RateLimit::default(),
+            last_nav_report: 0,
+            version: PeerConfig::VERSION,
+            reserved: [0; 56],
+        };
+
+        let payer = ctx.accounts.config_admin.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        resize_account(&info, &payer, &system_program, peer.space()?)?;
+        peer.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        Ok(())
+    }
+}
+
+/// Original PeerConfig layout
+#[derive(AnchorDeserialize)]
+struct PeerConfigV0 {
+    peer_address: [u8; 32],
+    send: Vec<u8>,
+    _send_and_call: Vec<u8>,
+    bump: u8,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigratePeerConfigParams {
+    pub remote_eid: u32,
+    pub peer_address: [u8; 32],   // Address stored in the old PeerConfig
+    pub max_received_nonce: u64,  // Endpoint inbound nonce of the path
+}
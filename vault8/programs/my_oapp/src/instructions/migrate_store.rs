@@ -0,0 +1,258 @@
+use crate::errors::MyOAppError;
+use crate::instructions::set_jl_config::write_jl_config;
+use crate::strategy::{self, StrategyKind, BPS_DENOMINATOR, STRATEGY_COUNT};
+use crate::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+/// Upgrades a Store created with the original layout (`Store::V0_SIZE`) in place: reallocs it to
+/// `Store::SIZE`, moves its Jupiter Lend pubkeys into the JL `StrategyConfig` PDA, and creates the
+/// Roles and LzComposeTypes PDAs it predates.
+///
+/// The fTokens in the Store fToken ATA become the Jupiter Lend position and are all issued as
+/// vault shares, held for the UserBalances under the old seeds. `params.legacy_total_deposited` is
+/// the sum of their `total_deposited` (that layout never recorded a withdrawal), counted off-chain:
+/// `migrate_user_balance` later hands each record its share of the pool, pro rata to what it
+/// deposited, so no record needs to be passed here and none can be left out.
+#[derive(Accounts)]
+pub struct MigrateStore<'info> {
+    /// Admin recorded in the old Store layout, checked in `apply`
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: Store PDA in the old layout, deserialized by hand
+    #[account(mut, seeds = [STORE_SEED], bump)]
+    pub store: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = StrategyConfig::SIZE,
+        seeds = [STRATEGY_SEED, &StrategyKind::JupiterLend.seed()],
+        bump
+    )]
+    pub strategy: Account<'info, StrategyConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Roles::SIZE,
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Account<'info, Roles>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = LzComposeTypesAccounts::SIZE,
+        seeds = [LZ_COMPOSE_TYPES_SEED, store.key().as_ref()],
+        bump
+    )]
+    pub lz_compose_types_accounts: Account<'info, LzComposeTypesAccounts>,
+    /// CHECK: Store fToken ATA, checked against the old layout in `apply`
+    pub store_f_token_ata: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateStore<'_> {
+    pub fn apply(ctx: &mut Context<MigrateStore>, params: &MigrateStoreParams) -> Result<()> {
+        let store_info = ctx.accounts.store.to_account_info();
+        require_keys_eq!(*store_info.owner, crate::ID, MyOAppError::InvalidAccount);
+        let old = {
+            let data = store_info.try_borrow_data()?;
+            require!(data.len() == Store::V0_SIZE, MyOAppError::AccountAlreadyMigrated);
+            require!(data[..8] == Store::DISCRIMINATOR, MyOAppError::InvalidAccount);
+            StoreV0::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(ctx.accounts.admin.key(), old.admin, MyOAppError::InvalidAccount);
+
+        let mut allocations = [StrategyAllocation::default(); STRATEGY_COUNT];
+        allocations[StrategyKind::JupiterLend.index()].weight_bps = BPS_DENOMINATOR as u16;
+        let mut store = Store {
+            admin: old.admin,
+            pending_admin: Pubkey::default(),
+            bump: old.bump,
+            endpoint_program: old.endpoint_program,
+            usdc_mint: old.usdc_mint,
+            token_program: old.token_program,
+            associated_token_program: old.associated_token_program,
+            system_program: old.system_program,
+            allocations,
+            total_shares: 0,
+            idle_target_bps: 0,
+            idle_assets: 0,
+            parked_assets: 0,
+            ordered_nonce: false,
+            paused: false,
+            user_deposit_cap: 0,
+            tvl_cap: 0,
+            config_delay: 0,
+            config_change_count: 0,
+            nav_report_interval: 0,
+            version: Store::VERSION,
+            legacy_shares: 0,
+            legacy_deposits: 0,
+            reserved: [0; 96],
+        };
+        let jl_params = old.jl_config();
+        write_jl_config(&mut store, &mut ctx.accounts.strategy, ctx.bumps.strategy, &jl_params)?;
+
+        // Every fToken the Store holds backs the legacy deposits
+        let store_key = store_info.key();
+        let f_token_ata = ctx.accounts.store_f_token_ata.to_account_info();
+        let expected_ata = store.associated_token_address(&store_key, &old.jl_f_token_mint);
+        require_keys_eq!(f_token_ata.key(), expected_ata, MyOAppError::InvalidAccount);
+        let f_tokens = strategy::token_amount(&store, &f_token_ata)?;
+        store.allocation_mut(StrategyKind::JupiterLend).shares = f_tokens;
+        // Outstanding from the start, so later depositors mint at the legacy share price
+        store.total_shares = f_tokens;
+        store.legacy_shares = f_tokens;
+        store.legacy_deposits = params.legacy_total_deposited;
+
+        let admin = ctx.accounts.admin.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        resize_account(&store_info, &admin, &system_program, Store::SIZE)?;
+        store.try_serialize(&mut &mut store_info.try_borrow_mut_data()?[..])?;
+
+        if ctx.accounts.roles.version == 0 {
+            ctx.accounts.roles.init(old.admin, ctx.bumps.roles);
+        }
+        ctx.accounts.lz_compose_types_accounts.store = store_key;
+        ctx.accounts.lz_compose_types_accounts.bump = ctx.bumps.lz_compose_types_accounts;
+        Ok(())
+    }
+}
+
+/// Grows `account` to `len` bytes, topping its rent exemption up from `payer`. Shared by the
+/// migration instructions.
+pub(crate) fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    len: usize,
+) -> Result<()> {
+    let missing = Rent::get()?.minimum_balance(len).saturating_sub(account.lamports());
+    if missing > 0 {
+        let accounts = Transfer { from: payer.clone(), to: account.clone() };
+        transfer(CpiContext::new(system_program.clone(), accounts), missing)?;
+    }
+    account.realloc(len, true)?;
+    Ok(())
+}
+
+/// Original Store layout, with the Jupiter Lend config inlined
+#[derive(AnchorDeserialize)]
+struct StoreV0 {
+    admin: Pubkey,
+    bump: u8,
+    endpoint_program: Pubkey,
+    usdc_mint: Pubkey,
+    token_program: Pubkey,
+    associated_token_program: Pubkey,
+    system_program: Pubkey,
+    jl_lending_program: Pubkey,
+    jl_liquidity_program: Pubkey,
+    jl_lending_admin: Pubkey,
+    jl_lending: Pubkey,
+    jl_f_token_mint: Pubkey,
+    jl_supply_token_reserves_liquidity: Pubkey,
+    jl_lending_supply_position_on_liquidity: Pubkey,
+    jl_rate_model: Pubkey,
+    jl_vault: Pubkey,
+    jl_liquidity: Pubkey,
+    jl_rewards_rate_model: Pubkey,
+}
+
+impl StoreV0 {
+    fn jl_config(&self) -> SetJlConfigParams {
+        SetJlConfigParams {
+            usdc_mint: self.usdc_mint,
+            token_program: self.token_program,
+            associated_token_program: self.associated_token_program,
+            system_program: self.system_program,
+            jl_lending_program: self.jl_lending_program,
+            jl_liquidity_program: self.jl_liquidity_program,
+            jl_lending_admin: self.jl_lending_admin,
+            jl_lending: self.jl_lending,
+            jl_f_token_mint: self.jl_f_token_mint,
+            jl_supply_token_reserves_liquidity: self.jl_supply_token_reserves_liquidity,
+            jl_lending_supply_position_on_liquidity: self.jl_lending_supply_position_on_liquidity,
+            jl_rate_model: self.jl_rate_model,
+            jl_vault: self.jl_vault,
+            jl_liquidity: self.jl_liquidity,
+            jl_rewards_rate_model: self.jl_rewards_rate_model,
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateStoreParams {
+    pub legacy_total_deposited: u64, // Sum of total_deposited over the UserBalances under the old seeds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::migrate_user_balance::{merge_legacy, read_legacy};
+
+    const VAULT: [u8; 20] = [0xaa; 20];
+
+    /// UserBalance in the original layout under its old seeds
+    fn legacy_user_balance(evm_address: [u8; 20], total_deposited: u64) -> Vec<u8> {
+        let (_, bump) =
+            Pubkey::find_program_address(&[USER_BALANCE_SEED, &evm_address], &crate::ID);
+        let mut data = UserBalance::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&evm_address);
+        data.extend_from_slice(&total_deposited.to_le_bytes());
+        data.extend_from_slice(&[0u8; 8 + 8]); // total_withdrawn, ftoken_balance
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.push(bump);
+        assert_eq!(data.len(), UserBalance::V0_SIZE);
+        data
+    }
+
+    #[test]
+    fn store_v0_reads_the_f_token_mint_at_its_offset() {
+        // The deploy helper reads the fToken mint at this offset to derive the Store fToken ATA
+        let f_token_mint = Pubkey::new_unique();
+        let mut data = vec![0u8; Store::V0_SIZE];
+        data[..8].copy_from_slice(&Store::DISCRIMINATOR);
+        let offset = 8 + 32 + 1 + 32 + (4 * 32) + (4 * 32);
+        data[offset..offset + 32].copy_from_slice(&f_token_mint.to_bytes());
+        let old = StoreV0::deserialize(&mut &data[8..]).unwrap();
+        assert_eq!(old.jl_f_token_mint, f_token_mint);
+    }
+
+    #[test]
+    fn legacy_balances_get_shares_pro_rata_to_their_deposits() {
+        // 3 USDC deposited, grown to 3_100_000 fTokens worth of Jupiter Lend shares
+        let mut store = Store::try_from_slice(&[0u8; Store::INIT_SPACE]).unwrap();
+        store.total_shares = 3_100_000;
+        store.legacy_shares = 3_100_000;
+        store.legacy_deposits = 3_000_000;
+
+        // Both records move to the same vault's position, in any order
+        let mut position = VaultPosition::try_from_slice(&[0u8; VaultPosition::INIT_SPACE]).unwrap();
+        let mut assigned = 0;
+        for (evm_address, deposited, expected) in
+            [([2u8; 20], 2_000_000, 2_066_666), ([1u8; 20], 1_000_000, 1_033_334)]
+        {
+            let data = legacy_user_balance(evm_address, deposited);
+            let mut old = read_legacy(&data).unwrap();
+            assert_eq!(old.deposit_count, 3);
+            old.shares = store.take_legacy_shares(old.total_deposited).unwrap();
+            assert_eq!(old.shares, expected);
+
+            let mut user_balance =
+                UserBalance::try_from_slice(&[0u8; UserBalance::INIT_SPACE]).unwrap();
+            position.open_if_new(30184, VAULT, 255, &mut user_balance).unwrap();
+            position.link(&mut old).unwrap();
+            merge_legacy(&mut user_balance, &old).unwrap();
+            assert_eq!(user_balance.shares, expected);
+            assigned += user_balance.shares;
+        }
+        // Store.total_shares is exactly what the users hold once every record has moved
+        assert_eq!(store.total_shares, assigned);
+        assert_eq!(store.legacy_shares, 0);
+        assert_eq!(position.shares, store.total_shares);
+        assert_eq!(position.total_deposited, 3_000_000);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::errors::MyOAppError;
+use crate::*;
+use anchor_lang::Discriminator;
+
+/// Moves a UserBalance created before balances were namespaced by source chain (seeds
+/// [USER_BALANCE_SEED, evm_address]) to [USER_BALANCE_SEED, src_eid, evm_address], then closes the
+/// old account to the config admin.
+///
+/// The old record does not say which chain it came from, so the config admin picks `src_eid`
+/// (it must have a peer). If `lz_receive` already opened the new record, the old one is merged
+/// into it. It takes its part of the legacy shares `migrate_store` set aside, pro rata to what it
+/// deposited, into the position of `vault` on `src_eid`.
+///
+/// This deliberately departs from the permissionless `migrate_user_balance` the layout migration
+/// asked for. The attribution decides who can withdraw the shares and which vault the UserBalance
+/// is linked to for good, and neither is recorded anywhere the program could check a caller's
+/// choice against, so only the config admin may make it. Users whose record is not yet migrated
+/// keep their part of the legacy shares; it is only unreachable until the config admin moves them.
+#[derive(Accounts)]
+#[instruction(params: MigrateUserBalanceParams)]
+pub struct MigrateUserBalance<'info> {
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    /// Peer of the chain the old balance is attributed to
+    #[account(
+        seeds = [PEER_SEED, &store.key().to_bytes(), &params.src_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    /// CHECK: UserBalance PDA under the old seeds, deserialized by hand
+    #[account(mut, seeds = [USER_BALANCE_SEED, &params.evm_address], bump)]
+    pub legacy_user_balance: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = UserBalance::SIZE,
+        seeds = [USER_BALANCE_SEED, &params.src_eid.to_be_bytes(), &params.evm_address],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = VaultPosition::SIZE,
+        seeds = [VAULT_POSITION_SEED, &params.src_eid.to_be_bytes(), &params.vault],
+        bump
+    )]
+    pub vault_position: Account<'info, VaultPosition>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateUserBalance<'_> {
+    pub fn apply(ctx: &mut Context<MigrateUserBalance>, params: &MigrateUserBalanceParams) -> Result<()> {
+        let info = ctx.accounts.legacy_user_balance.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, MyOAppError::AccountAlreadyMigrated);
+        let mut old = read_legacy(&info.try_borrow_data()?)?;
+
+        let a = &mut ctx.accounts;
+        old.shares = a.store.take_legacy_shares(old.total_deposited)?;
+        let user_balance = &mut a.user_balance;
+        if user_balance.version == 0 {
+            user_balance.src_eid = params.src_eid;
+            user_balance.evm_address = params.evm_address;
+            user_balance.bump = ctx.bumps.user_balance;
+            user_balance.version = UserBalance::VERSION;
+        }
+        // Both records must end up in the same position
+        a.vault_position.open_if_new(
+            params.src_eid,
+            params.vault,
+            ctx.bumps.vault_position,
+            user_balance,
+        )?;
+        a.vault_position.link(&mut old)?;
+        merge_legacy(user_balance, &old)?;
+
+        // Close the old account to the config admin, who paid for the new one
+        let config_admin = a.config_admin.to_account_info();
+        let rent = info.lamports();
+        **config_admin.try_borrow_mut_lamports()? =
+            config_admin.lamports().checked_add(rent).ok_or(MyOAppError::Overflow)?;
+        **info.try_borrow_mut_lamports()? = 0;
+        info.assign(&anchor_lang::solana_program::system_program::ID);
+        info.realloc(0, false)?;
+        Ok(())
+    }
+}
+
+/// Reads a UserBalance under the old seeds. Fields were only ever appended (or carved from zeroed
+/// reserved space), so zero padding reads the original layout.
+pub(crate) fn read_legacy(data: &[u8]) -> Result<UserBalance> {
+    require!(data.len() <= UserBalance::SIZE, MyOAppError::InvalidAccount);
+    require!(data[..8] == UserBalance::DISCRIMINATOR, MyOAppError::InvalidAccount);
+    let mut padded = data.to_vec();
+    padded.resize(UserBalance::SIZE, 0);
+    UserBalance::try_deserialize(&mut &padded[..])
+}
+
+/// Adds the totals of a legacy record to the UserBalance it moves into.
+pub(crate) fn merge_legacy(user_balance: &mut UserBalance, old: &UserBalance) -> Result<()> {
+    user_balance.shares =
+        user_balance.shares.checked_add(old.shares).ok_or(MyOAppError::Overflow)?;
+    user_balance.total_deposited = user_balance
+        .total_deposited
+        .checked_add(old.total_deposited)
+        .ok_or(MyOAppError::Overflow)?;
+    user_balance.total_withdrawn = user_balance
+        .total_withdrawn
+        .checked_add(old.total_withdrawn)
+        .ok_or(MyOAppError::Overflow)?;
+    user_balance.deposit_count =
+        user_balance.deposit_count.checked_add(old.deposit_count).ok_or(MyOAppError::Overflow)?;
+    user_balance.last_updated = user_balance.last_updated.max(old.last_updated);
+    Ok(())
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateUserBalanceParams {
+    pub evm_address: [u8; 20],
+    pub src_eid: u32,      // Chain the old balance is attributed to
+    pub vault: [u8; 20],   // EVM vault the pooled shares are attributed to
+}
@@ -1,23 +1,91 @@
 pub mod send;
+pub mod send_deposit_ack;
+pub mod accept_admin;
+pub mod cancel_config_change;
+pub mod execute_config_change;
+pub mod get_user_value;
+pub mod get_vault_value;
 pub mod init_store;
 pub mod init_store_atas;
+pub mod lz_compose;
+pub mod lz_compose_types;
 pub mod lz_receive;
 pub mod lz_receive_types;
 pub mod lz_receive_types_info;
 pub mod lz_receive_types_v2;
+pub mod migrate_peer_config;
+pub mod migrate_store;
+pub mod migrate_user_balance;
+pub mod next_nonce;
+pub mod propose_admin;
+pub mod queue_config_change;
 pub mod quote_send;
+pub mod report_nav;
+pub mod rebalance;
+pub mod rebalance_types;
 pub mod set_peer_config;
+pub mod set_role;
 pub mod set_jl_config;
+pub mod set_kamino_config;
+pub mod set_marginfi_config;
+pub mod set_save_config;
+pub mod init_marginfi_account;
 pub mod set_alt;
+pub mod set_config_delay;
+pub mod set_deposit_caps;
+pub mod set_idle_target;
+pub mod set_nav_report_interval;
+pub mod set_ordered_nonce;
+pub mod set_paused;
+pub mod set_peer_paused;
+pub mod set_strategy_config;
+pub mod set_strategy_weights;
+pub mod settle_pending_deposit;
+pub mod skip_inbound_nonce;
+pub mod sweep_idle;
 
 pub use send::*;
+pub use send_deposit_ack::*;
+pub use accept_admin::*;
+pub use cancel_config_change::*;
+pub use execute_config_change::*;
+pub use get_user_value::*;
+pub use get_vault_value::*;
 pub use init_store::*;
 pub use init_store_atas::*;
+pub use lz_compose::*;
+pub use lz_compose_types::*;
 pub use lz_receive::*;
 pub use lz_receive_types::*;
 pub use lz_receive_types_info::*;
 pub use lz_receive_types_v2::*;
+pub use migrate_peer_config::*;
+pub use migrate_store::*;
+pub use migrate_user_balance::*;
+pub use next_nonce::*;
+pub use propose_admin::*;
+pub use queue_config_change::*;
 pub use quote_send::*;
+pub use report_nav::*;
+pub use rebalance::*;
+pub use rebalance_types::*;
 pub use set_peer_config::*;
+pub use set_role::*;
 pub use set_jl_config::*;
+pub use set_kamino_config::*;
+pub use set_marginfi_config::*;
+pub use set_save_config::*;
+pub use init_marginfi_account::*;
 pub use set_alt::*;
+pub use set_config_delay::*;
+pub use set_deposit_caps::*;
+pub use set_idle_target::*;
+pub use set_nav_report_interval::*;
+pub use set_ordered_nonce::*;
+pub use set_paused::*;
+pub use set_peer_paused::*;
+pub use set_strategy_config::*;
+pub use set_strategy_weights::*;
+pub use settle_pending_deposit::*;
+pub use skip_inbound_nonce::*;
+pub use sweep_idle::*;
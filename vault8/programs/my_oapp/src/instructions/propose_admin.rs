@@ -0,0 +1,27 @@
+use crate::*;
+
+/// First step of an admin transfer: records `pending_admin`, who must call `accept_admin`.
+/// Proposing `Pubkey::default()` cancels a pending transfer.
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(address = store.admin)]
+    pub admin: Signer<'info>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl ProposeAdmin<'_> {
+    pub fn apply(ctx: &mut Context<ProposeAdmin>, params: &ProposeAdminParams) -> Result<()> {
+        ctx.accounts.store.pending_admin = params.pending_admin;
+        emit!(AdminProposedEvent {
+            admin: ctx.accounts.store.admin,
+            pending_admin: params.pending_admin,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAdminParams {
+    pub pending_admin: Pubkey,
+}
@@ -0,0 +1,58 @@
+use crate::instructions::set_strategy_config::check_generic_kind;
+use crate::*;
+
+/// Queues a timelocked config change, executable by `execute_config_change` once
+/// `Store.config_delay` has elapsed. The full update is emitted so monitoring can review it.
+///
+/// A `JlConfig` update is validated here, with the accounts `jupiter_lend::validate_config` takes
+/// passed as remaining accounts; a `StrategyConfig` update may not target Jupiter Lend.
+#[derive(Accounts)]
+#[instruction(params: QueueConfigChangeParams)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(
+        init,
+        payer = config_admin,
+        space = ConfigChange::space(&params.update),
+        seeds = [CONFIG_CHANGE_SEED, &store.config_change_count.to_be_bytes()],
+        bump
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+    pub system_program: Program<'info, System>,
+}
+
+impl QueueConfigChange<'_> {
+    pub fn apply(ctx: &mut Context<QueueConfigChange>, params: &QueueConfigChangeParams) -> Result<()> {
+        match &params.update {
+            ConfigUpdate::JlConfig(jl_params) => {
+                jupiter_lend::validate_config(jl_params, ctx.remaining_accounts)?;
+            },
+            ConfigUpdate::StrategyConfig(strategy_params) => check_generic_kind(strategy_params)?,
+            _ => {},
+        }
+
+        let store = &mut ctx.accounts.store;
+        let eta = Clock::get()?.unix_timestamp.saturating_add(store.config_delay);
+
+        let change = &mut ctx.accounts.config_change;
+        change.id = store.config_change_count;
+        change.eta = eta;
+        change.update = params.update.clone();
+        change.bump = ctx.bumps.config_change;
+        change.version = ConfigChange::VERSION;
+        store.config_change_count += 1;
+
+        emit!(ConfigChangeQueuedEvent { id: change.id, update: params.update.clone(), eta });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct QueueConfigChangeParams {
+    pub update: ConfigUpdate,
+}
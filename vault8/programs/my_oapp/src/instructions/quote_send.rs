@@ -21,12 +21,16 @@ pub struct QuoteSend<'info> {
     pub peer: Account<'info, PeerConfig>,
     #[account(seeds = [ENDPOINT_SEED], bump = endpoint.bump, seeds::program = ENDPOINT_ID)]
     pub endpoint: Account<'info, EndpointSettings>,
+    /// Required to quote a `BalanceSnapshot`
+    pub user_balance: Option<Account<'info, UserBalance>>,
 }
 
 impl<'info> QuoteSend<'info> {
     pub fn apply(ctx: &Context<QuoteSend>, params: &QuoteSendParams) -> Result<MessagingFee> {
-        // Encode the payload for quoting
-        let message = msg_codec::encode(&params.message);
+        // Encode the payload for quoting, exactly as `send` does
+        let message = params.message.to_message(ctx.accounts.user_balance.as_deref())?;
+        let msg_type = message.msg_type() as u8;
+        let message = message.encode();
 
         // Ask the Endpoint how much a send would cost
         let quote_params = QuoteParams {
@@ -39,7 +43,7 @@ impl<'info> QuoteSend<'info> {
                 .accounts
                 .peer
                 .enforced_options
-                .combine_options(&None::<Vec<u8>>,&params.options)?,
+                .combine_options(msg_type, &params.options)?,
         };
         oapp::endpoint_cpi::quote(ENDPOINT_ID, ctx.remaining_accounts, quote_params)
     }
@@ -49,7 +53,7 @@ impl<'info> QuoteSend<'info> {
 pub struct QuoteSendParams {
     pub dst_eid: u32,
     pub receiver: [u8; 32],
-    pub message: String,
+    pub message: OutboundMessage,
     pub options: Vec<u8>,
     pub pay_in_lz_token: bool,
 }
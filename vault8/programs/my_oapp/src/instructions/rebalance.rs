@@ -0,0 +1,81 @@
+use crate::errors::MyOAppError;
+use crate::strategy::StrategyKind;
+use crate::*;
+
+/// Moves funds between two configured strategies in one transaction: withdraws `amount` USDC from
+/// `from` into the Store USDC ATA, then deposits whatever actually arrived into `to`. Vault shares
+/// are untouched; only the Store's venue positions change.
+///
+/// Remaining accounts are the `from` withdraw accounts followed by the `to` deposit accounts, as
+/// resolved by `rebalance_types`.
+#[derive(Accounts)]
+#[instruction(params: RebalanceParams)]
+pub struct Rebalance<'info> {
+    #[account(address = roles.strategy_keeper)]
+    pub keeper: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump, constraint = !store.paused @ MyOAppError::Paused)]
+    pub store: Account<'info, Store>,
+    #[account(seeds = [STRATEGY_SEED, &params.from.seed()], bump = from_strategy.bump)]
+    pub from_strategy: Account<'info, StrategyConfig>,
+    #[account(seeds = [STRATEGY_SEED, &params.to.seed()], bump = to_strategy.bump)]
+    pub to_strategy: Account<'info, StrategyConfig>,
+}
+
+impl Rebalance<'_> {
+    pub fn apply(ctx: &mut Context<Rebalance>, params: &RebalanceParams) -> Result<()> {
+        require!(params.from != params.to, MyOAppError::InvalidStrategyConfig);
+        require!(params.amount > 0, MyOAppError::ZeroAmount);
+
+        let store_key = ctx.accounts.store.key();
+        let from = &ctx.accounts.from_strategy;
+        let to = &ctx.accounts.to_strategy;
+        let ra = ctx.remaining_accounts;
+        let withdraw_end = from.kind.adapter().withdraw_accounts_len();
+        let deposit_end = withdraw_end + to.kind.adapter().deposit_accounts_len();
+        require!(ra.len() >= deposit_end, MyOAppError::InvalidAccount);
+        let withdraw_accounts = &ra[..withdraw_end];
+        let deposit_accounts = &ra[withdraw_end..deposit_end];
+
+        // Measure the USDC the source venue actually pays out; that is what gets re-deposited
+        let store = &ctx.accounts.store;
+        let store_usdc_ata = strategy::find_account(
+            withdraw_accounts,
+            &store.associated_token_address(&store_key, &store.usdc_mint),
+        )?;
+        let usdc_before = strategy::token_amount(store, store_usdc_ata)?;
+        let from_shares =
+            from.kind.adapter().withdraw(store, &store_key, from, withdraw_accounts, params.amount)?;
+        let received = strategy::token_amount(store, store_usdc_ata)?
+            .checked_sub(usdc_before)
+            .ok_or(MyOAppError::Overflow)?;
+        require!(received >= params.min_amount_out, MyOAppError::SlippageExceeded);
+        let to_shares = to.kind.adapter().deposit(store, &store_key, to, deposit_accounts, received)?;
+
+        let store = &mut ctx.accounts.store;
+        let allocation = store.allocation_mut(params.from);
+        allocation.shares = allocation.shares.checked_sub(from_shares).ok_or(MyOAppError::Overflow)?;
+        let allocation = store.allocation_mut(params.to);
+        allocation.shares = allocation.shares.checked_add(to_shares).ok_or(MyOAppError::Overflow)?;
+
+        emit!(RebalanceEvent {
+            from: params.from,
+            to: params.to,
+            amount: params.amount,
+            received,
+            from_shares,
+            to_shares,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RebalanceParams {
+    pub from: StrategyKind,
+    pub to: StrategyKind,
+    pub amount: u64,          // USDC to withdraw from `from` (base units)
+    pub min_amount_out: u64,  // Minimum USDC `from` must pay out, or the rebalance reverts
+}
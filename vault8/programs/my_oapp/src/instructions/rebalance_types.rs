@@ -0,0 +1,65 @@
+use crate::*;
+use anchor_lang::InstructionData;
+use oapp::{
+    common::{compact_accounts_with_alts, AccountMetaRef, AddressLocator, EXECUTION_CONTEXT_VERSION_1},
+    lz_receive_types_v2::{Instruction, LzReceiveTypesV2Result},
+};
+
+/// Compute budget requested for a rebalance: two venue CPIs, each possibly preceded by a refresh
+pub const REBALANCE_COMPUTE_UNITS: u32 = 1_000_000;
+
+/// Returns the execution plan for `rebalance` in the same format `lz_receive_types_v2` uses, with
+/// accounts compressed through the ALTs passed as remaining accounts. The strategy keeper is
+/// returned as `AddressLocator::Payer`: whoever submits the plan signs it.
+#[derive(Accounts)]
+#[instruction(params: RebalanceParams)]
+pub struct RebalanceTypes<'info> {
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(seeds = [STRATEGY_SEED, &params.from.seed()], bump = from_strategy.bump)]
+    pub from_strategy: Account<'info, StrategyConfig>,
+    #[account(seeds = [STRATEGY_SEED, &params.to.seed()], bump = to_strategy.bump)]
+    pub to_strategy: Account<'info, StrategyConfig>,
+}
+
+impl RebalanceTypes<'_> {
+    pub fn apply(ctx: &Context<RebalanceTypes>, params: &RebalanceParams) -> Result<LzReceiveTypesV2Result> {
+        let s = &ctx.accounts.store;
+        let store = s.key();
+        let from = &ctx.accounts.from_strategy;
+        let to = &ctx.accounts.to_strategy;
+
+        let mut accounts = vec![
+            // keeper (signer) - AddressLocator::Payer resolved by the keeper
+            AccountMetaRef { pubkey: AddressLocator::Payer, is_writable: false },
+            // roles (read-only)
+            AccountMetaRef { pubkey: ctx.accounts.roles.key().into(), is_writable: false },
+            // store (mutable)
+            AccountMetaRef { pubkey: store.into(), is_writable: true },
+            // Source and destination strategy config PDAs (read-only)
+            AccountMetaRef { pubkey: from.key().into(), is_writable: false },
+            AccountMetaRef { pubkey: to.key().into(), is_writable: false },
+        ];
+
+        // Source withdraw accounts, then destination deposit accounts (compressed via ALT)
+        let mut cpi_accounts = from.kind.adapter().withdraw_accounts(s, &store, from);
+        cpi_accounts.extend(to.kind.adapter().deposit_accounts(s, &store, to));
+        accounts.extend(cpi_accounts.into_iter().map(|lz_account| AccountMetaRef {
+            pubkey: lz_account.pubkey.into(),
+            is_writable: lz_account.is_writable,
+        }));
+
+        Ok(LzReceiveTypesV2Result {
+            context_version: EXECUTION_CONTEXT_VERSION_1,
+            alts: ctx.remaining_accounts.iter().map(|alt| alt.key()).collect(),
+            instructions: vec![Instruction::Standard {
+                program_id: *ctx.program_id,
+                accounts: compact_accounts_with_alts(ctx.remaining_accounts, accounts)?,
+                data: crate::instruction::Rebalance { params: params.clone() }.data(),
+                compute_units: REBALANCE_COMPUTE_UNITS,
+            }],
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use crate::*;
+use crate::errors::MyOAppError;
+use oapp::endpoint::{
+    instructions::SendParams, state::EndpointSettings, ENDPOINT_SEED, ID as ENDPOINT_ID,
+};
+
+/// Permissionless crank sending the vault's NAV to an EVM peer, at most once per
+/// `Store.nav_report_interval` per peer.
+///
+/// The value is priced on-chain like `get_user_value`: the idle buffer plus each allocated
+/// strategy's venue shares (for Jupiter Lend, fTokens at the lending account's exchange price).
+/// Remaining accounts are one pricing leg per allocated strategy (config PDA, then price account),
+/// followed by the Endpoint send accounts; the caller pays the fee.
+#[derive(Accounts)]
+#[instruction(params: ReportNavParams)]
+pub struct ReportNav<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = !store.paused @ MyOAppError::Paused)]
+    /// OApp Store PDA that signs the send instruction
+    pub store: Account<'info, Store>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, &store.key().to_bytes(), &params.dst_eid.to_be_bytes()],
+        bump = peer.bump,
+        constraint = !peer.outbound_paused @ MyOAppError::PeerOutboundPaused
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(seeds = [ENDPOINT_SEED], bump = endpoint.bump, seeds::program = ENDPOINT_ID)]
+    pub endpoint: Account<'info, EndpointSettings>,
+}
+
+impl ReportNav<'_> {
+    pub fn apply(ctx: &mut Context<ReportNav>, params: &ReportNavParams) -> Result<()> {
+        let clock = Clock::get()?;
+        let store = &ctx.accounts.store;
+        let peer = &ctx.accounts.peer;
+        let next_report = peer.last_nav_report.saturating_add(store.nav_report_interval);
+        require!(
+            peer.last_nav_report == 0 || clock.unix_timestamp >= next_report,
+            MyOAppError::NavReportTooSoon
+        );
+
+        let pricing_len = strategy::pricing_accounts_len(store);
+        require!(ctx.remaining_accounts.len() >= pricing_len, MyOAppError::InvalidAccount);
+        let (pricing_accounts, send_accounts) = ctx.remaining_accounts.split_at(pricing_len);
+        let value = strategy::total_assets(store, pricing_accounts)?;
+
+        let message = msg_codec::Message::Nav {
+            value,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        }
+        .encode();
+        let seeds: &[&[u8]] = &[STORE_SEED, &[store.bump]];
+        let send_params = SendParams {
+            dst_eid: params.dst_eid,
+            receiver: peer.peer_address,
+            message,
+            options: peer
+                .enforced_options
+                .combine_options(msg_codec::MsgType::Nav as u8, &params.options)?,
+            native_fee: params.native_fee,
+            lz_token_fee: params.lz_token_fee,
+        };
+        let messaging_receipt =
+            oapp::endpoint_cpi::send(ENDPOINT_ID, store.key(), send_accounts, seeds, send_params)?;
+
+        emit!(NavReportedEvent {
+            guid: messaging_receipt.guid,
+            dst_eid: params.dst_eid,
+            value,
+            total_shares: store.total_shares,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        });
+
+        ctx.accounts.peer.last_nav_report = clock.unix_timestamp;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReportNavParams {
+    pub dst_eid: u32,
+    pub options: Vec<u8>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
@@ -1,9 +1,17 @@
 use crate::*;
+use crate::errors::MyOAppError;
 use anchor_lang::prelude::*;
 use oapp::endpoint::{
     instructions::SendParams, state::EndpointSettings, ENDPOINT_SEED, ID as ENDPOINT_ID,
 };
 
+/// Sends a typed message to a peer. Who may send depends on the type:
+/// - `WithdrawComplete`: the strategy keeper, which attests the payout.
+/// - `BalanceSnapshot`: anyone. The body is read from the passed `UserBalance`.
+/// - `Ack`: nobody. ACKs are built by the program from a `DepositReceipt` in `send_deposit_ack`.
+/// - `Nav`: nobody. NAV reports are priced on-chain by `report_nav`.
+///
+/// Enforced options are looked up under the message type.
 #[derive(Accounts)]
 #[instruction(params: SendMessageParams)]
 pub struct Send<'info> {
@@ -13,21 +21,40 @@ pub struct Send<'info> {
             &store.key().to_bytes(),
             &params.dst_eid.to_be_bytes()
         ],
-        bump = peer.bump
+        bump = peer.bump,
+        constraint = !peer.outbound_paused @ MyOAppError::PeerOutboundPaused
     )]
     /// Configuration for the destination chain. Holds the peer address and any
     /// enforced messaging options.
     pub peer: Account<'info, PeerConfig>,
-    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = !store.paused @ MyOAppError::Paused)]
     /// OApp Store PDA that signs the send instruction
     pub store: Account<'info, Store>,
     #[account(seeds = [ENDPOINT_SEED], bump = endpoint.bump, seeds::program = ENDPOINT_ID)]
     pub endpoint: Account<'info, EndpointSettings>,
+    /// Required for `WithdrawComplete`
+    #[account(address = roles.strategy_keeper)]
+    pub keeper: Option<Signer<'info>>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    /// Required for `BalanceSnapshot`: the user's balance from the destination chain
+    #[account(constraint = user_balance.src_eid == params.dst_eid @ MyOAppError::InvalidAccount)]
+    pub user_balance: Option<Account<'info, UserBalance>>,
 }
 impl<'info> Send<'info> {
     pub fn apply(ctx: &mut Context<Send>, params: &SendMessageParams) -> Result<()> {
-        // Serialize the message according to our codec
-        let message = msg_codec::encode(&params.message);
+        match params.message {
+            OutboundMessage::WithdrawComplete { .. } => {
+                require!(ctx.accounts.keeper.is_some(), MyOAppError::UnauthorizedSender)
+            },
+            OutboundMessage::BalanceSnapshot => {},
+            OutboundMessage::Ack { .. } | OutboundMessage::Nav { .. } => {
+                return err!(MyOAppError::UnauthorizedSender)
+            },
+        }
+        let message = params.message.to_message(ctx.accounts.user_balance.as_deref())?;
+        let msg_type = message.msg_type() as u8;
+        let message = message.encode();
         // Prepare the seeds for the OApp Store PDA, which is used to sign the CPI call to the Endpoint program.
         let seeds: &[&[u8]] = &[STORE_SEED, &[ctx.accounts.store.bump]];
 
@@ -40,7 +67,7 @@ impl<'info> Send<'info> {
                 .accounts
                 .peer
                 .enforced_options
-                .combine_options( &None::<Vec<u8>>, &params.options)?,
+                .combine_options(msg_type, &params.options)?,
             native_fee: params.native_fee,
             lz_token_fee: params.lz_token_fee,
         };
@@ -59,8 +86,47 @@ impl<'info> Send<'info> {
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct SendMessageParams {
     pub dst_eid: u32,
-    pub message: String,
+    pub message: OutboundMessage,
     pub options: Vec<u8>,
     pub native_fee: u64,
     pub lz_token_fee: u64,
 }
+
+/// Message types the OApp sends to its peers, see `msg_codec::Message` for the wire format
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum OutboundMessage {
+    /// The withdrawal received as `guid` was paid out to `evm_address`
+    WithdrawComplete { amount: u64, evm_address: [u8; 20], guid: [u8; 32] },
+    /// Current state of the passed `UserBalance`
+    BalanceSnapshot,
+    /// Deposit acknowledgement; only quoted here, sent by `send_deposit_ack`
+    Ack { amount: u64, guid: [u8; 32] },
+    /// NAV report; only quoted here, sent by `report_nav`
+    Nav { value: u64, timestamp: i64, slot: u64 },
+}
+
+impl OutboundMessage {
+    /// Builds the wire message, reading a snapshot's body from `user_balance`.
+    pub fn to_message(&self, user_balance: Option<&UserBalance>) -> Result<msg_codec::Message> {
+        let message = match *self {
+            OutboundMessage::WithdrawComplete { amount, evm_address, guid } => {
+                msg_codec::Message::WithdrawComplete { amount, evm_address, guid }
+            },
+            OutboundMessage::BalanceSnapshot => {
+                let user_balance = user_balance.ok_or(MyOAppError::InvalidAccount)?;
+                msg_codec::Message::BalanceSnapshot {
+                    evm_address: user_balance.evm_address,
+                    shares: user_balance.shares,
+                    total_deposited: user_balance.total_deposited,
+                    total_withdrawn: user_balance.total_withdrawn,
+                    timestamp: user_balance.last_updated,
+                }
+            },
+            OutboundMessage::Ack { amount, guid } => msg_codec::Message::Ack { amount, guid },
+            OutboundMessage::Nav { value, timestamp, slot } => {
+                msg_codec::Message::Nav { value, timestamp, slot }
+            },
+        };
+        Ok(message)
+    }
+}
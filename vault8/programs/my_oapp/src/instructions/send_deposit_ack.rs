@@ -0,0 +1,79 @@
+use crate::*;
+use crate::errors::MyOAppError;
+use oapp::endpoint::{
+    instructions::SendParams, state::EndpointSettings, ENDPOINT_SEED, ID as ENDPOINT_ID,
+};
+
+/// Permissionless crank that ACKs a credited deposit back to the EVM peer it came from.
+///
+/// Sends a typed `Ack { amount, guid }` through `Endpoint::send`; the caller pays the fee
+/// (the Endpoint send accounts, payer included, are passed as remaining accounts).
+#[derive(Accounts)]
+pub struct SendDepositAck<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = !store.paused @ MyOAppError::Paused)]
+    /// OApp Store PDA that signs the send instruction
+    pub store: Account<'info, Store>,
+    /// Peer config of the chain the deposit came from
+    #[account(
+        seeds = [PEER_SEED, &store.key().to_bytes(), &deposit_receipt.src_eid.to_be_bytes()],
+        bump = peer.bump,
+        constraint = !peer.outbound_paused @ MyOAppError::PeerOutboundPaused
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(
+        mut,
+        seeds = [DEPOSIT_RECEIPT_SEED, &deposit_receipt.guid],
+        bump = deposit_receipt.bump
+    )]
+    pub deposit_receipt: Account<'info, DepositReceipt>,
+    #[account(seeds = [ENDPOINT_SEED], bump = endpoint.bump, seeds::program = ENDPOINT_ID)]
+    pub endpoint: Account<'info, EndpointSettings>,
+}
+
+impl SendDepositAck<'_> {
+    pub fn apply(ctx: &mut Context<SendDepositAck>, params: &SendDepositAckParams) -> Result<()> {
+        let receipt = &ctx.accounts.deposit_receipt;
+        require!(receipt.credited, MyOAppError::DepositNotCredited);
+        require!(!receipt.acked, MyOAppError::DepositAlreadyAcked);
+
+        let message = msg_codec::Message::Ack { amount: receipt.amount, guid: receipt.guid }.encode();
+        let seeds: &[&[u8]] = &[STORE_SEED, &[ctx.accounts.store.bump]];
+        let send_params = SendParams {
+            dst_eid: receipt.src_eid,
+            receiver: ctx.accounts.peer.peer_address,
+            message,
+            options: ctx
+                .accounts
+                .peer
+                .enforced_options
+                .combine_options(msg_codec::MsgType::Ack as u8, &params.options)?,
+            native_fee: params.native_fee,
+            lz_token_fee: params.lz_token_fee,
+        };
+        let messaging_receipt = oapp::endpoint_cpi::send(
+            ENDPOINT_ID,
+            ctx.accounts.store.key(),
+            ctx.remaining_accounts,
+            seeds,
+            send_params,
+        )?;
+
+        emit!(DepositAckEvent {
+            guid: receipt.guid,
+            ack_guid: messaging_receipt.guid,
+            evm_address: receipt.evm_address,
+            amount: receipt.amount,
+            dst_eid: receipt.src_eid,
+        });
+
+        ctx.accounts.deposit_receipt.acked = true;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SendDepositAckParams {
+    pub options: Vec<u8>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
@@ -17,8 +17,10 @@ pub struct SetAlt<'info> {
     #[account(owner = ALT_PROGRAM_ID)]
     pub alt: UncheckedAccount<'info>,
     
-    #[account(mut, address = store.admin)]
-    pub admin: Signer<'info>,
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
 }
 
 impl SetAlt<'_> {
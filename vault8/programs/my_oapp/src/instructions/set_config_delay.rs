@@ -0,0 +1,32 @@
+use crate::errors::MyOAppError;
+use crate::*;
+
+/// Turns on the config timelock. Like the other direct setters it only works while no delay is
+/// set; afterwards the delay itself changes through `queue_config_change`.
+#[derive(Accounts)]
+pub struct SetConfigDelay<'info> {
+    #[account(address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(
+        mut,
+        seeds = [STORE_SEED],
+        bump = store.bump,
+        constraint = store.config_delay == 0 @ MyOAppError::ConfigTimelocked
+    )]
+    pub store: Account<'info, Store>,
+}
+
+impl SetConfigDelay<'_> {
+    pub fn apply(ctx: &mut Context<SetConfigDelay>, params: &SetConfigDelayParams) -> Result<()> {
+        require!(params.config_delay >= 0, MyOAppError::InvalidConfigDelay);
+        ctx.accounts.store.config_delay = params.config_delay;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetConfigDelayParams {
+    pub config_delay: i64, // Seconds between queueing and executing a config change
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Sets the per-user cap on cumulative deposits and the global TVL cap, both enforced when a
+/// deposit is credited. Zero disables a cap.
+#[derive(Accounts)]
+pub struct SetDepositCaps<'info> {
+    #[account(address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl SetDepositCaps<'_> {
+    pub fn apply(ctx: &mut Context<SetDepositCaps>, params: &SetDepositCapsParams) -> Result<()> {
+        ctx.accounts.store.user_deposit_cap = params.user_deposit_cap;
+        ctx.accounts.store.tvl_cap = params.tvl_cap;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetDepositCapsParams {
+    pub user_deposit_cap: u64,
+    pub tvl_cap: u64,
+}
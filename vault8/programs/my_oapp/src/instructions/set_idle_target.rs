@@ -0,0 +1,28 @@
+use crate::errors::MyOAppError;
+use crate::strategy::BPS_DENOMINATOR;
+use crate::*;
+
+/// Sets the share of total assets (bps) kept uninvested in the Store USDC ATA to serve
+/// withdrawals. Deposits fill the buffer first; `sweep_idle` moves it back toward target.
+#[derive(Accounts)]
+pub struct SetIdleTarget<'info> {
+    #[account(address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl SetIdleTarget<'_> {
+    pub fn apply(ctx: &mut Context<SetIdleTarget>, params: &SetIdleTargetParams) -> Result<()> {
+        require!(params.idle_target_bps as u64 <= BPS_DENOMINATOR, MyOAppError::InvalidIdleTarget);
+        ctx.accounts.store.idle_target_bps = params.idle_target_bps;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetIdleTargetParams {
+    pub idle_target_bps: u16,
+}
@@ -1,35 +1,110 @@
+use crate::strategy::StrategyKind;
+use crate::errors::MyOAppError;
 use crate::*;
 use anchor_lang::prelude::*;
+use crate::instructions::set_strategy_config::write_config;
 
+/// Sets the Store's USDC/SPL program config and writes the Jupiter Lend `StrategyConfig` PDA
+/// (the first strategy), using the JL IDL discriminators. Applied directly only while no config
+/// timelock is set; otherwise through `queue_config_change`.
+///
+/// The JL accounts are passed in so the config is checked against them on-chain
+/// (see `jupiter_lend::validate_config`).
 #[derive(Accounts)]
+#[instruction(params: SetJlConfigParams)]
 pub struct SetJlConfig<'info> {
-    #[account(mut, has_one = admin)]
+    #[account(
+        mut,
+        seeds = [STORE_SEED],
+        bump = store.bump,
+        constraint = store.config_delay == 0 @ MyOAppError::ConfigTimelocked
+    )]
     pub store: Account<'info, Store>,
-    pub admin: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = StrategyConfig::SIZE,
+        seeds = [STRATEGY_SEED, &StrategyKind::JupiterLend.seed()],
+        bump
+    )]
+    pub strategy: Account<'info, StrategyConfig>,
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    /// CHECK: JL lending admin, checked in `jupiter_lend::validate_config`
+    #[account(address = params.jl_lending_admin @ MyOAppError::InvalidAccount)]
+    pub jl_lending_admin: UncheckedAccount<'info>,
+    /// CHECK: JL lending account, checked in `jupiter_lend::validate_config`
+    #[account(address = params.jl_lending @ MyOAppError::InvalidAccount)]
+    pub jl_lending: UncheckedAccount<'info>,
+    /// CHECK: JL fToken mint, checked in `jupiter_lend::validate_config`
+    #[account(address = params.jl_f_token_mint @ MyOAppError::InvalidAccount)]
+    pub jl_f_token_mint: UncheckedAccount<'info>,
+    /// CHECK: JL liquidity vault, checked in `jupiter_lend::validate_config`
+    #[account(address = params.jl_vault @ MyOAppError::InvalidAccount)]
+    pub jl_vault: UncheckedAccount<'info>,
+    /// CHECK: USDC mint, checked in `jupiter_lend::validate_config`
+    #[account(address = params.usdc_mint @ MyOAppError::InvalidAccount)]
+    pub usdc_mint: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 impl SetJlConfig<'_> {
     pub fn apply(ctx: &mut Context<SetJlConfig>, params: SetJlConfigParams) -> Result<()> {
-        let s = &mut ctx.accounts.store;
-        s.usdc_mint = params.usdc_mint;
-        s.token_program = params.token_program;
-        s.associated_token_program = params.associated_token_program;
-        s.system_program = params.system_program;
-        s.jl_lending_program = params.jl_lending_program;
-        s.jl_liquidity_program = params.jl_liquidity_program;
-        s.jl_lending_admin = params.jl_lending_admin;
-        s.jl_lending = params.jl_lending;
-        s.jl_f_token_mint = params.jl_f_token_mint;
-        s.jl_supply_token_reserves_liquidity = params.jl_supply_token_reserves_liquidity;
-        s.jl_lending_supply_position_on_liquidity = params.jl_lending_supply_position_on_liquidity;
-        s.jl_rate_model = params.jl_rate_model;
-        s.jl_vault = params.jl_vault;
-        s.jl_liquidity = params.jl_liquidity;
-        s.jl_rewards_rate_model = params.jl_rewards_rate_model;
-        Ok(())
+        let a = &ctx.accounts;
+        jupiter_lend::validate_config(
+            &params,
+            &[
+                a.jl_lending_admin.to_account_info(),
+                a.jl_lending.to_account_info(),
+                a.jl_f_token_mint.to_account_info(),
+                a.jl_vault.to_account_info(),
+                a.usdc_mint.to_account_info(),
+            ],
+        )?;
+        let strategy = &mut ctx.accounts.strategy;
+        write_jl_config(&mut ctx.accounts.store, strategy, ctx.bumps.strategy, &params)
     }
 }
 
+/// Writes the Store SPL programs and the Jupiter Lend config. Shared with `execute_config_change`.
+pub(crate) fn write_jl_config(
+    store: &mut Store,
+    strategy: &mut StrategyConfig,
+    bump: u8,
+    params: &SetJlConfigParams,
+) -> Result<()> {
+    store.usdc_mint = params.usdc_mint;
+    store.token_program = params.token_program;
+    store.associated_token_program = params.associated_token_program;
+    store.system_program = params.system_program;
+
+    // Venue accounts in the order of the `jupiter_lend` config indices
+    let mut accounts = vec![Pubkey::default(); jupiter_lend::CONFIG_ACCOUNTS_LEN];
+    accounts[jupiter_lend::LIQUIDITY_PROGRAM] = params.jl_liquidity_program;
+    accounts[jupiter_lend::LENDING_ADMIN] = params.jl_lending_admin;
+    accounts[jupiter_lend::LENDING] = params.jl_lending;
+    accounts[jupiter_lend::SUPPLY_TOKEN_RESERVES_LIQUIDITY] =
+        params.jl_supply_token_reserves_liquidity;
+    accounts[jupiter_lend::LENDING_SUPPLY_POSITION_ON_LIQUIDITY] =
+        params.jl_lending_supply_position_on_liquidity;
+    accounts[jupiter_lend::RATE_MODEL] = params.jl_rate_model;
+    accounts[jupiter_lend::VAULT] = params.jl_vault;
+    accounts[jupiter_lend::LIQUIDITY] = params.jl_liquidity;
+    accounts[jupiter_lend::REWARDS_RATE_MODEL] = params.jl_rewards_rate_model;
+
+    let strategy_params = SetStrategyConfigParams {
+        kind: StrategyKind::JupiterLend,
+        program_id: params.jl_lending_program,
+        share_mint: params.jl_f_token_mint,
+        deposit_discriminator: jupiter_lend::DEPOSIT_DISCRIMINATOR,
+        withdraw_discriminator: jupiter_lend::WITHDRAW_DISCRIMINATOR,
+        accounts,
+    };
+    write_config(strategy, &strategy_params, bump)
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct SetJlConfigParams {
     pub usdc_mint: Pubkey,
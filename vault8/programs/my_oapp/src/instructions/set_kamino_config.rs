@@ -0,0 +1,71 @@
+use crate::errors::MyOAppError;
+use crate::instructions::set_strategy_config::write_config;
+use crate::strategy::StrategyKind;
+use crate::*;
+
+/// Writes the Kamino K-Lend `StrategyConfig` PDA for the Store's USDC reserve, using the K-Lend
+/// IDL discriminators. Unused oracles should be set to the K-Lend program ID. Once the timelock is
+/// on, the same params are queued as `ConfigUpdate::KaminoConfig`.
+#[derive(Accounts)]
+pub struct SetKaminoConfig<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = store.config_delay == 0 @ MyOAppError::ConfigTimelocked)]
+    pub store: Account<'info, Store>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = StrategyConfig::SIZE,
+        seeds = [STRATEGY_SEED, &StrategyKind::Kamino.seed()],
+        bump
+    )]
+    pub strategy: Account<'info, StrategyConfig>,
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetKaminoConfig<'_> {
+    pub fn apply(ctx: &mut Context<SetKaminoConfig>, params: &SetKaminoConfigParams) -> Result<()> {
+        write_config(&mut ctx.accounts.strategy, &params.strategy_params(), ctx.bumps.strategy)
+    }
+}
+
+impl SetKaminoConfigParams {
+    /// Generic strategy params for these venue accounts. Shared with `execute_config_change`.
+    pub(crate) fn strategy_params(&self) -> SetStrategyConfigParams {
+        // Venue accounts in the order of the `kamino` config indices
+        let mut accounts = vec![Pubkey::default(); kamino::CONFIG_ACCOUNTS_LEN];
+        accounts[kamino::LENDING_MARKET] = self.lending_market;
+        accounts[kamino::LENDING_MARKET_AUTHORITY] = self.lending_market_authority;
+        accounts[kamino::RESERVE] = self.reserve;
+        accounts[kamino::RESERVE_LIQUIDITY_SUPPLY] = self.reserve_liquidity_supply;
+        accounts[kamino::PYTH_ORACLE] = self.pyth_oracle;
+        accounts[kamino::SWITCHBOARD_PRICE_ORACLE] = self.switchboard_price_oracle;
+        accounts[kamino::SWITCHBOARD_TWAP_ORACLE] = self.switchboard_twap_oracle;
+        accounts[kamino::SCOPE_PRICES] = self.scope_prices;
+
+        SetStrategyConfigParams {
+            kind: StrategyKind::Kamino,
+            program_id: self.klend_program,
+            share_mint: self.reserve_collateral_mint,
+            deposit_discriminator: kamino::DEPOSIT_DISCRIMINATOR,
+            withdraw_discriminator: kamino::WITHDRAW_DISCRIMINATOR,
+            accounts,
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetKaminoConfigParams {
+    pub klend_program: Pubkey,
+    pub lending_market: Pubkey,
+    pub lending_market_authority: Pubkey,
+    pub reserve: Pubkey,
+    pub reserve_liquidity_supply: Pubkey,
+    pub reserve_collateral_mint: Pubkey,
+    pub pyth_oracle: Pubkey,
+    pub switchboard_price_oracle: Pubkey,
+    pub switchboard_twap_oracle: Pubkey,
+    pub scope_prices: Pubkey,
+}
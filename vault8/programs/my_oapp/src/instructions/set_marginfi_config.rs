@@ -0,0 +1,64 @@
+use crate::errors::MyOAppError;
+use crate::instructions::set_strategy_config::write_config;
+use crate::strategy::StrategyKind;
+use crate::*;
+
+/// Writes the MarginFi `StrategyConfig` PDA for the Store's USDC bank, using the MarginFi IDL
+/// discriminators. The Store's `MarginfiAccount` is then created with `init_marginfi_account`.
+/// Once the timelock is on, the same params are queued as `ConfigUpdate::MarginfiConfig`.
+#[derive(Accounts)]
+pub struct SetMarginfiConfig<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = store.config_delay == 0 @ MyOAppError::ConfigTimelocked)]
+    pub store: Account<'info, Store>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = StrategyConfig::SIZE,
+        seeds = [STRATEGY_SEED, &StrategyKind::MarginFi.seed()],
+        bump
+    )]
+    pub strategy: Account<'info, StrategyConfig>,
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetMarginfiConfig<'_> {
+    pub fn apply(ctx: &mut Context<SetMarginfiConfig>, params: &SetMarginfiConfigParams) -> Result<()> {
+        write_config(&mut ctx.accounts.strategy, &params.strategy_params(), ctx.bumps.strategy)
+    }
+}
+
+impl SetMarginfiConfigParams {
+    /// Generic strategy params for these venue accounts. Shared with `execute_config_change`.
+    pub(crate) fn strategy_params(&self) -> SetStrategyConfigParams {
+        // Venue accounts in the order of the `marginfi` config indices
+        let mut accounts = vec![Pubkey::default(); marginfi::CONFIG_ACCOUNTS_LEN];
+        accounts[marginfi::GROUP] = self.group;
+        accounts[marginfi::BANK] = self.bank;
+        accounts[marginfi::LIQUIDITY_VAULT] = self.liquidity_vault;
+        accounts[marginfi::LIQUIDITY_VAULT_AUTHORITY] = self.liquidity_vault_authority;
+        accounts[marginfi::ORACLE] = self.oracle;
+
+        SetStrategyConfigParams {
+            kind: StrategyKind::MarginFi,
+            program_id: self.marginfi_program,
+            share_mint: Pubkey::default(),
+            deposit_discriminator: marginfi::DEPOSIT_DISCRIMINATOR,
+            withdraw_discriminator: marginfi::WITHDRAW_DISCRIMINATOR,
+            accounts,
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetMarginfiConfigParams {
+    pub marginfi_program: Pubkey,
+    pub group: Pubkey,
+    pub bank: Pubkey,
+    pub liquidity_vault: Pubkey,
+    pub liquidity_vault_authority: Pubkey,
+    pub oracle: Pubkey,
+}
@@ -0,0 +1,29 @@
+use crate::errors::MyOAppError;
+use crate::*;
+
+/// Sets how often `report_nav` may send the vault's NAV to each peer.
+#[derive(Accounts)]
+pub struct SetNavReportInterval<'info> {
+    #[account(address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl SetNavReportInterval<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetNavReportInterval>,
+        params: &SetNavReportIntervalParams,
+    ) -> Result<()> {
+        require!(params.nav_report_interval >= 0, MyOAppError::InvalidNavReportInterval);
+        ctx.accounts.store.nav_report_interval = params.nav_report_interval;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetNavReportIntervalParams {
+    pub nav_report_interval: i64, // Minimum seconds between reports to a peer (0 = no limit)
+}
@@ -0,0 +1,24 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: SetOrderedNonceParams)]
+pub struct SetOrderedNonce<'info> {
+    #[account(address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl SetOrderedNonce<'_> {
+    pub fn apply(ctx: &mut Context<SetOrderedNonce>, params: &SetOrderedNonceParams) -> Result<()> {
+        ctx.accounts.store.ordered_nonce = params.ordered_nonce;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetOrderedNonceParams {
+    pub ordered_nonce: bool,
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Global circuit breaker. While paused, `lz_receive` and `lz_compose` revert, so messages stay
+/// on the Endpoint and can be re-executed after unpausing; sends, deposit settlement and idle
+/// sweeps are rejected. `rebalance` stays available to move funds out of a faulty venue.
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(address = roles.pauser)]
+    pub pauser: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl SetPaused<'_> {
+    pub fn apply(ctx: &mut Context<SetPaused>, params: &SetPausedParams) -> Result<()> {
+        ctx.accounts.store.paused = params.paused;
+        emit!(PausedEvent { paused: params.paused, timestamp: Clock::get()?.unix_timestamp });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetPausedParams {
+    pub paused: bool,
+}
@@ -1,26 +1,40 @@
 use crate::*;
+use crate::errors::MyOAppError;
+use crate::instructions::migrate_store::resize_account;
 use anchor_lang::prelude::*;
 
 // PeerConfig PDAs are used to store configuration for each remote chain
 // For each remote chain, a PeerConfig PDA is created with the remote EID as part of the seed
 // The PDA holds the peer address and any enforced options for messaging
+// Applied directly only while no config timelock is set; otherwise through `queue_config_change`
 
 #[derive(Accounts)]
 #[instruction(params: SetPeerConfigParams)]
 pub struct SetPeerConfig<'info> {
-    #[account(mut, address = store.admin)]
-    /// Admin of the OApp store
-    pub admin: Signer<'info>,
+    #[account(mut, address = roles.config_admin)]
+    /// Holder of the config admin role
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
     #[account(
         init_if_needed,
-        payer = admin,
-        space = PeerConfig::SIZE,
+        payer = config_admin,
+        space = PeerConfig::MIN_SIZE,
         seeds = [PEER_SEED, &store.key().to_bytes(), &params.remote_eid.to_be_bytes()],
         bump
     )]
     /// Peer configuration PDA for a specific remote chain
     pub peer: Account<'info, PeerConfig>,
-    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = Nonce::SIZE,
+        seeds = [NONCE_SEED, &store.key().to_bytes(), &params.remote_eid.to_be_bytes(), &params.config.peer_address()?],
+        bump
+    )]
+    /// Inbound nonce PDA for the (remote_eid, peer) path; required when setting the peer address
+    pub nonce_account: Option<Account<'info, Nonce>>,
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = store.config_delay == 0 @ MyOAppError::ConfigTimelocked)]
     /// Store PDA of this OApp
     pub store: Account<'info, Store>,
     pub system_program: Program<'info, System>,
@@ -28,24 +42,56 @@ pub struct SetPeerConfig<'info> {
 
 impl SetPeerConfig<'_> {
     pub fn apply(ctx: &mut Context<SetPeerConfig>, params: &SetPeerConfigParams) -> Result<()> {
-        // Update or create the peer config PDA
-        match params.config.clone() {
-            PeerConfigParam::PeerAddress(peer_address) => {
-                ctx.accounts.peer.peer_address = peer_address;
-            },
-            PeerConfigParam::EnforcedOptions { send, send_and_call } => {
-                oapp::options::assert_type_3(&send)?;
-                ctx.accounts.peer.enforced_options.send = send;
-                oapp::options::assert_type_3(&send_and_call)?;
-                ctx.accounts.peer.enforced_options.send_and_call = send_and_call;
-            },
-        }
-        // Store the PDA bump for later validation
-        ctx.accounts.peer.bump = ctx.bumps.peer;
-        Ok(())
+        write_peer_config(
+            &mut ctx.accounts.peer,
+            ctx.bumps.peer,
+            ctx.accounts.nonce_account.as_deref_mut(),
+            ctx.bumps.nonce_account,
+            &params.config,
+        )?;
+        // Grow (or shrink) the peer account to fit its enforced options
+        let space = ctx.accounts.peer.space()?;
+        resize_account(
+            &ctx.accounts.peer.to_account_info(),
+            &ctx.accounts.config_admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            space,
+        )
     }
 }
 
+/// Writes `config` into the peer PDA. Shared with `execute_config_change`.
+pub(crate) fn write_peer_config(
+    peer: &mut PeerConfig,
+    peer_bump: u8,
+    nonce_account: Option<&mut Nonce>,
+    nonce_bump: u8,
+    config: &PeerConfigParam,
+) -> Result<()> {
+    // Update or create the peer config PDA
+    match config.clone() {
+        PeerConfigParam::PeerAddress(peer_address) => {
+            peer.peer_address = peer_address;
+            // Nonce tracking for ordered execution starts with the peer
+            let nonce_account = nonce_account.ok_or(MyOAppError::InvalidAccount)?;
+            nonce_account.bump = nonce_bump;
+            nonce_account.version = Nonce::VERSION;
+        },
+        PeerConfigParam::InboundRateLimit { limit, window } => {
+            let now = Clock::get()?.unix_timestamp;
+            peer.inbound_rate_limit.set(limit, window, now)?;
+        },
+        PeerConfigParam::EnforcedOptions { msg_type, options } => {
+            msg_codec::MsgType::from_u8(msg_type)?;
+            peer.enforced_options.set(msg_type, options)?;
+        },
+    }
+    // Store the PDA bump for later validation
+    peer.bump = peer_bump;
+    peer.version = PeerConfig::VERSION;
+    Ok(())
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct SetPeerConfigParams {
     pub remote_eid: u32,
@@ -55,6 +101,18 @@ pub struct SetPeerConfigParams {
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub enum PeerConfigParam {
     PeerAddress([u8; 32]),
-    /// Optionally enforce specific send options for this peer
-    EnforcedOptions { send: Vec<u8>, send_and_call: Vec<u8> },
-}
\ No newline at end of file
+    /// Enforce options on sends of `msg_type` to this peer (empty options clear them)
+    EnforcedOptions { msg_type: u8, options: Vec<u8> },
+    /// Max deposit amount credited from this peer per rolling window of `window` seconds (0 disables)
+    InboundRateLimit { limit: u64, window: u64 },
+}
+
+impl PeerConfigParam {
+    /// Peer address being set by this update
+    pub(crate) fn peer_address(&self) -> Result<[u8; 32]> {
+        match self {
+            PeerConfigParam::PeerAddress(peer_address) => Ok(*peer_address),
+            _ => err!(MyOAppError::InvalidAccount),
+        }
+    }
+}
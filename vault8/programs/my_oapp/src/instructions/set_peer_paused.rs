@@ -0,0 +1,42 @@
+use crate::*;
+
+/// Pauses messaging with a single peer. Inbound pause makes `lz_receive` revert for messages
+/// from that chain, which stay on the Endpoint until unpaused; outbound pause rejects sends to it.
+#[derive(Accounts)]
+#[instruction(params: SetPeerPausedParams)]
+pub struct SetPeerPaused<'info> {
+    #[account(address = roles.pauser)]
+    pub pauser: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, &store.key().to_bytes(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl SetPeerPaused<'_> {
+    pub fn apply(ctx: &mut Context<SetPeerPaused>, params: &SetPeerPausedParams) -> Result<()> {
+        let peer = &mut ctx.accounts.peer;
+        peer.inbound_paused = params.inbound_paused;
+        peer.outbound_paused = params.outbound_paused;
+        emit!(PeerPausedEvent {
+            remote_eid: params.remote_eid,
+            inbound_paused: params.inbound_paused,
+            outbound_paused: params.outbound_paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetPeerPausedParams {
+    pub remote_eid: u32,
+    pub inbound_paused: bool,
+    pub outbound_paused: bool,
+}
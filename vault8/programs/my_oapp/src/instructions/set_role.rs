@@ -0,0 +1,38 @@
+use crate::*;
+
+/// Assigns one operational role. The Roles PDA is created on first use for stores initialized
+/// before it existed, with every role starting at the admin.
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(mut, address = store.admin)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Roles::SIZE,
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Account<'info, Roles>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetRole<'_> {
+    pub fn apply(ctx: &mut Context<SetRole>, params: &SetRoleParams) -> Result<()> {
+        let roles = &mut ctx.accounts.roles;
+        if roles.version == 0 {
+            roles.init(ctx.accounts.store.admin, ctx.bumps.roles);
+        }
+        roles.set(params.role, params.account);
+        emit!(RoleSetEvent { role: params.role, account: params.account });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetRoleParams {
+    pub role: Role,
+    pub account: Pubkey,
+}
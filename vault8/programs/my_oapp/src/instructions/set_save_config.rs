@@ -0,0 +1,71 @@
+use crate::errors::MyOAppError;
+use crate::instructions::set_strategy_config::write_config;
+use crate::strategy::StrategyKind;
+use crate::*;
+
+/// Writes the Save `StrategyConfig` PDA for the Store's USDC reserve. Save instructions are tagged
+/// by a single byte, stored as the first byte of the discriminators. Once the timelock is on, the
+/// same params are queued as `ConfigUpdate::SaveConfig`.
+#[derive(Accounts)]
+pub struct SetSaveConfig<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = store.config_delay == 0 @ MyOAppError::ConfigTimelocked)]
+    pub store: Account<'info, Store>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = StrategyConfig::SIZE,
+        seeds = [STRATEGY_SEED, &StrategyKind::Save.seed()],
+        bump
+    )]
+    pub strategy: Account<'info, StrategyConfig>,
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetSaveConfig<'_> {
+    pub fn apply(ctx: &mut Context<SetSaveConfig>, params: &SetSaveConfigParams) -> Result<()> {
+        write_config(&mut ctx.accounts.strategy, &params.strategy_params(), ctx.bumps.strategy)
+    }
+}
+
+impl SetSaveConfigParams {
+    /// Generic strategy params for these venue accounts. Shared with `execute_config_change`.
+    pub(crate) fn strategy_params(&self) -> SetStrategyConfigParams {
+        // Venue accounts in the order of the `save` config indices
+        let mut accounts = vec![Pubkey::default(); save::CONFIG_ACCOUNTS_LEN];
+        accounts[save::LENDING_MARKET] = self.lending_market;
+        accounts[save::LENDING_MARKET_AUTHORITY] = self.lending_market_authority;
+        accounts[save::RESERVE] = self.reserve;
+        accounts[save::RESERVE_LIQUIDITY_SUPPLY] = self.reserve_liquidity_supply;
+        accounts[save::PYTH_ORACLE] = self.pyth_oracle;
+        accounts[save::SWITCHBOARD_ORACLE] = self.switchboard_oracle;
+
+        let mut deposit_discriminator = [0u8; 8];
+        deposit_discriminator[0] = save::DEPOSIT_RESERVE_LIQUIDITY_TAG;
+        let mut withdraw_discriminator = [0u8; 8];
+        withdraw_discriminator[0] = save::REDEEM_RESERVE_COLLATERAL_TAG;
+        SetStrategyConfigParams {
+            kind: StrategyKind::Save,
+            program_id: self.save_program,
+            share_mint: self.reserve_collateral_mint,
+            deposit_discriminator,
+            withdraw_discriminator,
+            accounts,
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetSaveConfigParams {
+    pub save_program: Pubkey,
+    pub lending_market: Pubkey,
+    pub lending_market_authority: Pubkey,
+    pub reserve: Pubkey,
+    pub reserve_liquidity_supply: Pubkey,
+    pub reserve_collateral_mint: Pubkey,
+    pub pyth_oracle: Pubkey,
+    pub switchboard_oracle: Pubkey,
+}
@@ -0,0 +1,66 @@
+use crate::errors::MyOAppError;
+use crate::strategy::StrategyKind;
+use crate::*;
+
+/// Creates or updates the config PDA of a yield strategy. The account list is checked against
+/// what the strategy's adapter expects. Jupiter Lend is rejected: its config is only written by
+/// `set_jl_config`, which validates it against the live JL accounts.
+#[derive(Accounts)]
+#[instruction(params: SetStrategyConfigParams)]
+pub struct SetStrategyConfig<'info> {
+    #[account(seeds = [STORE_SEED], bump = store.bump, constraint = store.config_delay == 0 @ MyOAppError::ConfigTimelocked)]
+    pub store: Account<'info, Store>,
+    #[account(
+        init_if_needed,
+        payer = config_admin,
+        space = StrategyConfig::SIZE,
+        seeds = [STRATEGY_SEED, &params.kind.seed()],
+        bump
+    )]
+    pub strategy: Account<'info, StrategyConfig>,
+    #[account(mut, address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetStrategyConfig<'_> {
+    pub fn apply(ctx: &mut Context<SetStrategyConfig>, params: &SetStrategyConfigParams) -> Result<()> {
+        check_generic_kind(params)?;
+        write_config(&mut ctx.accounts.strategy, params, ctx.bumps.strategy)
+    }
+}
+
+/// Rejects kinds whose config has a dedicated, validating setter. Shared with the timelocked path.
+pub(crate) fn check_generic_kind(params: &SetStrategyConfigParams) -> Result<()> {
+    require!(params.kind != StrategyKind::JupiterLend, MyOAppError::JlConfigRequired);
+    Ok(())
+}
+
+/// Writes `params` into `config` and validates the result. Shared with `set_jl_config`.
+pub(crate) fn write_config(
+    config: &mut StrategyConfig,
+    params: &SetStrategyConfigParams,
+    bump: u8,
+) -> Result<()> {
+    config.kind = params.kind;
+    config.program_id = params.program_id;
+    config.share_mint = params.share_mint;
+    config.deposit_discriminator = params.deposit_discriminator;
+    config.withdraw_discriminator = params.withdraw_discriminator;
+    config.accounts = params.accounts.clone();
+    config.bump = bump;
+    config.version = StrategyConfig::VERSION;
+    config.validate()
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetStrategyConfigParams {
+    pub kind: StrategyKind,
+    pub program_id: Pubkey,
+    pub share_mint: Pubkey,
+    pub deposit_discriminator: [u8; 8],
+    pub withdraw_discriminator: [u8; 8],
+    pub accounts: Vec<Pubkey>,
+}
@@ -0,0 +1,41 @@
+use crate::errors::MyOAppError;
+use crate::strategy::{self, StrategyKind, BPS_DENOMINATOR, STRATEGY_COUNT};
+use crate::*;
+
+/// Sets the allocation weights each deposit is split by, in bps per `StrategyKind`. Weights must
+/// sum to `BPS_DENOMINATOR`, and remaining accounts are the config PDAs of every strategy given a
+/// weight, in `StrategyKind` order, so only configured venues can receive deposits.
+///
+/// Existing positions are not moved: a strategy dropped to zero stays allocated until its venue
+/// shares have been withdrawn.
+#[derive(Accounts)]
+pub struct SetStrategyWeights<'info> {
+    #[account(address = roles.config_admin)]
+    pub config_admin: Signer<'info>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump)]
+    pub store: Account<'info, Store>,
+}
+
+impl SetStrategyWeights<'_> {
+    pub fn apply(ctx: &mut Context<SetStrategyWeights>, params: &SetStrategyWeightsParams) -> Result<()> {
+        let total: u64 = params.weights.iter().map(|w| *w as u64).sum();
+        require!(total == BPS_DENOMINATOR, MyOAppError::InvalidStrategyWeights);
+
+        let mut configs = ctx.remaining_accounts.iter();
+        for kind in StrategyKind::ALL {
+            let weight_bps = params.weights[kind.index()];
+            if weight_bps > 0 {
+                strategy::load_config(kind, configs.next().ok_or(MyOAppError::InvalidAccount)?)?;
+            }
+            ctx.accounts.store.allocation_mut(kind).weight_bps = weight_bps;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetStrategyWeightsParams {
+    pub weights: [u16; STRATEGY_COUNT],
+}
@@ -0,0 +1,184 @@
+use crate::*;
+use crate::errors::MyOAppError;
+use crate::instructions::lz_receive::send_compose;
+
+/// Permissionless crank that settles a deposit parked by `lz_receive`.
+///
+/// Once the CCTP mint for the deposit has landed in the Store USDC ATA and the deposit fits the
+/// user and TVL caps and the peer's inbound rate limit, anyone can call this to split the funds across
+/// the allocated strategies and credit the vault shares to the user's `UserBalance` and the
+/// `VaultPosition` of the vault named in the deposit. The caller pays for the position if the deposit was parked
+/// before positions existed. Remaining accounts are the strategy deposit legs, in the same order
+/// `lz_receive_types` resolves them, followed by the `Endpoint::send_compose` accounts if the
+/// deposit came with a compose action, which is only queued for `lz_compose` now.
+#[derive(Accounts)]
+pub struct SettlePendingDeposit<'info> {
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump, constraint = !store.paused @ MyOAppError::Paused)]
+    pub store: Account<'info, Store>,
+    #[account(
+        mut,
+        seeds = [PENDING_DEPOSIT_SEED, &pending_deposit.guid],
+        bump = pending_deposit.bump,
+        has_one = rent_payer,
+        close = rent_payer
+    )]
+    pub pending_deposit: Account<'info, PendingDeposit>,
+    /// UserBalance PDA created by `lz_receive` when the deposit was parked
+    #[account(
+        mut,
+        seeds = [
+            USER_BALANCE_SEED,
+            &pending_deposit.src_eid.to_be_bytes(),
+            &pending_deposit.evm_address
+        ],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+    /// VaultPosition PDA of the depositing vault on the source chain
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultPosition::SIZE,
+        seeds = [
+            VAULT_POSITION_SEED,
+            &pending_deposit.src_eid.to_be_bytes(),
+            &pending_deposit.vault
+        ],
+        bump
+    )]
+    pub vault_position: Account<'info, VaultPosition>,
+    /// Receipt written by `lz_receive`; marked credited so the deposit can be ACKed
+    #[account(
+        mut,
+        seeds = [DEPOSIT_RECEIPT_SEED, &pending_deposit.guid],
+        bump = deposit_receipt.bump
+    )]
+    pub deposit_receipt: Account<'info, DepositReceipt>,
+    /// Peer the deposit came from; the deposit counts against its inbound rate limit
+    #[account(
+        mut,
+        seeds = [PEER_SEED, &store.key().to_bytes(), &pending_deposit.src_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    /// CHECK: Circle MessageTransmitter `used_nonce` PDA; must exist before the deposit settles
+    #[account(
+        address = cctp::used_nonce_address(&pending_deposit.cctp_nonce),
+        constraint = cctp::is_nonce_used(&cctp_used_nonce) @ MyOAppError::CctpNonceNotUsed
+    )]
+    pub cctp_used_nonce: UncheckedAccount<'info>,
+    /// CHECK: Receives the pending deposit rent back (whoever paid for it in `lz_receive`)
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SettlePendingDeposit<'_> {
+    pub fn apply(ctx: &mut Context<SettlePendingDeposit>) -> Result<()> {
+        let store_key = ctx.accounts.store.key();
+        let compose = ctx.accounts.pending_deposit.compose_msg()?;
+        settle(
+            &mut ctx.accounts.store,
+            store_key,
+            &ctx.accounts.pending_deposit,
+            Depositor {
+                user_balance: &mut ctx.accounts.user_balance,
+                user_balance_bump: ctx.bumps.user_balance,
+                vault_position: &mut ctx.accounts.vault_position,
+                vault_position_bump: ctx.bumps.vault_position,
+            },
+            &mut ctx.accounts.deposit_receipt,
+            &mut ctx.accounts.peer.inbound_rate_limit,
+            ctx.remaining_accounts,
+        )?;
+
+        if let Some(compose) = compose {
+            let deposit_len = strategy::deposit_accounts_len(&ctx.accounts.store);
+            send_compose(
+                &ctx.accounts.store,
+                ctx.accounts.pending_deposit.guid,
+                compose,
+                &ctx.remaining_accounts[deposit_len..],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// UserBalance and VaultPosition a parked deposit is credited to, with their PDA bumps.
+pub(crate) struct Depositor<'a> {
+    pub user_balance: &'a mut UserBalance,
+    pub user_balance_bump: u8,
+    pub vault_position: &'a mut VaultPosition,
+    pub vault_position_bump: u8,
+}
+
+/// Credits a parked deposit and splits its USDC across the allocated strategies, counting it
+/// against the source peer's `inbound_rate_limit`. Shared by this crank and the compose `Allocate`
+/// action; the caller checks the CCTP nonce and closes `pending`. `remaining_accounts` must start
+/// with the strategy deposit legs.
+pub(crate) fn settle(
+    store: &mut Store,
+    store_key: Pubkey,
+    pending: &PendingDeposit,
+    depositor: Depositor,
+    deposit_receipt: &mut DepositReceipt,
+    inbound_rate_limit: &mut RateLimit,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    let Depositor { user_balance, user_balance_bump, vault_position, vault_position_bump } =
+        depositor;
+    let deposit_len = strategy::deposit_accounts_len(store);
+    require!(remaining_accounts.len() >= deposit_len, MyOAppError::InvalidAccount);
+    let strategy_accounts = &remaining_accounts[..deposit_len];
+
+    let store_usdc_ata = strategy::find_account(
+        strategy_accounts,
+        &store.associated_token_address(&store_key, &store.usdc_mint),
+    )?;
+    // USDC held for the deposit since it was parked over a cap is released to it
+    if pending.funded {
+        store.parked_assets =
+            store.parked_assets.checked_sub(pending.amount).ok_or(MyOAppError::Overflow)?;
+    }
+    require!(
+        store.unallocated_usdc(strategy::token_amount(store, store_usdc_ata)?) >= pending.amount,
+        MyOAppError::InsufficientStoreBalance
+    );
+
+    vault_position.open_if_new(
+        pending.src_eid,
+        pending.vault,
+        vault_position_bump,
+        user_balance,
+    )?;
+    store.check_user_deposit_cap(user_balance.total_deposited, pending.amount)?;
+    let clock = Clock::get()?;
+    inbound_rate_limit.consume(pending.amount, clock.unix_timestamp)?;
+    let (shares, _) = strategy::deposit(store, &store_key, strategy_accounts, pending.amount)?;
+    deposit_receipt.credited = true;
+
+    user_balance.record_deposit(
+        pending.src_eid,
+        pending.evm_address,
+        user_balance_bump,
+        pending.amount,
+        shares,
+        clock.unix_timestamp,
+    )?;
+    vault_position.record_deposit(pending.amount, shares, clock.unix_timestamp)?;
+
+    emit!(DepositEvent {
+        guid: pending.guid,
+        evm_address: pending.evm_address,
+        amount: pending.amount,
+        shares,
+        new_total: user_balance.total_deposited,
+        deposit_index: user_balance.deposit_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::errors::MyOAppError;
+use crate::*;
+
+/// Permissionless crank moving the idle buffer toward `Store.idle_target_bps` of total assets.
+///
+/// With `top_up`, remaining accounts are the strategy withdraw legs and the shortfall is redeemed
+/// pro rata into the Store USDC ATA. Otherwise they are the deposit legs and the excess is invested
+/// by weight. A sweep in a direction the buffer does not need is a no-op.
+#[derive(Accounts)]
+pub struct SweepIdle<'info> {
+    #[account(mut, seeds = [STORE_SEED], bump = store.bump, constraint = !store.paused @ MyOAppError::Paused)]
+    pub store: Account<'info, Store>,
+}
+
+impl SweepIdle<'_> {
+    pub fn apply(ctx: &mut Context<SweepIdle>, params: &SweepIdleParams) -> Result<()> {
+        let store = &mut ctx.accounts.store;
+        let store_key = store.key();
+        let (legs, _) = if params.top_up {
+            strategy::withdraw_legs(store, ctx.remaining_accounts)?
+        } else {
+            strategy::deposit_legs(store, ctx.remaining_accounts)?
+        };
+        let values = strategy::leg_values(store, &legs)?;
+        let invested = strategy::sum(&values)?;
+        let target = store.idle_target(store.idle_assets.saturating_add(invested));
+
+        let (deposited, withdrawn) = if params.top_up && store.idle_assets < target {
+            let amount = target - store.idle_assets;
+            let received = strategy::divest(store, &store_key, &legs, &values, amount)?;
+            store.idle_assets = store.idle_assets.checked_add(received).ok_or(MyOAppError::Overflow)?;
+            (0, received)
+        } else if !params.top_up && store.idle_assets > target {
+            let amount = store.idle_assets - target;
+            let spent = strategy::invest(store, &store_key, &legs, amount)?;
+            store.idle_assets = store.idle_assets.checked_sub(spent).ok_or(MyOAppError::Overflow)?;
+            (spent, 0)
+        } else {
+            return Ok(());
+        };
+
+        emit!(IdleSweptEvent {
+            deposited,
+            withdrawn,
+            idle_assets: store.idle_assets,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SweepIdleParams {
+    pub top_up: bool, // Redeem from the strategies into the buffer, rather than invest its excess
+}
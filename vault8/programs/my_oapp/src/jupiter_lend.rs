@@ -0,0 +1,257 @@
+use crate::errors::MyOAppError;
+use crate::strategy::{self, Strategy};
+use crate::*;
+use anchor_lang::solana_program::system_program;
+use oapp::endpoint_cpi::LzAccount;
+
+/// Number of accounts the Jupiter Lend `deposit` CPI expects (see `deposit_accounts` for the order)
+pub const DEPOSIT_ACCOUNTS_LEN: usize = 18;
+/// Jupiter Lend deposit discriminator from IDL
+pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+/// Number of accounts the Jupiter Lend `withdraw` CPI expects (see `withdraw_accounts` for the order)
+pub const WITHDRAW_ACCOUNTS_LEN: usize = 19;
+/// Jupiter Lend withdraw discriminator from IDL
+pub const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+/// Liquidity program seed for the claim account used when withdrawing
+pub const USER_CLAIM_SEED: &[u8] = b"user_claim";
+/// Byte offset of `token_exchange_price` in the JL `Lending` account:
+/// discriminator(8) + mint(32) + f_token_mint(32) + lending_id(2) + decimals(1)
+/// + rewards_rate_model(32) + liquidity_exchange_price(8)
+pub const LENDING_TOKEN_EXCHANGE_PRICE_OFFSET: usize = 115;
+/// Byte offsets of `mint` (underlying) and `f_token_mint` in the JL `Lending` account
+pub const LENDING_MINT_OFFSET: usize = 8;
+pub const LENDING_F_TOKEN_MINT_OFFSET: usize = 40;
+/// Precision of JL exchange prices (1e12 = 1 underlying per fToken)
+pub const EXCHANGE_PRICES_PRECISION: u128 = 1_000_000_000_000;
+
+// Order of the Jupiter Lend Earn accounts in `StrategyConfig.accounts` (USDC pool on the current
+// cluster). `program_id` is the lending program and `share_mint` the fToken mint.
+pub const LIQUIDITY_PROGRAM: usize = 0;
+pub const LENDING_ADMIN: usize = 1;
+pub const LENDING: usize = 2;
+pub const SUPPLY_TOKEN_RESERVES_LIQUIDITY: usize = 3;
+pub const LENDING_SUPPLY_POSITION_ON_LIQUIDITY: usize = 4;
+pub const RATE_MODEL: usize = 5;
+pub const VAULT: usize = 6;
+pub const LIQUIDITY: usize = 7;
+pub const REWARDS_RATE_MODEL: usize = 8;
+pub const CONFIG_ACCOUNTS_LEN: usize = 9;
+
+/// Jupiter Lend Earn adapter. Deposits mint fTokens to the Store fToken ATA; shares are fTokens.
+pub struct JupiterLend;
+
+impl Strategy for JupiterLend {
+    fn config_accounts_len(&self) -> usize {
+        CONFIG_ACCOUNTS_LEN
+    }
+
+    fn deposit_accounts_len(&self) -> usize {
+        DEPOSIT_ACCOUNTS_LEN
+    }
+
+    fn withdraw_accounts_len(&self) -> usize {
+        WITHDRAW_ACCOUNTS_LEN
+    }
+
+    /// 0: signer (store PDA, writable, signer via invoke_signed)
+    /// 1: depositorTokenAccount (USDC ATA)
+    /// 2: recipientTokenAccount (jlUSDC ATA)
+    /// 3: mint (USDC)
+    /// 4: lendingAdmin
+    /// 5: lending
+    /// 6: fTokenMint
+    /// 7: supplyTokenReservesLiquidity
+    /// 8: lendingSupplyPositionOnLiquidity
+    /// 9: rateModel
+    /// 10: vault
+    /// 11: liquidity
+    /// 12: liquidityProgram
+    /// 13: rewardsRateModel
+    /// 14: tokenProgram
+    /// 15: associatedTokenProgram
+    /// 16: systemProgram
+    /// 17: jlLendingProgram
+    fn deposit_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        let f_token_ata = s.associated_token_address(store_key, &c.share_mint);
+        vec![
+            // signer (store PDA) again as CPI account 0 in our JL slice.
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: true },
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: f_token_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: s.usdc_mint, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(LENDING_ADMIN), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(LENDING), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.share_mint, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(SUPPLY_TOKEN_RESERVES_LIQUIDITY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LENDING_SUPPLY_POSITION_ON_LIQUIDITY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(RATE_MODEL), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(VAULT), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LIQUIDITY), is_signer: false, is_writable: true },
+            // Jupiter docs specify liquidity_program as mutable
+            LzAccount { pubkey: c.account(LIQUIDITY_PROGRAM), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(REWARDS_RATE_MODEL), is_signer: false, is_writable: false },
+            // Well-known programs MUST be included so Solana runtime can find them during CPI
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.associated_token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: system_program::ID, is_signer: false, is_writable: false },
+            // Jupiter Lend program MUST be included for invoke_signed to work
+            LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false },
+        ]
+    }
+
+    /// 0: signer (store PDA, writable, signer via invoke_signed)
+    /// 1: ownerTokenAccount (jlUSDC ATA)
+    /// 2: recipientTokenAccount (USDC ATA)
+    /// 3: lendingAdmin
+    /// 4: lending
+    /// 5: mint (USDC)
+    /// 6: fTokenMint
+    /// 7: supplyTokenReservesLiquidity
+    /// 8: lendingSupplyPositionOnLiquidity
+    /// 9: rateModel
+    /// 10: vault
+    /// 11: claimAccount
+    /// 12: liquidity
+    /// 13: liquidityProgram
+    /// 14: rewardsRateModel
+    /// 15: tokenProgram
+    /// 16: associatedTokenProgram
+    /// 17: systemProgram
+    /// 18: jlLendingProgram
+    fn withdraw_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        let f_token_ata = s.associated_token_address(store_key, &c.share_mint);
+        vec![
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: true },
+            LzAccount { pubkey: f_token_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LENDING_ADMIN), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(LENDING), is_signer: false, is_writable: true },
+            LzAccount { pubkey: s.usdc_mint, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.share_mint, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(SUPPLY_TOKEN_RESERVES_LIQUIDITY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LENDING_SUPPLY_POSITION_ON_LIQUIDITY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(RATE_MODEL), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(VAULT), is_signer: false, is_writable: true },
+            LzAccount { pubkey: claim_account_address(s, c), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LIQUIDITY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LIQUIDITY_PROGRAM), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(REWARDS_RATE_MODEL), is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.associated_token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: system_program::ID, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false },
+        ]
+    }
+
+    /// Deposits from the Store USDC ATA; returns the fTokens minted, measured on the Store
+    /// fToken ATA (account 2) around the CPI.
+    fn deposit(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.deposit_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+
+        let data = strategy::amount_ix_data(config.deposit_discriminator, amount);
+        let shares_before = strategy::token_amount(store, &accounts[2])?;
+        strategy::invoke(store, store_key, config, &expected, accounts, data)?;
+        let shares_after = strategy::token_amount(store, &accounts[2])?;
+        Ok(shares_after.checked_sub(shares_before).ok_or(MyOAppError::Overflow)?)
+    }
+
+    /// Withdraws into the Store USDC ATA, burning the Store's fTokens; returns the fTokens burned,
+    /// measured on the Store fToken ATA (account 1) around the CPI.
+    fn withdraw(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.withdraw_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+
+        let data = strategy::amount_ix_data(config.withdraw_discriminator, amount);
+        let shares_before = strategy::token_amount(store, &accounts[1])?;
+        strategy::invoke(store, store_key, config, &expected, accounts, data)?;
+        let shares_after = strategy::token_amount(store, &accounts[1])?;
+        Ok(shares_before.checked_sub(shares_after).ok_or(MyOAppError::Overflow)?)
+    }
+
+    fn price_account(&self, config: &StrategyConfig) -> Pubkey {
+        config.account(LENDING)
+    }
+
+    /// Prices fTokens with the `Lending` account's `token_exchange_price`, as of its last update.
+    fn shares_to_assets(&self, config: &StrategyConfig, price_account: &AccountInfo, shares: u64) -> Result<u64> {
+        let exchange_price = token_exchange_price(config, price_account)?;
+        shares_to_assets(shares, exchange_price)
+    }
+}
+
+/// Liquidity-program claim account of the lending protocol for the Store's USDC pool.
+pub fn claim_account_address(store: &Store, config: &StrategyConfig) -> Pubkey {
+    let seeds: &[&[u8]] =
+        &[USER_CLAIM_SEED, &config.account(LENDING_ADMIN).to_bytes(), &store.usdc_mint.to_bytes()];
+    Pubkey::find_program_address(seeds, &config.account(LIQUIDITY_PROGRAM)).0
+}
+
+/// Checks a Jupiter Lend config against the live accounts before it is written. `accounts` are
+/// the lending admin, lending, fToken mint, vault and USDC mint named in `params`, in that order:
+/// lending, lending admin and the vault must be owned by the lending program, the mints by the
+/// token program, and the lending account must point at the fToken mint and at USDC.
+pub fn validate_config(params: &SetJlConfigParams, accounts: &[AccountInfo]) -> Result<()> {
+    require!(accounts.len() >= 5, MyOAppError::InvalidAccount);
+    let [lending_admin, lending, f_token_mint, vault, usdc_mint] = &accounts[..5] else {
+        return err!(MyOAppError::InvalidAccount);
+    };
+    require_keys_eq!(lending_admin.key(), params.jl_lending_admin, MyOAppError::InvalidAccount);
+    require_keys_eq!(lending.key(), params.jl_lending, MyOAppError::InvalidAccount);
+    require_keys_eq!(f_token_mint.key(), params.jl_f_token_mint, MyOAppError::InvalidAccount);
+    require_keys_eq!(vault.key(), params.jl_vault, MyOAppError::InvalidAccount);
+    require_keys_eq!(usdc_mint.key(), params.usdc_mint, MyOAppError::InvalidAccount);
+
+    for account in [lending_admin, lending, vault] {
+        require_keys_eq!(*account.owner, params.jl_lending_program, MyOAppError::InvalidJlAccountOwner);
+    }
+    for account in [f_token_mint, usdc_mint] {
+        require_keys_eq!(*account.owner, params.token_program, MyOAppError::InvalidJlAccountOwner);
+    }
+
+    let data = lending.try_borrow_data()?;
+    require!(data.len() >= LENDING_F_TOKEN_MINT_OFFSET + 32, MyOAppError::InvalidAccount);
+    let underlying = &data[LENDING_MINT_OFFSET..LENDING_MINT_OFFSET + 32];
+    let f_token = &data[LENDING_F_TOKEN_MINT_OFFSET..LENDING_F_TOKEN_MINT_OFFSET + 32];
+    require!(f_token == params.jl_f_token_mint.as_ref(), MyOAppError::JlFTokenMintMismatch);
+    require!(underlying == params.usdc_mint.as_ref(), MyOAppError::JlUnderlyingMintMismatch);
+    Ok(())
+}
+
+/// Reads the fToken -> underlying exchange price from the JL `Lending` account, as of its last update.
+pub fn token_exchange_price(config: &StrategyConfig, lending: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(lending.key(), config.account(LENDING), MyOAppError::InvalidAccount);
+    require_keys_eq!(*lending.owner, config.program_id, MyOAppError::InvalidAccount);
+    let data = lending.try_borrow_data()?;
+    let offset = LENDING_TOKEN_EXCHANGE_PRICE_OFFSET;
+    require!(data.len() >= offset + 8, MyOAppError::InvalidAccount);
+    let mut price = [0u8; 8];
+    price.copy_from_slice(&data[offset..offset + 8]);
+    Ok(u64::from_le_bytes(price))
+}
+
+/// Converts fTokens to underlying USDC at `exchange_price` (rounded down).
+pub fn shares_to_assets(shares: u64, exchange_price: u64) -> Result<u64> {
+    let assets = (shares as u128) * (exchange_price as u128) / EXCHANGE_PRICES_PRECISION;
+    Ok(u64::try_from(assets).map_err(|_| MyOAppError::Overflow)?)
+}
@@ -0,0 +1,263 @@
+use crate::errors::MyOAppError;
+use crate::strategy::{self, Strategy};
+use crate::*;
+use anchor_lang::solana_program::sysvar;
+use oapp::endpoint_cpi::LzAccount;
+
+/// Number of accounts the Kamino deposit takes (see `deposit_accounts` for the order)
+pub const DEPOSIT_ACCOUNTS_LEN: usize = 17;
+/// Kamino `deposit_reserve_liquidity` discriminator from IDL
+pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [169, 201, 30, 126, 6, 205, 102, 68];
+/// Number of accounts the Kamino withdraw takes (see `withdraw_accounts` for the order)
+pub const WITHDRAW_ACCOUNTS_LEN: usize = 17;
+/// Kamino `redeem_reserve_collateral` discriminator from IDL
+pub const WITHDRAW_DISCRIMINATOR: [u8; 8] = [234, 117, 181, 125, 185, 142, 220, 29];
+/// Kamino `refresh_reserve` discriminator from IDL. Deposits and redemptions require a reserve
+/// refreshed in the same slot, so both are preceded by this CPI.
+pub const REFRESH_RESERVE_DISCRIMINATOR: [u8; 8] = [2, 218, 138, 235, 79, 201, 25, 102];
+/// Byte offsets in the K-Lend `Reserve` account: discriminator(8) + version(8) + last_update(16) +
+/// lending_market(32) + farm_collateral(32) + farm_debt(32), then `ReserveLiquidity`:
+/// mint(32) + supply_vault(32) + fee_vault(32) + available_amount(8) + borrowed_amount_sf(16) ...
+pub const RESERVE_AVAILABLE_AMOUNT_OFFSET: usize = 224;
+pub const RESERVE_BORROWED_AMOUNT_SF_OFFSET: usize = 232;
+/// ... cumulative_borrow_rate_bsf(48) + accumulated_protocol_fees_sf(16) +
+/// accumulated_referrer_fees_sf(16) + pending_referrer_fees_sf(16)
+pub const RESERVE_PROTOCOL_FEES_SF_OFFSET: usize = 344;
+pub const RESERVE_REFERRER_FEES_SF_OFFSET: usize = 360;
+pub const RESERVE_PENDING_REFERRER_FEES_SF_OFFSET: usize = 376;
+/// `ReserveCollateral.mint_total_supply`, after the 1200-byte liquidity padding and the collateral mint
+pub const RESERVE_COLLATERAL_SUPPLY_OFFSET: usize = 2592;
+/// K-Lend scaled fractions (`*_sf`) carry 60 fractional bits
+pub const FRACTION_BITS: u32 = 60;
+
+// Order of the K-Lend accounts in `StrategyConfig.accounts` (USDC reserve on the current cluster).
+// `program_id` is the K-Lend program and `share_mint` the reserve collateral (cToken) mint.
+// Oracles the reserve does not use are set to the K-Lend program ID, its "none" placeholder.
+pub const LENDING_MARKET: usize = 0;
+pub const LENDING_MARKET_AUTHORITY: usize = 1;
+pub const RESERVE: usize = 2;
+pub const RESERVE_LIQUIDITY_SUPPLY: usize = 3;
+pub const PYTH_ORACLE: usize = 4;
+pub const SWITCHBOARD_PRICE_ORACLE: usize = 5;
+pub const SWITCHBOARD_TWAP_ORACLE: usize = 6;
+pub const SCOPE_PRICES: usize = 7;
+pub const CONFIG_ACCOUNTS_LEN: usize = 8;
+
+/// Kamino K-Lend reserve adapter. Deposits mint reserve collateral (cTokens) to the Store cToken
+/// ATA; shares are cTokens. Withdrawals redeem the cTokens worth the requested USDC.
+pub struct Kamino;
+
+impl Strategy for Kamino {
+    fn config_accounts_len(&self) -> usize {
+        CONFIG_ACCOUNTS_LEN
+    }
+
+    fn deposit_accounts_len(&self) -> usize {
+        DEPOSIT_ACCOUNTS_LEN
+    }
+
+    fn withdraw_accounts_len(&self) -> usize {
+        WITHDRAW_ACCOUNTS_LEN
+    }
+
+    /// 0: owner (store PDA, signer via invoke_signed)
+    /// 1: reserve
+    /// 2: lendingMarket
+    /// 3: lendingMarketAuthority
+    /// 4: reserveLiquidityMint (USDC)
+    /// 5: reserveLiquiditySupply
+    /// 6: reserveCollateralMint
+    /// 7: userSourceLiquidity (USDC ATA)
+    /// 8: userDestinationCollateral (cToken ATA)
+    /// 9: collateralTokenProgram
+    /// 10: liquidityTokenProgram
+    /// 11: instructionSysvarAccount
+    /// 12..=15: refresh_reserve oracles (pyth, switchboard price, switchboard twap, scope)
+    /// 16: K-Lend program
+    fn deposit_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        let c_token_ata = s.associated_token_address(store_key, &c.share_mint);
+        let mut accounts = vec![
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(RESERVE), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LENDING_MARKET), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(LENDING_MARKET_AUTHORITY), is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.usdc_mint, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(RESERVE_LIQUIDITY_SUPPLY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.share_mint, is_signer: false, is_writable: true },
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c_token_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: sysvar::instructions::ID, is_signer: false, is_writable: false },
+        ];
+        accounts.extend(oracle_accounts(c));
+        accounts.push(LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false });
+        accounts
+    }
+
+    /// 0: owner (store PDA, signer via invoke_signed)
+    /// 1: lendingMarket
+    /// 2: reserve
+    /// 3: lendingMarketAuthority
+    /// 4: reserveLiquidityMint (USDC)
+    /// 5: reserveCollateralMint
+    /// 6: reserveLiquiditySupply
+    /// 7: userSourceCollateral (cToken ATA)
+    /// 8: userDestinationLiquidity (USDC ATA)
+    /// 9: collateralTokenProgram
+    /// 10: liquidityTokenProgram
+    /// 11: instructionSysvarAccount
+    /// 12..=15: refresh_reserve oracles (pyth, switchboard price, switchboard twap, scope)
+    /// 16: K-Lend program
+    fn withdraw_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        let c_token_ata = s.associated_token_address(store_key, &c.share_mint);
+        let mut accounts = vec![
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(LENDING_MARKET), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(RESERVE), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LENDING_MARKET_AUTHORITY), is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.usdc_mint, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.share_mint, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(RESERVE_LIQUIDITY_SUPPLY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c_token_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: sysvar::instructions::ID, is_signer: false, is_writable: false },
+        ];
+        accounts.extend(oracle_accounts(c));
+        accounts.push(LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false });
+        accounts
+    }
+
+    /// Refreshes the reserve, then deposits from the Store USDC ATA; returns the cTokens minted,
+    /// measured on the Store cToken ATA (account 8) around the CPI.
+    fn deposit(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.deposit_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+        refresh_reserve(store, store_key, config, accounts)?;
+
+        let data = strategy::amount_ix_data(config.deposit_discriminator, amount);
+        let shares_before = strategy::token_amount(store, &accounts[8])?;
+        strategy::invoke_with_metas(store, store_key, config.program_id, &expected[..12], accounts, data)?;
+        let shares_after = strategy::token_amount(store, &accounts[8])?;
+        Ok(shares_after.checked_sub(shares_before).ok_or(MyOAppError::Overflow)?)
+    }
+
+    /// Refreshes the reserve, then redeems the cTokens worth `amount` USDC (rounded up) into the
+    /// Store USDC ATA; returns the cTokens burned, measured on the Store cToken ATA (account 7).
+    fn withdraw(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.withdraw_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+        refresh_reserve(store, store_key, config, accounts)?;
+
+        let collateral = collateral_for_liquidity(&accounts[2], amount)?;
+        let data = strategy::amount_ix_data(config.withdraw_discriminator, collateral);
+        let shares_before = strategy::token_amount(store, &accounts[7])?;
+        strategy::invoke_with_metas(store, store_key, config.program_id, &expected[..12], accounts, data)?;
+        let shares_after = strategy::token_amount(store, &accounts[7])?;
+        Ok(shares_before.checked_sub(shares_after).ok_or(MyOAppError::Overflow)?)
+    }
+
+    fn price_account(&self, config: &StrategyConfig) -> Pubkey {
+        config.account(RESERVE)
+    }
+
+    /// Prices cTokens with the `Reserve` account's liquidity/collateral ratio, as of its last refresh.
+    fn shares_to_assets(&self, config: &StrategyConfig, price_account: &AccountInfo, shares: u64) -> Result<u64> {
+        require_keys_eq!(price_account.key(), config.account(RESERVE), MyOAppError::InvalidAccount);
+        require_keys_eq!(*price_account.owner, config.program_id, MyOAppError::InvalidAccount);
+        let (total_liquidity, collateral_supply) = reserve_exchange(price_account)?;
+        if collateral_supply == 0 || total_liquidity == 0 {
+            return Ok(shares);
+        }
+        let assets = (shares as u128) * total_liquidity / collateral_supply;
+        Ok(u64::try_from(assets).map_err(|_| MyOAppError::Overflow)?)
+    }
+}
+
+/// `refresh_reserve` oracle accounts, read-only, in IDL order.
+fn oracle_accounts(config: &StrategyConfig) -> Vec<LzAccount> {
+    [PYTH_ORACLE, SWITCHBOARD_PRICE_ORACLE, SWITCHBOARD_TWAP_ORACLE, SCOPE_PRICES]
+        .iter()
+        .map(|&i| LzAccount { pubkey: config.account(i), is_signer: false, is_writable: false })
+        .collect()
+}
+
+/// CPIs `refresh_reserve` (reserve, lending market, oracles); `accounts` must hold all of them.
+fn refresh_reserve(
+    store: &Store,
+    store_key: &Pubkey,
+    config: &StrategyConfig,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let mut metas = vec![
+        LzAccount { pubkey: config.account(RESERVE), is_signer: false, is_writable: true },
+        LzAccount { pubkey: config.account(LENDING_MARKET), is_signer: false, is_writable: false },
+    ];
+    metas.extend(oracle_accounts(config));
+    strategy::invoke_with_metas(
+        store,
+        store_key,
+        config.program_id,
+        &metas,
+        accounts,
+        REFRESH_RESERVE_DISCRIMINATOR.to_vec(),
+    )
+}
+
+/// cTokens to redeem for `amount` USDC, rounded up so the redemption covers `amount`.
+fn collateral_for_liquidity(reserve: &AccountInfo, amount: u64) -> Result<u64> {
+    let (total_liquidity, collateral_supply) = reserve_exchange(reserve)?;
+    if collateral_supply == 0 || total_liquidity == 0 {
+        return Ok(amount);
+    }
+    let collateral = ((amount as u128) * collateral_supply).div_ceil(total_liquidity);
+    Ok(u64::try_from(collateral).map_err(|_| MyOAppError::Overflow)?)
+}
+
+/// Reads (total liquidity in USDC base units, cToken supply) from a K-Lend `Reserve` account.
+/// Total liquidity is available + borrowed - fees, with the scaled fractions floored.
+fn reserve_exchange(reserve: &AccountInfo) -> Result<(u128, u128)> {
+    let data = reserve.try_borrow_data()?;
+    require!(data.len() >= RESERVE_COLLATERAL_SUPPLY_OFFSET + 8, MyOAppError::InvalidAccount);
+    let read_u64 = |offset: usize| {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&data[offset..offset + 8]);
+        u64::from_le_bytes(buf) as u128
+    };
+    let read_u128 = |offset: usize| {
+        let mut buf = [0u8; 16];
+        buf.copy_from_slice(&data[offset..offset + 16]);
+        u128::from_le_bytes(buf)
+    };
+
+    let available_sf = read_u64(RESERVE_AVAILABLE_AMOUNT_OFFSET) << FRACTION_BITS;
+    let total_sf = available_sf
+        .checked_add(read_u128(RESERVE_BORROWED_AMOUNT_SF_OFFSET))
+        .and_then(|v| v.checked_sub(read_u128(RESERVE_PROTOCOL_FEES_SF_OFFSET)))
+        .and_then(|v| v.checked_sub(read_u128(RESERVE_REFERRER_FEES_SF_OFFSET)))
+        .and_then(|v| v.checked_sub(read_u128(RESERVE_PENDING_REFERRER_FEES_SF_OFFSET)))
+        .ok_or(MyOAppError::Overflow)?;
+    Ok((total_sf >> FRACTION_BITS, read_u64(RESERVE_COLLATERAL_SUPPLY_OFFSET)))
+}
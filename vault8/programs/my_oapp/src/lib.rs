@@ -1,15 +1,22 @@
+mod cctp;
 mod errors;
 mod instructions;
+mod jupiter_lend;
+mod kamino;
+mod marginfi;
 mod msg_codec;
+mod save;
 mod state;
+mod strategy;
 
 use anchor_lang::prelude::*;
+use cctp::CctpNonce;
 use instructions::*;
 use oapp::{
     endpoint::MessagingFee,
     endpoint_cpi::LzAccount,
     lz_receive_types_v2::{LzReceiveTypesV2Accounts, LzReceiveTypesV2Result},
-    LzReceiveParams,
+    LzComposeParams, LzReceiveParams,
 };
 use solana_helper::program_id_from_env;
 use state::*;
@@ -26,7 +33,19 @@ declare_id!(anchor_lang::solana_program::pubkey::Pubkey::new_from_array(program_
 const STORE_SEED: &[u8] = b"Store";
 const PEER_SEED: &[u8] = b"Peer";
 const LZ_RECEIVE_TYPES_SEED: &[u8] = b"LzReceiveTypes";
+const LZ_COMPOSE_TYPES_SEED: &[u8] = b"LzComposeTypes";
 const USER_BALANCE_SEED: &[u8] = b"UserBalance";
+const CCTP_NONCE_SEED: &[u8] = b"CctpNonce";
+const PENDING_DEPOSIT_SEED: &[u8] = b"PendingDeposit";
+const MESSAGE_SENT_EVENT_SEED: &[u8] = b"MessageSentEvent";
+const DEPOSIT_RECEIPT_SEED: &[u8] = b"DepositReceipt";
+const NONCE_SEED: &[u8] = b"Nonce";
+const REFERRAL_SEED: &[u8] = b"Referral";
+const STRATEGY_SEED: &[u8] = b"Strategy";
+const MARGINFI_ACCOUNT_SEED: &[u8] = b"MarginfiAccount";
+const ROLES_SEED: &[u8] = b"Roles";
+const CONFIG_CHANGE_SEED: &[u8] = b"ConfigChange";
+const VAULT_POSITION_SEED: &[u8] = b"VaultPosition";
 
 /// Event emitted for each deposit with GUID for bot indexing
 #[event]
@@ -34,22 +53,179 @@ pub struct DepositEvent {
     pub guid: [u8; 32],            // LayerZero message GUID
     pub evm_address: [u8; 20],     // User's EVM address
     pub amount: u64,               // Amount deposited (base units)
+    pub shares: u64,               // Vault shares credited to the user
     pub new_total: u64,            // Cumulative total after this deposit
     pub deposit_index: u32,        // nth deposit for this user
     pub timestamp: i64,            // Unix timestamp
 }
 
+/// Event emitted when a deposit is parked until its CCTP mint reaches the Store USDC ATA
+#[event]
+pub struct PendingDepositEvent {
+    pub guid: [u8; 32],            // LayerZero message GUID
+    pub evm_address: [u8; 20],     // User's EVM address
+    pub amount: u64,               // Amount awaiting settlement (base units)
+    pub cctp_nonce: CctpNonce,     // CCTP nonce of the backing mint
+    pub src_eid: u32,              // Source endpoint ID
+    pub over_cap: bool,            // Funded, but over a deposit cap or the peer's inbound rate limit
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when a deposit ACK is sent back to the EVM peer
+#[event]
+pub struct DepositAckEvent {
+    pub guid: [u8; 32],            // LayerZero GUID of the acknowledged deposit
+    pub ack_guid: [u8; 32],        // LayerZero GUID of the ACK message
+    pub evm_address: [u8; 20],     // User's EVM address
+    pub amount: u64,               // Amount acknowledged (base units)
+    pub dst_eid: u32,              // Destination endpoint ID
+}
+
+/// Event emitted when a composed deposit registers the user's referrer
+#[event]
+pub struct ReferrerRegisteredEvent {
+    pub guid: [u8; 32],            // LayerZero message GUID of the deposit
+    pub evm_address: [u8; 20],     // User's EVM address
+    pub referrer: [u8; 20],        // Referrer's EVM address
+}
+
+/// Event emitted for each withdrawal burned back to the EVM vault over CCTP
+#[event]
+pub struct WithdrawEvent {
+    pub guid: [u8; 32],            // LayerZero message GUID
+    pub evm_address: [u8; 20],     // User's EVM address
+    pub amount: u64,               // Amount withdrawn and burned (base units)
+    pub shares: u64,               // Vault shares burned and debited from the user
+    pub new_total: u64,            // Cumulative withdrawals after this one
+    pub mint_recipient: [u8; 32],  // EVM vault receiving the mint on Base
+    pub destination_domain: u32,   // CCTP destination domain
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when funds are moved from one strategy to another
+#[event]
+pub struct RebalanceEvent {
+    pub from: strategy::StrategyKind, // Strategy withdrawn from
+    pub to: strategy::StrategyKind,   // Strategy deposited into
+    pub amount: u64,               // USDC requested from the source (base units)
+    pub received: u64,             // USDC the source paid out and the destination received
+    pub from_shares: u64,          // Venue shares burned in the source
+    pub to_shares: u64,            // Venue shares minted in the destination
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when `sweep_idle` moves the idle buffer toward its target
+#[event]
+pub struct IdleSweptEvent {
+    pub deposited: u64,            // USDC invested from the buffer into the strategies
+    pub withdrawn: u64,            // USDC redeemed from the strategies into the buffer
+    pub idle_assets: u64,          // Idle buffer after the sweep
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when the admin proposes a successor (default cancels)
+#[event]
+pub struct AdminProposedEvent {
+    pub admin: Pubkey,             // Current admin
+    pub pending_admin: Pubkey,     // Proposed admin
+}
+
+/// Event emitted when the proposed admin accepts the transfer
+#[event]
+pub struct AdminTransferredEvent {
+    pub previous_admin: Pubkey,    // Admin before the transfer
+    pub admin: Pubkey,             // New admin and Endpoint delegate
+}
+
+/// Event emitted when the admin assigns a role
+#[event]
+pub struct RoleSetEvent {
+    pub role: Role,                // Role assigned
+    pub account: Pubkey,           // Key now holding the role
+}
+
+/// Event emitted when the pauser toggles the global pause
+#[event]
+pub struct PausedEvent {
+    pub paused: bool,              // New global pause state
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when the pauser toggles a peer's inbound/outbound pause
+#[event]
+pub struct PeerPausedEvent {
+    pub remote_eid: u32,           // Endpoint ID of the peer
+    pub inbound_paused: bool,      // lz_receive from this peer rejected
+    pub outbound_paused: bool,     // Sends to this peer rejected
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when a timelocked config change is queued
+#[event]
+pub struct ConfigChangeQueuedEvent {
+    pub id: u64,                   // Config change id
+    pub update: ConfigUpdate,      // Change to be applied
+    pub eta: i64,                  // Earliest execution time (Unix timestamp)
+}
+
+/// Event emitted when a queued config change is applied
+#[event]
+pub struct ConfigChangeExecutedEvent {
+    pub id: u64,                   // Config change id
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when a queued config change is cancelled
+#[event]
+pub struct ConfigChangeCancelledEvent {
+    pub id: u64,                   // Config change id
+    pub timestamp: i64,            // Unix timestamp
+}
+
+/// Event emitted when `report_nav` sends the vault's NAV to an EVM peer
+#[event]
+pub struct NavReportedEvent {
+    pub guid: [u8; 32],            // LayerZero GUID of the NAV message
+    pub dst_eid: u32,              // Destination endpoint ID
+    pub value: u64,                // USDC value of the idle buffer and the strategies
+    pub total_shares: u64,         // Vault shares outstanding
+    pub timestamp: i64,            // Unix timestamp
+    pub slot: u64,                 // Slot the value was read at
+}
+
 #[program]
 pub mod my_oapp {
     use super::*;
 
     // ============================== Initializers ==============================
-    // In this example, init_store can be called by anyone and can be called only once. Ensure you implement your own access control logic if needed.
+    // init_store can be called only once, by the program's upgrade authority.
     pub fn init_store(mut ctx: Context<InitStore>, params: InitStoreParams) -> Result<()> {
         InitStore::apply(&mut ctx, &params)
     }
 
     // ============================== Admin ==============================
+    // admin instruction to upgrade a Store in the original layout to the current one
+    pub fn migrate_store(mut ctx: Context<MigrateStore>, params: MigrateStoreParams) -> Result<()> {
+        MigrateStore::apply(&mut ctx, &params)
+    }
+
+    // admin instruction to upgrade a PeerConfig in the original layout to per-msg-type options
+    pub fn migrate_peer_config(
+        mut ctx: Context<MigratePeerConfig>,
+        params: MigratePeerConfigParams,
+    ) -> Result<()> {
+        MigratePeerConfig::apply(&mut ctx, &params)
+    }
+
+    // config admin instruction (not permissionless: see MigrateUserBalance) to move a UserBalance
+    // under the old seeds to its source chain
+    pub fn migrate_user_balance(
+        mut ctx: Context<MigrateUserBalance>,
+        params: MigrateUserBalanceParams,
+    ) -> Result<()> {
+        MigrateUserBalance::apply(&mut ctx, &params)
+    }
+
     // admin instruction to set or update cross-chain peer configuration parameters.
     pub fn set_peer_config(
         mut ctx: Context<SetPeerConfig>,
@@ -58,6 +234,79 @@ pub mod my_oapp {
         SetPeerConfig::apply(&mut ctx, &params)
     }
 
+    // admin instruction to toggle ordered execution of inbound messages
+    pub fn set_ordered_nonce(
+        mut ctx: Context<SetOrderedNonce>,
+        params: SetOrderedNonceParams,
+    ) -> Result<()> {
+        SetOrderedNonce::apply(&mut ctx, &params)
+    }
+
+    // admin instruction to skip an inbound nonce that will never be delivered
+    pub fn skip_inbound_nonce(
+        mut ctx: Context<SkipInboundNonce>,
+        params: SkipInboundNonceParams,
+    ) -> Result<()> {
+        SkipInboundNonce::apply(&mut ctx, &params)
+    }
+
+    // admin instruction to propose a new admin (two-step transfer)
+    pub fn propose_admin(
+        mut ctx: Context<ProposeAdmin>,
+        params: ProposeAdminParams,
+    ) -> Result<()> {
+        ProposeAdmin::apply(&mut ctx, &params)
+    }
+
+    // pending admin instruction to accept the transfer; also moves the Endpoint delegate
+    pub fn accept_admin(mut ctx: Context<AcceptAdmin>) -> Result<()> {
+        AcceptAdmin::apply(&mut ctx)
+    }
+
+    // admin instruction to assign a role (config admin, strategy keeper, pauser, fee collector)
+    pub fn set_role(mut ctx: Context<SetRole>, params: SetRoleParams) -> Result<()> {
+        SetRole::apply(&mut ctx, &params)
+    }
+
+    // pauser instruction to toggle the global circuit breaker
+    pub fn set_paused(mut ctx: Context<SetPaused>, params: SetPausedParams) -> Result<()> {
+        SetPaused::apply(&mut ctx, &params)
+    }
+
+    // pauser instruction to pause inbound and/or outbound messaging with one peer
+    pub fn set_peer_paused(
+        mut ctx: Context<SetPeerPaused>,
+        params: SetPeerPausedParams,
+    ) -> Result<()> {
+        SetPeerPaused::apply(&mut ctx, &params)
+    }
+
+    // config admin instruction to queue a timelocked config change
+    pub fn queue_config_change(
+        mut ctx: Context<QueueConfigChange>,
+        params: QueueConfigChangeParams,
+    ) -> Result<()> {
+        QueueConfigChange::apply(&mut ctx, &params)
+    }
+
+    // config admin instruction to apply a queued config change after its ETA
+    pub fn execute_config_change(mut ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        ExecuteConfigChange::apply(&mut ctx)
+    }
+
+    // config admin instruction to abort a queued config change
+    pub fn cancel_config_change(mut ctx: Context<CancelConfigChange>) -> Result<()> {
+        CancelConfigChange::apply(&mut ctx)
+    }
+
+    // config admin instruction to turn on the config timelock
+    pub fn set_config_delay(
+        mut ctx: Context<SetConfigDelay>,
+        params: SetConfigDelayParams,
+    ) -> Result<()> {
+        SetConfigDelay::apply(&mut ctx, &params)
+    }
+
     // ============================== Public ==============================
     // public instruction returning the estimated MessagingFee for sending a message.
     pub fn quote_send(ctx: Context<QuoteSend>, params: QuoteSendParams) -> Result<MessagingFee> {
@@ -74,6 +323,11 @@ pub mod my_oapp {
         LzReceive::apply(&mut ctx, &params)
     }
 
+    // returns the next inbound nonce the Executor should deliver (0 when ordering is off)
+    pub fn next_nonce(ctx: Context<NextNonce>, params: NextNonceParams) -> Result<u64> {
+        NextNonce::apply(&ctx, &params)
+    }
+
     // handler that returns the list of accounts required to execute lz_receive (V1)
     pub fn lz_receive_types(
         ctx: Context<LzReceiveTypes>,
@@ -82,6 +336,19 @@ pub mod my_oapp {
         LzReceiveTypes::apply(&ctx, &params)
     }
 
+    // handler for compose messages sent to this OApp by `lz_receive` (composed deposits)
+    pub fn lz_compose(mut ctx: Context<LzCompose>, params: LzComposeParams) -> Result<()> {
+        LzCompose::apply(&mut ctx, &params)
+    }
+
+    // handler that returns the list of accounts required to execute lz_compose
+    pub fn lz_compose_types(
+        ctx: Context<LzComposeTypes>,
+        params: LzComposeParams,
+    ) -> Result<Vec<LzAccount>> {
+        LzComposeTypes::apply(&ctx, &params)
+    }
+
     // V2 handler that returns version and versioned data for LzReceiveTypes
     pub fn lz_receive_types_info(
         ctx: Context<LzReceiveTypesInfo>,
@@ -103,13 +370,125 @@ pub mod my_oapp {
         SetJlConfig::apply(&mut ctx, params)
     }
 
+    // Admin method to set the Kamino K-Lend reserve configuration
+    pub fn set_kamino_config(
+        mut ctx: Context<SetKaminoConfig>,
+        params: SetKaminoConfigParams,
+    ) -> Result<()> {
+        SetKaminoConfig::apply(&mut ctx, &params)
+    }
+
+    // Admin method to create or update the config PDA of a yield strategy
+    pub fn set_strategy_config(
+        mut ctx: Context<SetStrategyConfig>,
+        params: SetStrategyConfigParams,
+    ) -> Result<()> {
+        SetStrategyConfig::apply(&mut ctx, &params)
+    }
+
+    // Admin method to set the MarginFi bank configuration
+    pub fn set_marginfi_config(
+        mut ctx: Context<SetMarginfiConfig>,
+        params: SetMarginfiConfigParams,
+    ) -> Result<()> {
+        SetMarginfiConfig::apply(&mut ctx, &params)
+    }
+
+    // Admin method to set the Save reserve configuration
+    pub fn set_save_config(mut ctx: Context<SetSaveConfig>, params: SetSaveConfigParams) -> Result<()> {
+        SetSaveConfig::apply(&mut ctx, &params)
+    }
+
+    // Admin method to set the bps weights each deposit is split by across strategies
+    pub fn set_strategy_weights(
+        mut ctx: Context<SetStrategyWeights>,
+        params: SetStrategyWeightsParams,
+    ) -> Result<()> {
+        SetStrategyWeights::apply(&mut ctx, &params)
+    }
+
+    // Strategy keeper method to move funds from one strategy to another, with a minimum USDC out
+    pub fn rebalance(mut ctx: Context<Rebalance>, params: RebalanceParams) -> Result<()> {
+        Rebalance::apply(&mut ctx, &params)
+    }
+
+    // returns the execution plan for rebalance with ALT-compressed accounts
+    pub fn rebalance_types(
+        ctx: Context<RebalanceTypes>,
+        params: RebalanceParams,
+    ) -> Result<LzReceiveTypesV2Result> {
+        RebalanceTypes::apply(&ctx, &params)
+    }
+
+    // Admin method to set the per-user deposit cap and the global TVL cap
+    pub fn set_deposit_caps(
+        mut ctx: Context<SetDepositCaps>,
+        params: SetDepositCapsParams,
+    ) -> Result<()> {
+        SetDepositCaps::apply(&mut ctx, &params)
+    }
+
+    // Admin method to set the minimum interval between NAV reports to a peer
+    pub fn set_nav_report_interval(
+        mut ctx: Context<SetNavReportInterval>,
+        params: SetNavReportIntervalParams,
+    ) -> Result<()> {
+        SetNavReportInterval::apply(&mut ctx, &params)
+    }
+
+    // Admin method to set the idle-cash buffer target kept in the Store USDC ATA
+    pub fn set_idle_target(
+        mut ctx: Context<SetIdleTarget>,
+        params: SetIdleTargetParams,
+    ) -> Result<()> {
+        SetIdleTarget::apply(&mut ctx, &params)
+    }
+
     // Admin method to set Address Lookup Table for V2
     pub fn set_alt(mut ctx: Context<SetAlt>) -> Result<()> {
         SetAlt::apply(&mut ctx)
     }
 
-    // One-time setup: Initialize Store's USDC and fToken ATAs
+    // Permissionless crank: credit and invest a parked deposit once its USDC has arrived
+    pub fn settle_pending_deposit(mut ctx: Context<SettlePendingDeposit>) -> Result<()> {
+        SettlePendingDeposit::apply(&mut ctx)
+    }
+
+    // Permissionless crank: top up or drain the idle buffer toward its target
+    pub fn sweep_idle(mut ctx: Context<SweepIdle>, params: SweepIdleParams) -> Result<()> {
+        SweepIdle::apply(&mut ctx, &params)
+    }
+
+    // Permissionless crank: ACK a credited deposit back to the EVM peer, paid by the caller
+    pub fn send_deposit_ack(
+        mut ctx: Context<SendDepositAck>,
+        params: SendDepositAckParams,
+    ) -> Result<()> {
+        SendDepositAck::apply(&mut ctx, &params)
+    }
+
+    // Permissionless crank: send the vault's NAV to an EVM peer, paid by the caller
+    pub fn report_nav(mut ctx: Context<ReportNav>, params: ReportNavParams) -> Result<()> {
+        ReportNav::apply(&mut ctx, &params)
+    }
+
+    // view instruction returning a user's USDC-equivalent value across the allocated strategies
+    pub fn get_user_value(ctx: Context<GetUserValue>) -> Result<u64> {
+        GetUserValue::apply(&ctx)
+    }
+
+    // view instruction returning an EVM vault's USDC-equivalent value on one source chain
+    pub fn get_vault_value(ctx: Context<GetVaultValue>) -> Result<u64> {
+        GetVaultValue::apply(&ctx)
+    }
+
+    // One-time setup: Initialize Store's USDC and strategy share ATAs
     pub fn init_store_atas(ctx: Context<InitStoreAtas>) -> Result<()> {
         InitStoreAtas::apply(&ctx)
     }
+
+    // One-time setup: Create the Store's MarginfiAccount in the configured group
+    pub fn init_marginfi_account(ctx: Context<InitMarginfiAccount>) -> Result<()> {
+        InitMarginfiAccount::apply(&ctx)
+    }
 }
@@ -0,0 +1,259 @@
+use crate::errors::MyOAppError;
+use crate::strategy::{self, Strategy};
+use crate::*;
+use oapp::endpoint_cpi::LzAccount;
+
+/// Number of accounts the MarginFi deposit takes (see `deposit_accounts` for the order)
+pub const DEPOSIT_ACCOUNTS_LEN: usize = 8;
+/// MarginFi `lending_account_deposit` discriminator from IDL
+pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [171, 94, 235, 103, 82, 64, 212, 140];
+/// Number of accounts the MarginFi withdraw takes (see `withdraw_accounts` for the order)
+pub const WITHDRAW_ACCOUNTS_LEN: usize = 11;
+/// MarginFi `lending_account_withdraw` discriminator from IDL
+pub const WITHDRAW_DISCRIMINATOR: [u8; 8] = [36, 72, 74, 19, 210, 210, 192, 192];
+/// MarginFi `marginfi_account_initialize` discriminator from IDL
+pub const INITIALIZE_ACCOUNT_DISCRIMINATOR: [u8; 8] = [43, 78, 61, 255, 148, 52, 249, 154];
+/// Byte offsets in the `MarginfiAccount`: discriminator(8) + group(32) + authority(32), then the
+/// lending account's 16 balances of 104 bytes: active(1) + bank_pk(32) + tag/padding(7)
+/// + asset_shares(16) + ...
+pub const ACCOUNT_BALANCES_OFFSET: usize = 72;
+pub const BALANCE_LEN: usize = 104;
+pub const MAX_BALANCES: usize = 16;
+pub const BALANCE_BANK_OFFSET: usize = 1;
+pub const BALANCE_ASSET_SHARES_OFFSET: usize = 40;
+/// `Bank.asset_share_value`: discriminator(8) + mint(32) + mint_decimals(1) + group(32) + padding(7)
+pub const BANK_ASSET_SHARE_VALUE_OFFSET: usize = 80;
+/// MarginFi amounts are `I80F48` fixed-point numbers with 48 fractional bits
+pub const FRACTION_BITS: u32 = 48;
+
+// Order of the MarginFi accounts in `StrategyConfig.accounts` (USDC bank on the current cluster).
+// `program_id` is the MarginFi program; `share_mint` is unused because positions live in the
+// Store's `MarginfiAccount` (see `init_marginfi_account`), not in a token.
+pub const GROUP: usize = 0;
+pub const BANK: usize = 1;
+pub const LIQUIDITY_VAULT: usize = 2;
+pub const LIQUIDITY_VAULT_AUTHORITY: usize = 3;
+pub const ORACLE: usize = 4;
+pub const CONFIG_ACCOUNTS_LEN: usize = 5;
+
+/// MarginFi v2 bank adapter. Deposits credit asset shares to the Store's `MarginfiAccount` in the
+/// configured group; shares are the change in those asset shares, floored to whole units.
+pub struct MarginFi;
+
+impl Strategy for MarginFi {
+    fn config_accounts_len(&self) -> usize {
+        CONFIG_ACCOUNTS_LEN
+    }
+
+    fn deposit_accounts_len(&self) -> usize {
+        DEPOSIT_ACCOUNTS_LEN
+    }
+
+    fn withdraw_accounts_len(&self) -> usize {
+        WITHDRAW_ACCOUNTS_LEN
+    }
+
+    /// 0: group
+    /// 1: marginfiAccount (Store's, PDA of this program)
+    /// 2: authority (store PDA, signer via invoke_signed)
+    /// 3: bank
+    /// 4: signerTokenAccount (USDC ATA)
+    /// 5: liquidityVault
+    /// 6: tokenProgram
+    /// 7: MarginFi program
+    fn deposit_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        vec![
+            LzAccount { pubkey: c.account(GROUP), is_signer: false, is_writable: false },
+            LzAccount { pubkey: marginfi_account_address(c), is_signer: false, is_writable: true },
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(BANK), is_signer: false, is_writable: true },
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LIQUIDITY_VAULT), is_signer: false, is_writable: true },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false },
+        ]
+    }
+
+    /// 0: group
+    /// 1: marginfiAccount (Store's, PDA of this program)
+    /// 2: authority (store PDA, signer via invoke_signed)
+    /// 3: bank
+    /// 4: destinationTokenAccount (USDC ATA)
+    /// 5: bankLiquidityVaultAuthority
+    /// 6: liquidityVault
+    /// 7: tokenProgram
+    /// 8..=9: risk engine health check accounts (bank, oracle). The bank repeats account 3 and is
+    ///        writable there, so it is listed writable here too: a transaction holds one meta per key
+    /// 10: MarginFi program
+    fn withdraw_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        vec![
+            LzAccount { pubkey: c.account(GROUP), is_signer: false, is_writable: false },
+            LzAccount { pubkey: marginfi_account_address(c), is_signer: false, is_writable: true },
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(BANK), is_signer: false, is_writable: true },
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LIQUIDITY_VAULT_AUTHORITY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LIQUIDITY_VAULT), is_signer: false, is_writable: true },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(BANK), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(ORACLE), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false },
+        ]
+    }
+
+    /// Deposits from the Store USDC ATA; returns the asset shares credited, measured on the
+    /// Store's `MarginfiAccount` (account 1) around the CPI.
+    fn deposit(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.deposit_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+
+        let data = ix_data(config.deposit_discriminator, amount);
+        let shares_before = asset_shares(config, &accounts[1])?;
+        strategy::invoke(store, store_key, config, &expected, accounts, data)?;
+        let shares_after = asset_shares(config, &accounts[1])?;
+        shares_delta(shares_before, shares_after)
+    }
+
+    /// Withdraws into the Store USDC ATA; returns the asset shares burned, measured on the
+    /// Store's `MarginfiAccount` (account 1) around the CPI.
+    fn withdraw(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.withdraw_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+
+        let data = ix_data(config.withdraw_discriminator, amount);
+        let shares_before = asset_shares(config, &accounts[1])?;
+        strategy::invoke(store, store_key, config, &expected, accounts, data)?;
+        let shares_after = asset_shares(config, &accounts[1])?;
+        shares_delta(shares_after, shares_before)
+    }
+
+    fn price_account(&self, config: &StrategyConfig) -> Pubkey {
+        config.account(BANK)
+    }
+
+    /// Prices asset shares with the `Bank` account's `asset_share_value`, as of its last accrual.
+    fn shares_to_assets(&self, config: &StrategyConfig, price_account: &AccountInfo, shares: u64) -> Result<u64> {
+        require_keys_eq!(price_account.key(), config.account(BANK), MyOAppError::InvalidAccount);
+        require_keys_eq!(*price_account.owner, config.program_id, MyOAppError::InvalidAccount);
+        let data = price_account.try_borrow_data()?;
+        let share_value = read_i80f48(&data, BANK_ASSET_SHARE_VALUE_OFFSET)?;
+        let assets = ((shares as u128) * share_value) >> FRACTION_BITS;
+        Ok(u64::try_from(assets).map_err(|_| MyOAppError::Overflow)?)
+    }
+}
+
+/// The Store's `MarginfiAccount` in the configured group, a PDA of this program so the Store can
+/// sign its creation.
+pub fn marginfi_account_address(config: &StrategyConfig) -> Pubkey {
+    let seeds: &[&[u8]] = &[MARGINFI_ACCOUNT_SEED, &config.account(GROUP).to_bytes()];
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+/// `discriminator || amount (u64 LE) || None`: both instructions take a trailing `Option<bool>`
+/// (deposit up to the bank limit / withdraw all), left unset.
+fn ix_data(discriminator: [u8; 8], amount: u64) -> Vec<u8> {
+    let mut data = strategy::amount_ix_data(discriminator, amount);
+    data.push(0);
+    data
+}
+
+/// Whole asset shares between two raw `I80F48` balances. The fractions are subtracted before
+/// flooring, so a deposit or withdrawal that crosses a whole share is not off by one.
+fn shares_delta(low: u128, high: u128) -> Result<u64> {
+    let delta = high.checked_sub(low).ok_or(MyOAppError::Overflow)? >> FRACTION_BITS;
+    Ok(u64::try_from(delta).map_err(|_| MyOAppError::Overflow)?)
+}
+
+/// Asset shares the Store holds in the configured bank as raw `I80F48` bits (0 before the first
+/// deposit).
+fn asset_shares(config: &StrategyConfig, marginfi_account: &AccountInfo) -> Result<u128> {
+    require_keys_eq!(*marginfi_account.owner, config.program_id, MyOAppError::InvalidAccount);
+    let data = marginfi_account.try_borrow_data()?;
+    require!(
+        data.len() >= ACCOUNT_BALANCES_OFFSET + MAX_BALANCES * BALANCE_LEN,
+        MyOAppError::InvalidAccount
+    );
+    let bank = config.account(BANK);
+    for i in 0..MAX_BALANCES {
+        let balance = ACCOUNT_BALANCES_OFFSET + i * BALANCE_LEN;
+        let bank_pk = &data[balance + BALANCE_BANK_OFFSET..balance + BALANCE_BANK_OFFSET + 32];
+        if data[balance] != 0 && bank_pk == bank.as_ref() {
+            return read_i80f48(&data, balance + BALANCE_ASSET_SHARES_OFFSET);
+        }
+    }
+    Ok(0)
+}
+
+/// Reads a non-negative `I80F48` as its raw bits.
+fn read_i80f48(data: &[u8], offset: usize) -> Result<u128> {
+    require!(data.len() >= offset + 16, MyOAppError::InvalidAccount);
+    let mut buf = [0u8; 16];
+    buf.copy_from_slice(&data[offset..offset + 16]);
+    Ok(u128::try_from(i128::from_le_bytes(buf)).map_err(|_| MyOAppError::Overflow)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: u128 = 1 << FRACTION_BITS;
+
+    #[test]
+    fn shares_delta_floors_the_difference_once() {
+        // 1.75 -> 2.25 asset shares is half a share, not the one whole share flooring each side gives
+        assert_eq!(shares_delta(ONE + 3 * ONE / 4, 2 * ONE + ONE / 4).unwrap(), 0);
+        assert_eq!(shares_delta(ONE / 2, 3 * ONE + ONE / 2).unwrap(), 3);
+        assert!(shares_delta(2 * ONE, ONE).is_err());
+    }
+
+    #[test]
+    fn withdraw_accounts_list_the_bank_writable_both_times() {
+        let store = Store::try_from_slice(&[0u8; Store::INIT_SPACE]).unwrap();
+        let config = StrategyConfig {
+            kind: strategy::StrategyKind::MarginFi,
+            program_id: Pubkey::new_unique(),
+            share_mint: Pubkey::default(),
+            deposit_discriminator: DEPOSIT_DISCRIMINATOR,
+            withdraw_discriminator: WITHDRAW_DISCRIMINATOR,
+            accounts: (0..CONFIG_ACCOUNTS_LEN).map(|_| Pubkey::new_unique()).collect(),
+            bump: 0,
+            version: StrategyConfig::VERSION,
+            reserved: [0; 64],
+        };
+        let accounts = MarginFi.withdraw_accounts(&store, &Pubkey::new_unique(), &config);
+        assert_eq!(accounts.len(), WITHDRAW_ACCOUNTS_LEN);
+        // The risk engine pair (bank, oracle) follows the fixed accounts
+        assert_eq!(accounts[3].pubkey, config.account(BANK));
+        assert_eq!(accounts[8].pubkey, config.account(BANK));
+        assert_eq!(accounts[9].pubkey, config.account(ORACLE));
+        assert_eq!(accounts[10].pubkey, config.program_id);
+        // Every key keeps one writability across the list
+        for a in &accounts {
+            for b in &accounts {
+                if a.pubkey == b.pubkey {
+                    assert_eq!(a.is_writable, b.is_writable, "{}", a.pubkey);
+                }
+            }
+        }
+    }
+}
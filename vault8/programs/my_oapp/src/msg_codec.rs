@@ -1,30 +1,550 @@
-use anchor_lang::prelude::error_code;
-use std::str;
+use crate::cctp::CctpNonce;
+use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum MsgCodecError {
     InvalidLength,
     BodyTooShort,
     InvalidUtf8,
+    UnsupportedVersion,
+    UnknownMessageType,
+    UnexpectedMessageType,
+    UnknownComposeAction,
 }
 
-// Legacy string codec retained for compatibility with existing flows
-pub const STRING_OFFSET: usize = 32;
+// Typed payloads exchanged with the EVM MyOApp (mirrored in contracts/libs/VaultMsgCodec.sol).
+// Layout: [version:1][msg_type:1][body], integers little-endian.
+pub const MSG_VERSION: u8 = 1;
+pub const HEADER_LEN: usize = 2;
 
+// [amount:8][evm_address:20][vault:20][cctp_source_domain:4][cctp_nonce:32]
+pub const DEPOSIT_BODY_LEN: usize = 8 + 20 + 20 + 4 + 32;
+// [amount:8][evm_address:20][vault:20]
+pub const WITHDRAW_BODY_LEN: usize = 8 + 20 + 20;
+// [amount:8][guid:32]
+pub const ACK_BODY_LEN: usize = 8 + 32;
+// [from_strategy:1][to_strategy:1][amount:8]
+pub const REBALANCE_BODY_LEN: usize = 1 + 1 + 8;
+// [command:1][value:8]
+pub const ADMIN_COMMAND_BODY_LEN: usize = 1 + 8;
+// [amount:8][evm_address:20][vault:20][cctp_source_domain:4][cctp_nonce:32][compose_action:1+]
+pub const DEPOSIT_AND_CALL_MIN_BODY_LEN: usize = DEPOSIT_BODY_LEN + 1;
+// [amount:8][evm_address:20][guid:32]
+pub const WITHDRAW_COMPLETE_BODY_LEN: usize = 8 + 20 + 32;
+// [evm_address:20][shares:8][total_deposited:8][total_withdrawn:8][timestamp:8]
+pub const BALANCE_SNAPSHOT_BODY_LEN: usize = 20 + 8 + 8 + 8 + 8;
+// [value:8][timestamp:8][slot:8]
+pub const NAV_BODY_LEN: usize = 8 + 8 + 8;
 
-pub fn encode_string(string: &str) -> Vec<u8> {
-    let string_bytes = string.as_bytes();
-    let mut msg = Vec::with_capacity(STRING_OFFSET + string_bytes.len());
-    msg.extend(std::iter::repeat(0).take(28));
-    msg.extend_from_slice(&(string_bytes.len() as u32).to_be_bytes());
-    msg.extend_from_slice(string_bytes);
-    msg
+// Compose payload, local to this program:
+// [amount:8][evm_address:20][vault:20][cctp_source_domain:4][cctp_nonce:32][src_eid:4]
+// [compose_action:1+]
+pub const COMPOSE_MSG_MIN_LEN: usize = DEPOSIT_BODY_LEN + 4 + 1;
+
+// Compose actions: [action:1][body]
+pub const COMPOSE_ALLOCATE: u8 = 1;
+// [referrer:20]
+pub const COMPOSE_REGISTER_REFERRER: u8 = 2;
+// Longest encoded action (`RegisterReferrer`)
+pub const COMPOSE_ACTION_MAX_LEN: usize = 1 + 20;
+
+/// Message type byte. Values match the `msgType` passed to `combineOptions` on the EVM side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MsgType {
+    Deposit = 1,
+    Withdraw = 2,
+    Ack = 3,
+    Rebalance = 4,
+    AdminCommand = 5,
+    DepositAndCall = 6,
+    WithdrawComplete = 7,
+    BalanceSnapshot = 8,
+    Nav = 9,
+}
+
+impl MsgType {
+    pub fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            1 => Ok(MsgType::Deposit),
+            2 => Ok(MsgType::Withdraw),
+            3 => Ok(MsgType::Ack),
+            4 => Ok(MsgType::Rebalance),
+            5 => Ok(MsgType::AdminCommand),
+            6 => Ok(MsgType::DepositAndCall),
+            7 => Ok(MsgType::WithdrawComplete),
+            8 => Ok(MsgType::BalanceSnapshot),
+            9 => Ok(MsgType::Nav),
+            _ => err!(MsgCodecError::UnknownMessageType),
+        }
+    }
+
+    /// Exact body length, or the minimum for `DepositAndCall` (whose compose action varies).
+    pub fn body_len(self) -> usize {
+        match self {
+            MsgType::Deposit => DEPOSIT_BODY_LEN,
+            MsgType::Withdraw => WITHDRAW_BODY_LEN,
+            MsgType::Ack => ACK_BODY_LEN,
+            MsgType::Rebalance => REBALANCE_BODY_LEN,
+            MsgType::AdminCommand => ADMIN_COMMAND_BODY_LEN,
+            MsgType::DepositAndCall => DEPOSIT_AND_CALL_MIN_BODY_LEN,
+            MsgType::WithdrawComplete => WITHDRAW_COMPLETE_BODY_LEN,
+            MsgType::BalanceSnapshot => BALANCE_SNAPSHOT_BODY_LEN,
+            MsgType::Nav => NAV_BODY_LEN,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    /// `vault` is the EVM vault whose position the deposit is credited to
+    Deposit { amount: u64, evm_address: [u8; 20], vault: [u8; 20], cctp_nonce: CctpNonce },
+    /// `vault` is the EVM vault whose position is debited and that receives the USDC
+    Withdraw { amount: u64, evm_address: [u8; 20], vault: [u8; 20] },
+    Ack { amount: u64, guid: [u8; 32] },
+    Rebalance { from_strategy: u8, to_strategy: u8, amount: u64 },
+    AdminCommand { command: u8, value: u64 },
+    /// Deposit followed by a compose action executed in `lz_compose`
+    DepositAndCall {
+        amount: u64,
+        evm_address: [u8; 20],
+        vault: [u8; 20],
+        cctp_nonce: CctpNonce,
+        action: ComposeAction,
+    },
+    /// Notifies the EVM side that the withdrawal received as `guid` was paid out
+    WithdrawComplete { amount: u64, evm_address: [u8; 20], guid: [u8; 32] },
+    /// A user's `UserBalance` as of `timestamp`
+    BalanceSnapshot {
+        evm_address: [u8; 20],
+        shares: u64,
+        total_deposited: u64,
+        total_withdrawn: u64,
+        timestamp: i64,
+    },
+    /// USDC value of everything backing the vault shares, as of `timestamp` / `slot`
+    Nav { value: u64, timestamp: i64, slot: u64 },
+}
+
+impl Message {
+    pub fn msg_type(&self) -> MsgType {
+        match self {
+            Message::Deposit { .. } => MsgType::Deposit,
+            Message::Withdraw { .. } => MsgType::Withdraw,
+            Message::Ack { .. } => MsgType::Ack,
+            Message::Rebalance { .. } => MsgType::Rebalance,
+            Message::AdminCommand { .. } => MsgType::AdminCommand,
+            Message::DepositAndCall { .. } => MsgType::DepositAndCall,
+            Message::WithdrawComplete { .. } => MsgType::WithdrawComplete,
+            Message::BalanceSnapshot { .. } => MsgType::BalanceSnapshot,
+            Message::Nav { .. } => MsgType::Nav,
+        }
+    }
+
+    /// EVM user the message acts on. Only deposits and withdrawals carry one.
+    pub fn evm_address(&self) -> Result<[u8; 20]> {
+        match self {
+            Message::Deposit { evm_address, .. }
+            | Message::Withdraw { evm_address, .. }
+            | Message::DepositAndCall { evm_address, .. } => Ok(*evm_address),
+            _ => err!(MsgCodecError::UnexpectedMessageType),
+        }
+    }
+
+    /// EVM vault a deposit or withdrawal is made for (keys its `VaultPosition`).
+    pub fn vault(&self) -> Result<[u8; 20]> {
+        match self {
+            Message::Deposit { vault, .. }
+            | Message::Withdraw { vault, .. }
+            | Message::DepositAndCall { vault, .. } => Ok(*vault),
+            _ => err!(MsgCodecError::UnexpectedMessageType),
+        }
+    }
+
+    /// CCTP nonce of the mint backing a deposit.
+    pub fn cctp_nonce(&self) -> Result<CctpNonce> {
+        match self {
+            Message::Deposit { cctp_nonce, .. } | Message::DepositAndCall { cctp_nonce, .. } => {
+                Ok(*cctp_nonce)
+            },
+            _ => err!(MsgCodecError::UnexpectedMessageType),
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let msg_type = self.msg_type();
+        let mut msg = Vec::with_capacity(HEADER_LEN + msg_type.body_len());
+        msg.push(MSG_VERSION);
+        msg.push(msg_type as u8);
+        match self {
+            Message::Deposit { amount, evm_address, vault, cctp_nonce } => {
+                msg.extend_from_slice(&amount.to_le_bytes());
+                msg.extend_from_slice(evm_address);
+                msg.extend_from_slice(vault);
+                encode_cctp_nonce(cctp_nonce, &mut msg);
+            },
+            Message::Withdraw { amount, evm_address, vault } => {
+                msg.extend_from_slice(&amount.to_le_bytes());
+                msg.extend_from_slice(evm_address);
+                msg.extend_from_slice(vault);
+            },
+            Message::Ack { amount, guid } => {
+                msg.extend_from_slice(&amount.to_le_bytes());
+                msg.extend_from_slice(guid);
+            },
+            Message::Rebalance { from_strategy, to_strategy, amount } => {
+                msg.push(*from_strategy);
+                msg.push(*to_strategy);
+                msg.extend_from_slice(&amount.to_le_bytes());
+            },
+            Message::AdminCommand { command, value } => {
+                msg.push(*command);
+                msg.extend_from_slice(&value.to_le_bytes());
+            },
+            Message::DepositAndCall { amount, evm_address, vault, cctp_nonce, action } => {
+                msg.extend_from_slice(&amount.to_le_bytes());
+                msg.extend_from_slice(evm_address);
+                msg.extend_from_slice(vault);
+                encode_cctp_nonce(cctp_nonce, &mut msg);
+                action.encode_into(&mut msg);
+            },
+            Message::WithdrawComplete { amount, evm_address, guid } => {
+                msg.extend_from_slice(&amount.to_le_bytes());
+                msg.extend_from_slice(evm_address);
+                msg.extend_from_slice(guid);
+            },
+            Message::BalanceSnapshot {
+                evm_address,
+                shares,
+                total_deposited,
+                total_withdrawn,
+                timestamp,
+            } => {
+                msg.extend_from_slice(evm_address);
+                msg.extend_from_slice(&shares.to_le_bytes());
+                msg.extend_from_slice(&total_deposited.to_le_bytes());
+                msg.extend_from_slice(&total_withdrawn.to_le_bytes());
+                msg.extend_from_slice(&timestamp.to_le_bytes());
+            },
+            Message::Nav { value, timestamp, slot } => {
+                msg.extend_from_slice(&value.to_le_bytes());
+                msg.extend_from_slice(&timestamp.to_le_bytes());
+                msg.extend_from_slice(&slot.to_le_bytes());
+            },
+        }
+        msg
+    }
+
+    pub fn decode(message: &[u8]) -> Result<Self> {
+        require!(message.len() >= HEADER_LEN, MsgCodecError::InvalidLength);
+        require!(message[0] == MSG_VERSION, MsgCodecError::UnsupportedVersion);
+        let msg_type = MsgType::from_u8(message[1])?;
+
+        let body = &message[HEADER_LEN..];
+        require!(body.len() >= msg_type.body_len(), MsgCodecError::BodyTooShort);
+        // Only the compose action of DepositAndCall is variable; it checks its own length
+        if msg_type != MsgType::DepositAndCall {
+            require!(body.len() == msg_type.body_len(), MsgCodecError::InvalidLength);
+        }
+
+        let msg = match msg_type {
+            MsgType::Deposit => Message::Deposit {
+                amount: read_u64(body, 0),
+                evm_address: read_array(body, 8),
+                vault: read_array(body, 28),
+                cctp_nonce: read_cctp_nonce(body, 48),
+            },
+            MsgType::Withdraw => Message::Withdraw {
+                amount: read_u64(body, 0),
+                evm_address: read_array(body, 8),
+                vault: read_array(body, 28),
+            },
+            MsgType::Ack => Message::Ack { amount: read_u64(body, 0), guid: read_array(body, 8) },
+            MsgType::Rebalance => Message::Rebalance {
+                from_strategy: body[0],
+                to_strategy: body[1],
+                amount: read_u64(body, 2),
+            },
+            MsgType::AdminCommand => {
+                Message::AdminCommand { command: body[0], value: read_u64(body, 1) }
+            },
+            MsgType::DepositAndCall => Message::DepositAndCall {
+                amount: read_u64(body, 0),
+                evm_address: read_array(body, 8),
+                vault: read_array(body, 28),
+                cctp_nonce: read_cctp_nonce(body, 48),
+                action: ComposeAction::decode(&body[DEPOSIT_BODY_LEN..])?,
+            },
+            MsgType::WithdrawComplete => Message::WithdrawComplete {
+                amount: read_u64(body, 0),
+                evm_address: read_array(body, 8),
+                guid: read_array(body, 28),
+            },
+            MsgType::BalanceSnapshot => Message::BalanceSnapshot {
+                evm_address: read_array(body, 0),
+                shares: read_u64(body, 20),
+                total_deposited: read_u64(body, 28),
+                total_withdrawn: read_u64(body, 36),
+                timestamp: read_u64(body, 44) as i64,
+            },
+            MsgType::Nav => Message::Nav {
+                value: read_u64(body, 0),
+                timestamp: read_u64(body, 8) as i64,
+                slot: read_u64(body, 16),
+            },
+        };
+        Ok(msg)
+    }
+}
+
+pub fn decode(message: &[u8]) -> Result<Message> {
+    Message::decode(message)
 }
 
+/// Follow-up action a composed deposit triggers against the deposited position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComposeAction {
+    /// Settle the deposit into the strategies if it was parked and its funds have landed
+    Allocate,
+    /// Record the user's referrer (first registration wins)
+    RegisterReferrer { referrer: [u8; 20] },
+}
+
+impl ComposeAction {
+    pub fn encode_into(&self, msg: &mut Vec<u8>) {
+        match self {
+            ComposeAction::Allocate => msg.push(COMPOSE_ALLOCATE),
+            ComposeAction::RegisterReferrer { referrer } => {
+                msg.push(COMPOSE_REGISTER_REFERRER);
+                msg.extend_from_slice(referrer);
+            },
+        }
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        require!(!buf.is_empty(), MsgCodecError::BodyTooShort);
+        let body = &buf[1..];
+        let (action, body_len) = match buf[0] {
+            COMPOSE_ALLOCATE => (ComposeAction::Allocate, 0),
+            COMPOSE_REGISTER_REFERRER => {
+                require!(body.len() >= 20, MsgCodecError::BodyTooShort);
+                (ComposeAction::RegisterReferrer { referrer: read_array(body, 0) }, 20)
+            },
+            _ => return err!(MsgCodecError::UnknownComposeAction),
+        };
+        require!(body.len() == body_len, MsgCodecError::InvalidLength);
+        Ok(action)
+    }
+}
+
+/// Payload `lz_receive` hands to `lz_compose` through `Endpoint::send_compose`.
+/// Layout: [amount:8][evm_address:20][vault:20][cctp_source_domain:4][cctp_nonce:32][src_eid:4]
+/// [compose_action]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComposeMsg {
+    pub amount: u64,
+    pub evm_address: [u8; 20],
+    pub vault: [u8; 20],
+    pub cctp_nonce: CctpNonce,
+    pub src_eid: u32,
+    pub action: ComposeAction,
+}
+
+impl ComposeMsg {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut msg = Vec::with_capacity(COMPOSE_MSG_MIN_LEN + 20);
+        msg.extend_from_slice(&self.amount.to_le_bytes());
+        msg.extend_from_slice(&self.evm_address);
+        msg.extend_from_slice(&self.vault);
+        encode_cctp_nonce(&self.cctp_nonce, &mut msg);
+        msg.extend_from_slice(&self.src_eid.to_le_bytes());
+        self.action.encode_into(&mut msg);
+        msg
+    }
+
+    pub fn decode(message: &[u8]) -> Result<Self> {
+        require!(message.len() >= COMPOSE_MSG_MIN_LEN, MsgCodecError::BodyTooShort);
+        Ok(ComposeMsg {
+            amount: read_u64(message, 0),
+            evm_address: read_array(message, 8),
+            vault: read_array(message, 28),
+            cctp_nonce: read_cctp_nonce(message, 48),
+            src_eid: read_u32(message, DEPOSIT_BODY_LEN),
+            action: ComposeAction::decode(&message[DEPOSIT_BODY_LEN + 4..])?,
+        })
+    }
+}
+
+fn encode_cctp_nonce(cctp_nonce: &CctpNonce, msg: &mut Vec<u8>) {
+    msg.extend_from_slice(&cctp_nonce.source_domain.to_le_bytes());
+    msg.extend_from_slice(&cctp_nonce.nonce);
+}
+
+// Callers check lengths before reading, so these only slice within bounds.
+fn read_cctp_nonce(buf: &[u8], offset: usize) -> CctpNonce {
+    CctpNonce { source_domain: read_u32(buf, offset), nonce: read_array(buf, offset + 4) }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(read_array(buf, offset))
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(read_array(buf, offset))
+}
+
+fn read_array<const N: usize>(buf: &[u8], offset: usize) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(&buf[offset..offset + N]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVM: [u8; 20] = [0x11; 20];
+    const VAULT: [u8; 20] = [0x22; 20];
+    const NONCE: CctpNonce = CctpNonce { source_domain: 6, nonce: [0x33; 32] };
+
+    fn all_messages() -> Vec<Message> {
+        vec![
+            Message::Deposit {
+                amount: 1_000_000,
+                evm_address: EVM,
+                vault: VAULT,
+                cctp_nonce: NONCE,
+            },
+            Message::Withdraw { amount: 500_000, evm_address: EVM, vault: VAULT },
+            Message::Ack { amount: 1_000_000, guid: [0x44; 32] },
+            Message::Rebalance { from_strategy: 0, to_strategy: 2, amount: 42 },
+            Message::AdminCommand { command: 1, value: u64::MAX },
+            Message::DepositAndCall {
+                amount: 7,
+                evm_address: EVM,
+                vault: VAULT,
+                cctp_nonce: NONCE,
+                action: ComposeAction::Allocate,
+            },
+            Message::DepositAndCall {
+                amount: 7,
+                evm_address: EVM,
+                vault: VAULT,
+                cctp_nonce: NONCE,
+                action: ComposeAction::RegisterReferrer { referrer: [0x55; 20] },
+            },
+            Message::WithdrawComplete { amount: 9, evm_address: EVM, guid: [0x66; 32] },
+            Message::BalanceSnapshot {
+                evm_address: EVM,
+                shares: 1,
+                total_deposited: 2,
+                total_withdrawn: 3,
+                timestamp: -4,
+            },
+            Message::Nav { value: 5, timestamp: 6, slot: 7 },
+        ]
+    }
+
+    fn error_code<T: std::fmt::Debug>(res: Result<T>) -> u32 {
+        match res.unwrap_err() {
+            Error::AnchorError(e) => e.error_code_number,
+            e => panic!("unexpected error {e:?}"),
+        }
+    }
+
+    #[test]
+    fn round_trips_every_message() {
+        for msg in all_messages() {
+            let encoded = msg.encode();
+            assert_eq!(encoded[0], MSG_VERSION);
+            assert_eq!(encoded[1], msg.msg_type() as u8);
+            assert_eq!(Message::decode(&encoded).unwrap(), msg);
+        }
+    }
+
+    #[test]
+    fn deposit_matches_evm_layout() {
+        // VaultMsgCodec.encDeposit(1_000_000, 0x11.., 0x22.., 6, 0x33..)
+        let mut expected = vec![MSG_VERSION, MsgType::Deposit as u8];
+        expected.extend_from_slice(&[0x40, 0x42, 0x0f, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(&EVM);
+        expected.extend_from_slice(&VAULT);
+        expected.extend_from_slice(&[6, 0, 0, 0]);
+        expected.extend_from_slice(&[0x33; 32]);
+        assert_eq!(all_messages()[0].encode(), expected);
+        assert_eq!(expected.len(), HEADER_LEN + DEPOSIT_BODY_LEN);
+    }
+
+    #[test]
+    fn rejects_truncated_payloads() {
+        assert_eq!(error_code(Message::decode(&[])), MsgCodecError::InvalidLength.into());
+        for msg in all_messages() {
+            let encoded = msg.encode();
+            let res = Message::decode(&encoded[..encoded.len() - 1]);
+            assert!(res.is_err(), "{:?} decoded from a truncated payload", msg.msg_type());
+        }
+        let deposit = all_messages()[0].encode();
+        assert_eq!(
+            error_code(Message::decode(&deposit[..HEADER_LEN + 8])),
+            MsgCodecError::BodyTooShort.into()
+        );
+    }
+
+    #[test]
+    fn rejects_over_long_payloads() {
+        for msg in all_messages() {
+            let mut encoded = msg.encode();
+            encoded.push(0);
+            assert_eq!(
+                error_code(Message::decode(&encoded)),
+                MsgCodecError::InvalidLength.into(),
+                "{:?} accepted a trailing byte",
+                msg.msg_type()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_bad_header() {
+        let mut encoded = all_messages()[0].encode();
+        encoded[0] = MSG_VERSION + 1;
+        assert_eq!(error_code(Message::decode(&encoded)), MsgCodecError::UnsupportedVersion.into());
+        encoded[0] = MSG_VERSION;
+        encoded[1] = 0;
+        assert_eq!(error_code(Message::decode(&encoded)), MsgCodecError::UnknownMessageType.into());
+    }
+
+    #[test]
+    fn rejects_unknown_compose_action() {
+        let mut encoded = all_messages()[5].encode();
+        *encoded.last_mut().unwrap() = 9;
+        assert_eq!(
+            error_code(Message::decode(&encoded)),
+            MsgCodecError::UnknownComposeAction.into()
+        );
+    }
 
+    #[test]
+    fn compose_msg_round_trips() {
+        for action in
+            [ComposeAction::Allocate, ComposeAction::RegisterReferrer { referrer: [0x55; 20] }]
+        {
+            let compose = ComposeMsg {
+                amount: 7,
+                evm_address: EVM,
+                vault: VAULT,
+                cctp_nonce: NONCE,
+                src_eid: 40245,
+                action,
+            };
+            let encoded = compose.encode();
+            assert_eq!(ComposeMsg::decode(&encoded).unwrap(), compose);
 
-// Minimal compatibility encoder used by send/quote_send paths in the template.
-// For our deposit flow we won’t use this, but keep it to avoid breaking existing tasks.
-pub fn encode(s: &str) -> Vec<u8> {
-    encode_string(s)
+            let res = ComposeMsg::decode(&encoded[..encoded.len() - 1]);
+            assert!(res.is_err());
+            let mut long = encoded.clone();
+            long.push(0);
+            assert_eq!(error_code(ComposeMsg::decode(&long)), MsgCodecError::InvalidLength.into());
+        }
+    }
 }
@@ -0,0 +1,247 @@
+use crate::errors::MyOAppError;
+use crate::strategy::{self, Strategy};
+use crate::*;
+use oapp::endpoint_cpi::LzAccount;
+
+/// Number of accounts the Save deposit takes (see `deposit_accounts` for the order)
+pub const DEPOSIT_ACCOUNTS_LEN: usize = 12;
+/// Number of accounts the Save withdraw takes (see `withdraw_accounts` for the order)
+pub const WITHDRAW_ACCOUNTS_LEN: usize = 12;
+/// Save (SPL token-lending) instructions are tagged by a single byte rather than an Anchor
+/// discriminator; the tag is kept in the first byte of the config discriminators.
+pub const DEPOSIT_RESERVE_LIQUIDITY_TAG: u8 = 4;
+pub const REDEEM_RESERVE_COLLATERAL_TAG: u8 = 5;
+/// `RefreshReserve` tag. Deposits and redemptions require a reserve refreshed in the same slot,
+/// so both are preceded by this CPI.
+pub const REFRESH_RESERVE_TAG: u8 = 3;
+/// Byte offsets in the Save `Reserve` account: version(1) + last_update(9) + lending_market(32),
+/// then liquidity: mint(32) + decimals(1) + supply(32) + pyth(32) + switchboard(32)
+/// + available_amount(8) + borrowed_amount_wads(16) ...
+pub const RESERVE_AVAILABLE_AMOUNT_OFFSET: usize = 171;
+pub const RESERVE_BORROWED_AMOUNT_WADS_OFFSET: usize = 179;
+/// ... cumulative_borrow_rate_wads(16) + market_price(16), then collateral: mint(32) + mint_total_supply(8)
+pub const RESERVE_COLLATERAL_SUPPLY_OFFSET: usize = 259;
+/// `liquidity.accumulated_protocol_fees_wads`, after the collateral supply pubkey and the reserve config
+pub const RESERVE_PROTOCOL_FEES_WADS_OFFSET: usize = 373;
+/// Save decimals (`*_wads`) are scaled by 1e18
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+
+// Order of the Save accounts in `StrategyConfig.accounts` (USDC reserve on the current cluster).
+// `program_id` is the Save program and `share_mint` the reserve collateral (cToken) mint.
+pub const LENDING_MARKET: usize = 0;
+pub const LENDING_MARKET_AUTHORITY: usize = 1;
+pub const RESERVE: usize = 2;
+pub const RESERVE_LIQUIDITY_SUPPLY: usize = 3;
+pub const PYTH_ORACLE: usize = 4;
+pub const SWITCHBOARD_ORACLE: usize = 5;
+pub const CONFIG_ACCOUNTS_LEN: usize = 6;
+
+/// Save (formerly Solend) reserve adapter. Deposits mint reserve collateral (cTokens) to the Store
+/// cToken ATA; shares are cTokens. Withdrawals redeem the cTokens worth the requested USDC.
+pub struct Save;
+
+impl Strategy for Save {
+    fn config_accounts_len(&self) -> usize {
+        CONFIG_ACCOUNTS_LEN
+    }
+
+    fn deposit_accounts_len(&self) -> usize {
+        DEPOSIT_ACCOUNTS_LEN
+    }
+
+    fn withdraw_accounts_len(&self) -> usize {
+        WITHDRAW_ACCOUNTS_LEN
+    }
+
+    /// 0: sourceLiquidity (USDC ATA)
+    /// 1: destinationCollateral (cToken ATA)
+    /// 2: reserve
+    /// 3: reserveLiquiditySupply
+    /// 4: reserveCollateralMint
+    /// 5: lendingMarket
+    /// 6: lendingMarketAuthority
+    /// 7: userTransferAuthority (store PDA, signer via invoke_signed)
+    /// 8: tokenProgram
+    /// 9..=10: refresh_reserve oracles (pyth, switchboard)
+    /// 11: Save program
+    fn deposit_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        let c_token_ata = s.associated_token_address(store_key, &c.share_mint);
+        let mut accounts = vec![
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c_token_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(RESERVE), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(RESERVE_LIQUIDITY_SUPPLY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.share_mint, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LENDING_MARKET), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(LENDING_MARKET_AUTHORITY), is_signer: false, is_writable: false },
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+        ];
+        accounts.extend(oracle_accounts(c));
+        accounts.push(LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false });
+        accounts
+    }
+
+    /// 0: sourceCollateral (cToken ATA)
+    /// 1: destinationLiquidity (USDC ATA)
+    /// 2: reserve
+    /// 3: reserveCollateralMint
+    /// 4: reserveLiquiditySupply
+    /// 5: lendingMarket
+    /// 6: lendingMarketAuthority
+    /// 7: userTransferAuthority (store PDA, signer via invoke_signed)
+    /// 8: tokenProgram
+    /// 9..=10: refresh_reserve oracles (pyth, switchboard)
+    /// 11: Save program
+    fn withdraw_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount> {
+        let s = store;
+        let c = config;
+        let usdc_ata = s.associated_token_address(store_key, &s.usdc_mint);
+        let c_token_ata = s.associated_token_address(store_key, &c.share_mint);
+        let mut accounts = vec![
+            LzAccount { pubkey: c_token_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: usdc_ata, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(RESERVE), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.share_mint, is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(RESERVE_LIQUIDITY_SUPPLY), is_signer: false, is_writable: true },
+            LzAccount { pubkey: c.account(LENDING_MARKET), is_signer: false, is_writable: false },
+            LzAccount { pubkey: c.account(LENDING_MARKET_AUTHORITY), is_signer: false, is_writable: false },
+            LzAccount { pubkey: *store_key, is_signer: false, is_writable: false },
+            LzAccount { pubkey: s.token_program, is_signer: false, is_writable: false },
+        ];
+        accounts.extend(oracle_accounts(c));
+        accounts.push(LzAccount { pubkey: c.program_id, is_signer: false, is_writable: false });
+        accounts
+    }
+
+    /// Refreshes the reserve, then deposits from the Store USDC ATA; returns the cTokens minted,
+    /// measured on the Store cToken ATA (account 1) around the CPI.
+    fn deposit(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.deposit_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+        refresh_reserve(store, store_key, config, accounts)?;
+
+        let data = ix_data(config.deposit_discriminator[0], amount);
+        let shares_before = strategy::token_amount(store, &accounts[1])?;
+        strategy::invoke_with_metas(store, store_key, config.program_id, &expected[..9], accounts, data)?;
+        let shares_after = strategy::token_amount(store, &accounts[1])?;
+        Ok(shares_after.checked_sub(shares_before).ok_or(MyOAppError::Overflow)?)
+    }
+
+    /// Refreshes the reserve, then redeems the cTokens worth `amount` USDC (rounded up) into the
+    /// Store USDC ATA; returns the cTokens burned, measured on the Store cToken ATA (account 0).
+    fn withdraw(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64> {
+        let expected = self.withdraw_accounts(store, store_key, config);
+        strategy::verify_accounts(&expected, accounts)?;
+        refresh_reserve(store, store_key, config, accounts)?;
+
+        let collateral = collateral_for_liquidity(&accounts[2], amount)?;
+        let data = ix_data(config.withdraw_discriminator[0], collateral);
+        let shares_before = strategy::token_amount(store, &accounts[0])?;
+        strategy::invoke_with_metas(store, store_key, config.program_id, &expected[..9], accounts, data)?;
+        let shares_after = strategy::token_amount(store, &accounts[0])?;
+        Ok(shares_before.checked_sub(shares_after).ok_or(MyOAppError::Overflow)?)
+    }
+
+    fn price_account(&self, config: &StrategyConfig) -> Pubkey {
+        config.account(RESERVE)
+    }
+
+    /// Prices cTokens with the `Reserve` account's liquidity/collateral ratio, as of its last refresh.
+    fn shares_to_assets(&self, config: &StrategyConfig, price_account: &AccountInfo, shares: u64) -> Result<u64> {
+        require_keys_eq!(price_account.key(), config.account(RESERVE), MyOAppError::InvalidAccount);
+        require_keys_eq!(*price_account.owner, config.program_id, MyOAppError::InvalidAccount);
+        let (total_liquidity, collateral_supply) = reserve_exchange(price_account)?;
+        if collateral_supply == 0 || total_liquidity == 0 {
+            return Ok(shares);
+        }
+        let assets = (shares as u128) * total_liquidity / collateral_supply;
+        Ok(u64::try_from(assets).map_err(|_| MyOAppError::Overflow)?)
+    }
+}
+
+/// `tag || amount (u64 LE)`
+fn ix_data(tag: u8, amount: u64) -> Vec<u8> {
+    let mut data = vec![tag];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// `RefreshReserve` oracle accounts, read-only.
+fn oracle_accounts(config: &StrategyConfig) -> Vec<LzAccount> {
+    [PYTH_ORACLE, SWITCHBOARD_ORACLE]
+        .iter()
+        .map(|&i| LzAccount { pubkey: config.account(i), is_signer: false, is_writable: false })
+        .collect()
+}
+
+/// CPIs `RefreshReserve` (reserve, oracles); `accounts` must hold all of them.
+fn refresh_reserve(
+    store: &Store,
+    store_key: &Pubkey,
+    config: &StrategyConfig,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let mut metas =
+        vec![LzAccount { pubkey: config.account(RESERVE), is_signer: false, is_writable: true }];
+    metas.extend(oracle_accounts(config));
+    strategy::invoke_with_metas(
+        store,
+        store_key,
+        config.program_id,
+        &metas,
+        accounts,
+        vec![REFRESH_RESERVE_TAG],
+    )
+}
+
+/// cTokens to redeem for `amount` USDC, rounded up so the redemption covers `amount`.
+fn collateral_for_liquidity(reserve: &AccountInfo, amount: u64) -> Result<u64> {
+    let (total_liquidity, collateral_supply) = reserve_exchange(reserve)?;
+    if collateral_supply == 0 || total_liquidity == 0 {
+        return Ok(amount);
+    }
+    let collateral = ((amount as u128) * collateral_supply).div_ceil(total_liquidity);
+    Ok(u64::try_from(collateral).map_err(|_| MyOAppError::Overflow)?)
+}
+
+/// Reads (total liquidity in USDC base units, cToken supply) from a Save `Reserve` account.
+/// Total liquidity is available + borrowed - protocol fees, with the wads floored.
+fn reserve_exchange(reserve: &AccountInfo) -> Result<(u128, u128)> {
+    let data = reserve.try_borrow_data()?;
+    require!(data.len() >= RESERVE_PROTOCOL_FEES_WADS_OFFSET + 16, MyOAppError::InvalidAccount);
+    let read_u64 = |offset: usize| {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&data[offset..offset + 8]);
+        u64::from_le_bytes(buf) as u128
+    };
+    let read_u128 = |offset: usize| {
+        let mut buf = [0u8; 16];
+        buf.copy_from_slice(&data[offset..offset + 16]);
+        u128::from_le_bytes(buf)
+    };
+
+    let total_wads = read_u64(RESERVE_AVAILABLE_AMOUNT_OFFSET)
+        .checked_mul(WAD)
+        .and_then(|v| v.checked_add(read_u128(RESERVE_BORROWED_AMOUNT_WADS_OFFSET)))
+        .and_then(|v| v.checked_sub(read_u128(RESERVE_PROTOCOL_FEES_WADS_OFFSET)))
+        .ok_or(MyOAppError::Overflow)?;
+    Ok((total_wads / WAD, read_u64(RESERVE_COLLATERAL_SUPPLY_OFFSET)))
+}
@@ -0,0 +1,19 @@
+use crate::cctp::CctpNonce;
+use crate::*;
+
+/// Marks a CCTP nonce as spent by an LZ deposit so one mint can never back two credits.
+#[account]
+#[derive(InitSpace)]
+pub struct ConsumedCctpNonce {
+    pub cctp_nonce: CctpNonce,     // CCTP nonce from the deposit payload
+    pub guid: [u8; 32],            // LZ message that consumed it
+    pub evm_address: [u8; 20],     // User credited with the mint
+    pub amount: u64,               // Amount credited (base units)
+    pub bump: u8,
+    pub version: u8,               // Layout version, see `ConsumedCctpNonce::VERSION`
+}
+
+impl ConsumedCctpNonce {
+    pub const SIZE: usize = 8 + ConsumedCctpNonce::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
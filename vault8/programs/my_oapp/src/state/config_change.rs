@@ -0,0 +1,64 @@
+use crate::errors::MyOAppError;
+use crate::strategy::StrategyKind;
+use crate::*;
+
+/// Config change queued by `queue_config_change`, executable from `eta` on. Keyed by its id.
+#[account]
+pub struct ConfigChange {
+    pub id: u64,                   // Store.config_change_count when queued
+    pub eta: i64,                  // Earliest execution time (Unix timestamp)
+    pub update: ConfigUpdate,      // Change applied on execution
+    pub bump: u8,
+    pub version: u8,               // Layout version, see `ConfigChange::VERSION`
+}
+
+impl ConfigChange {
+    pub const VERSION: u8 = 1;
+
+    /// Sized to the queued update, whose enforced options and account lists vary in length
+    pub fn space(update: &ConfigUpdate) -> usize {
+        8 + 8 + 8 + update.try_to_vec().map_or(0, |data| data.len()) + 1 + 1
+    }
+}
+
+/// Changes that must go through the timelock once `Store.config_delay` is set
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum ConfigUpdate {
+    JlConfig(Box<SetJlConfigParams>),
+    PeerConfig(SetPeerConfigParams),
+    StrategyConfig(SetStrategyConfigParams),
+    ConfigDelay(i64),
+    KaminoConfig(SetKaminoConfigParams),
+    MarginfiConfig(SetMarginfiConfigParams),
+    SaveConfig(SetSaveConfigParams),
+}
+
+impl ConfigUpdate {
+    /// Remote EID of a peer update (seeds of the peer and nonce PDAs in `execute_config_change`)
+    pub fn remote_eid(&self) -> Result<u32> {
+        match self {
+            ConfigUpdate::PeerConfig(params) => Ok(params.remote_eid),
+            _ => err!(MyOAppError::InvalidAccount),
+        }
+    }
+
+    /// Peer address set by a peer update
+    pub fn peer_address(&self) -> Result<[u8; 32]> {
+        match self {
+            ConfigUpdate::PeerConfig(params) => params.config.peer_address(),
+            _ => err!(MyOAppError::InvalidAccount),
+        }
+    }
+
+    /// Seed of the strategy config PDA written by a JL, strategy or venue update
+    pub fn strategy_seed(&self) -> Result<[u8; 1]> {
+        match self {
+            ConfigUpdate::JlConfig(_) => Ok(StrategyKind::JupiterLend.seed()),
+            ConfigUpdate::StrategyConfig(params) => Ok(params.kind.seed()),
+            ConfigUpdate::KaminoConfig(_) => Ok(StrategyKind::Kamino.seed()),
+            ConfigUpdate::MarginfiConfig(_) => Ok(StrategyKind::MarginFi.seed()),
+            ConfigUpdate::SaveConfig(_) => Ok(StrategyKind::Save.seed()),
+            _ => err!(MyOAppError::InvalidAccount),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::*;
+
+/// Per-GUID record of a deposit received by `lz_receive`, used to ACK it back to the EVM peer.
+/// `credited` flips once the deposit is credited (immediately, or by `settle_pending_deposit`);
+/// `send_deposit_ack` then sends the ACK once and sets `acked`.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositReceipt {
+    pub guid: [u8; 32],            // LayerZero message GUID of the deposit
+    pub evm_address: [u8; 20],     // User's EVM address
+    pub amount: u64,               // Amount deposited (base units)
+    pub src_eid: u32,              // Endpoint ID the deposit came from (ACK destination)
+    pub credited: bool,            // Deposit credited to UserBalance
+    pub acked: bool,               // ACK sent to the EVM peer
+    pub bump: u8,
+    pub version: u8,               // Layout version, see `DepositReceipt::VERSION`
+}
+
+impl DepositReceipt {
+    pub const SIZE: usize = 8 + DepositReceipt::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
@@ -1,5 +1,23 @@
 pub mod store;
 mod peer_config;
+mod cctp_nonce;
+mod pending_deposit;
+mod deposit_receipt;
+mod nonce;
+mod referral;
+mod strategy_config;
+mod roles;
+mod config_change;
+mod vault_position;
 
 pub use store::*; 
 pub use peer_config::*;
+pub use cctp_nonce::*;
+pub use pending_deposit::*;
+pub use deposit_receipt::*;
+pub use nonce::*;
+pub use referral::*;
+pub use strategy_config::*;
+pub use roles::*;
+pub use config_change::*;
+pub use vault_position::*;
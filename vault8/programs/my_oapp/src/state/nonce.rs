@@ -0,0 +1,31 @@
+use crate::*;
+use crate::errors::MyOAppError;
+
+/// Inbound nonce tracker per (receiver, src_eid, sender) path, read by the Executor through
+/// `next_nonce` to deliver messages in order.
+#[account]
+#[derive(InitSpace)]
+pub struct Nonce {
+    pub bump: u8,
+    pub max_received_nonce: u64,   // Highest inbound nonce accepted on this path
+    pub version: u8,               // Layout version, see `Nonce::VERSION`
+    pub reserved: [u8; 32],        // Zeroed space for future fields
+}
+
+impl Nonce {
+    pub const SIZE: usize = 8 + Nonce::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Accepts `nonce` for this path, requiring it to be the next one when ordering is enforced.
+    pub fn accept(&mut self, nonce: u64, ordered: bool) -> Result<()> {
+        let current_nonce = self.max_received_nonce;
+        if ordered {
+            require!(nonce == current_nonce + 1, MyOAppError::InvalidNonce);
+        }
+        // update the max nonce anyway. once the ordered mode is turned on, missing early nonces will be rejected
+        if nonce > current_nonce {
+            self.max_received_nonce = nonce;
+        }
+        Ok(())
+    }
+}
@@ -1,42 +1,226 @@
+use crate::errors::MyOAppError;
 use crate::*;
 
-pub const ENFORCED_OPTIONS_SEND_MAX_LEN: usize = 512;
-pub const ENFORCED_OPTIONS_SEND_AND_CALL_MAX_LEN: usize = 1024;
+/// Message types a peer can have enforced options for
+pub const MAX_ENFORCED_OPTIONS: usize = 8;
+pub const ENFORCED_OPTIONS_MAX_LEN: usize = 512;
 
 #[account]
+#[derive(InitSpace)]
 pub struct PeerConfig {
     pub peer_address: [u8; 32],
     pub enforced_options: EnforcedOptions,
     pub bump: u8,
+    // Set by the pauser: reject messages from / sends to this peer
+    pub inbound_paused: bool,
+    pub outbound_paused: bool,
+    // Rolling-window limit on deposit amounts credited from this peer
+    pub inbound_rate_limit: RateLimit,
+    // Unix timestamp of the last `report_nav` sent to this peer
+    pub last_nav_report: i64,
+    pub version: u8,               // Layout version, see `PeerConfig::VERSION`
+    pub reserved: [u8; 56],        // Zeroed space for future fields
 }
 
 impl PeerConfig {
-    pub const SIZE: usize = 8 + std::mem::size_of::<Self>();
+    /// Largest the account can grow to, with options at the max length for every message type
+    pub const SIZE: usize = 8 + PeerConfig::INIT_SPACE;
+    /// Size at creation, before any enforced options are set; `set_peer_config` reallocs from there
+    pub const MIN_SIZE: usize = PeerConfig::SIZE - EnforcedOptions::INIT_SPACE + 4;
+    pub const VERSION: u8 = 1;
+    /// Size of the original layout (`8 + size_of::<PeerConfig>()`), upgraded by `migrate_peer_config`
+    pub const V0_SIZE: usize = 96;
+
+    /// Size the account needs for its current contents
+    pub fn space(&self) -> Result<usize> {
+        Ok(8 + self.try_to_vec()?.len())
+    }
 }
 
+/// Rolling-window limit on amounts received from a peer. The amount in flight decays linearly over
+/// `window` seconds, so at most `limit` arrives in any window. A zero `limit` disables the check.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct RateLimit {
+    pub limit: u64,                // Max amount per window (base units)
+    pub window: u64,               // Window length (seconds)
+    pub amount_in_flight: u64,     // Amount counted against the current window
+    pub last_updated: i64,         // Unix timestamp of the last refresh
+}
+
+impl RateLimit {
+    /// Changes the limit, keeping what is already in flight (decayed under the previous one).
+    pub fn set(&mut self, limit: u64, window: u64, now: i64) -> Result<()> {
+        require!(limit == 0 || window > 0, MyOAppError::InvalidRateLimit);
+        self.amount_in_flight = self.in_flight_at(now);
+        self.last_updated = now;
+        self.limit = limit;
+        self.window = window;
+        Ok(())
+    }
+
+    /// Whether counting `amount` against the window at `now` would exceed the limit.
+    pub fn exceeds(&self, amount: u64, now: i64) -> Result<bool> {
+        if self.limit == 0 {
+            return Ok(false);
+        }
+        let in_flight = self.in_flight_at(now).checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        Ok(in_flight > self.limit)
+    }
+
+    /// Counts `amount` against the window, failing if it would exceed the limit.
+    pub fn consume(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.limit == 0 {
+            return Ok(());
+        }
+        let in_flight = self.in_flight_at(now).checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        require!(in_flight <= self.limit, MyOAppError::InboundRateLimitExceeded);
+        self.amount_in_flight = in_flight;
+        self.last_updated = now;
+        Ok(())
+    }
+
+    fn in_flight_at(&self, now: i64) -> u64 {
+        if self.window == 0 {
+            return 0;
+        }
+        let elapsed = now.saturating_sub(self.last_updated).max(0) as u128;
+        let decay = elapsed * (self.limit as u128) / (self.window as u128);
+        (self.amount_in_flight as u128).saturating_sub(decay) as u64
+    }
+}
+
+/// Enforced options per outbound message type, mirroring the EVM `enforcedOptions[eid][msgType]`
 #[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct EnforcedOptions {
-    #[max_len(ENFORCED_OPTIONS_SEND_MAX_LEN)]
-    pub send: Vec<u8>,
-    #[max_len(ENFORCED_OPTIONS_SEND_AND_CALL_MAX_LEN)]
-    pub send_and_call: Vec<u8>,
+    #[max_len(MAX_ENFORCED_OPTIONS)]
+    pub entries: Vec<MsgTypeOptions>,
+}
+
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct MsgTypeOptions {
+    pub msg_type: u8,              // `msg_codec::MsgType` value
+    #[max_len(ENFORCED_OPTIONS_MAX_LEN)]
+    pub options: Vec<u8>,          // Type 3 options
 }
 
 impl EnforcedOptions {
-    pub fn get_enforced_options(&self, composed_msg: &Option<Vec<u8>>) -> Vec<u8> {
-        if composed_msg.is_none() {
-            self.send.clone()
-        } else {
-            self.send_and_call.clone()
+    /// Enforced options for `msg_type` (empty when none are set)
+    pub fn get(&self, msg_type: u8) -> Vec<u8> {
+        self.entries
+            .iter()
+            .find(|entry| entry.msg_type == msg_type)
+            .map(|entry| entry.options.clone())
+            .unwrap_or_default()
+    }
+
+    /// Sets the options for `msg_type`; empty options remove the entry.
+    pub fn set(&mut self, msg_type: u8, options: Vec<u8>) -> Result<()> {
+        require!(options.len() <= ENFORCED_OPTIONS_MAX_LEN, MyOAppError::InvalidEnforcedOptions);
+        self.entries.retain(|entry| entry.msg_type != msg_type);
+        if !options.is_empty() {
+            oapp::options::assert_type_3(&options)?;
+            require!(self.entries.len() < MAX_ENFORCED_OPTIONS, MyOAppError::InvalidEnforcedOptions);
+            self.entries.push(MsgTypeOptions { msg_type, options });
+        }
+        Ok(())
+    }
+
+    pub fn combine_options(&self, msg_type: u8, extra_options: &Vec<u8>) -> Result<Vec<u8>> {
+        oapp::options::combine_options(self.get(msg_type), extra_options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limit(limit: u64, window: u64) -> RateLimit {
+        let mut rate_limit = RateLimit::default();
+        rate_limit.set(limit, window, 1_000).unwrap();
+        rate_limit
+    }
+
+    #[test]
+    fn consume_within_limit() {
+        let mut rate_limit = rate_limit(100, 10);
+        rate_limit.consume(60, 1_000).unwrap();
+        rate_limit.consume(40, 1_000).unwrap();
+        assert!(rate_limit.consume(1, 1_000).is_err());
+        // A rejected amount is not counted
+        assert_eq!(rate_limit.amount_in_flight, 100);
+    }
+
+    #[test]
+    fn consume_decays_linearly_over_window() {
+        let mut rate_limit = rate_limit(100, 10);
+        rate_limit.consume(100, 1_000).unwrap();
+        // 3s of a 10s window frees 30
+        assert!(rate_limit.consume(31, 1_003).is_err());
+        rate_limit.consume(30, 1_003).unwrap();
+        assert_eq!(rate_limit.amount_in_flight, 100);
+        // A full window frees everything, but never below zero
+        rate_limit.consume(100, 1_013).unwrap();
+        rate_limit.consume(0, 1_100).unwrap();
+        assert_eq!(rate_limit.amount_in_flight, 0);
+    }
+
+    #[test]
+    fn exceeds_checks_without_counting() {
+        let mut rate_limit = rate_limit(100, 10);
+        rate_limit.consume(70, 1_000).unwrap();
+        assert!(!rate_limit.exceeds(30, 1_000).unwrap());
+        assert!(rate_limit.exceeds(31, 1_000).unwrap());
+        assert_eq!(rate_limit.amount_in_flight, 70);
+        assert!(!RateLimit::default().exceeds(u64::MAX, 1_000).unwrap());
+    }
+
+    #[test]
+    fn zero_limit_disables_check() {
+        let mut rate_limit = RateLimit::default();
+        rate_limit.consume(u64::MAX, 1_000).unwrap();
+        assert_eq!(rate_limit.amount_in_flight, 0);
+    }
+
+    #[test]
+    fn set_requires_window_and_keeps_in_flight() {
+        assert!(RateLimit::default().set(100, 0, 1_000).is_err());
+        let mut rate_limit = rate_limit(100, 10);
+        rate_limit.consume(100, 1_000).unwrap();
+        rate_limit.set(200, 10, 1_005).unwrap();
+        assert_eq!(rate_limit.amount_in_flight, 50);
+    }
+
+    fn options(gas: u8) -> Vec<u8> {
+        vec![0, 3, 1, 0, 17, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, gas]
+    }
+
+    #[test]
+    fn enforced_options_replace_and_remove() {
+        let mut enforced = EnforcedOptions::default();
+        enforced.set(7, options(1)).unwrap();
+        enforced.set(7, options(2)).unwrap();
+        assert_eq!(enforced.entries.len(), 1);
+        assert_eq!(enforced.get(7), options(2));
+        enforced.set(7, vec![]).unwrap();
+        assert!(enforced.entries.is_empty());
+        assert!(enforced.get(7).is_empty());
+    }
+
+    #[test]
+    fn enforced_options_max_entries() {
+        let mut enforced = EnforcedOptions::default();
+        for msg_type in 0..MAX_ENFORCED_OPTIONS as u8 {
+            enforced.set(msg_type, options(msg_type)).unwrap();
         }
+        assert!(enforced.set(MAX_ENFORCED_OPTIONS as u8, options(0)).is_err());
+        // Replacing an existing type still fits
+        enforced.set(0, options(9)).unwrap();
+        assert_eq!(enforced.entries.len(), MAX_ENFORCED_OPTIONS);
     }
 
-    pub fn combine_options(
-        &self,
-        compose_msg: &Option<Vec<u8>>,
-        extra_options: &Vec<u8>,
-    ) -> Result<Vec<u8>> {
-        let enforced_options = self.get_enforced_options(compose_msg);
-        oapp::options::combine_options(enforced_options, extra_options)
+    #[test]
+    fn enforced_options_max_len() {
+        let mut enforced = EnforcedOptions::default();
+        assert!(enforced.set(7, vec![0; ENFORCED_OPTIONS_MAX_LEN + 1]).is_err());
     }
 }
@@ -0,0 +1,98 @@
+use crate::cctp::CctpNonce;
+use crate::*;
+
+/// Deposit whose LZ message landed before the CCTP mint reached the Store USDC ATA, or whose
+/// minted USDC is held over a cap (`funded`, counted in `Store.parked_assets`).
+/// Keyed by LZ GUID; settled (and closed) by the permissionless `settle_pending_deposit` crank,
+/// which also queues the compose of a parked `DepositAndCall`.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingDeposit {
+    pub guid: [u8; 32],            // LayerZero message GUID
+    pub evm_address: [u8; 20],     // User's EVM address
+    pub vault: [u8; 20],           // EVM vault whose position is credited
+    pub amount: u64,               // Amount to credit (base units)
+    pub cctp_nonce: CctpNonce,     // CCTP nonce of the backing mint
+    pub src_eid: u32,              // Source endpoint ID
+    pub rent_payer: Pubkey,        // Refunded when the entry is settled
+    pub created_at: i64,           // Unix timestamp of lz_receive
+    pub funded: bool,              // Minted USDC already held for it in `Store.parked_assets`
+    #[max_len(msg_codec::COMPOSE_ACTION_MAX_LEN)]
+    pub compose_action: Vec<u8>,   // Encoded compose action of a `DepositAndCall`, empty otherwise
+    pub bump: u8,
+    pub version: u8,               // Layout version, see `PendingDeposit::VERSION`
+}
+
+impl PendingDeposit {
+    pub const SIZE: usize = 8 + PendingDeposit::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Compose payload `lz_receive` would have queued had the deposit been credited, or `None`
+    /// for a plain deposit
+    pub fn compose_msg(&self) -> Result<Option<msg_codec::ComposeMsg>> {
+        if self.compose_action.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(msg_codec::ComposeMsg {
+            amount: self.amount,
+            evm_address: self.evm_address,
+            vault: self.vault,
+            cctp_nonce: self.cctp_nonce,
+            src_eid: self.src_eid,
+            action: msg_codec::ComposeAction::decode(&self.compose_action)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(compose_action: Vec<u8>) -> PendingDeposit {
+        PendingDeposit {
+            guid: [1u8; 32],
+            evm_address: [0x11; 20],
+            vault: [0x22; 20],
+            amount: 1_000_000,
+            cctp_nonce: CctpNonce { source_domain: 0, nonce: [0x33; 32] },
+            src_eid: 30101,
+            rent_payer: Pubkey::new_unique(),
+            created_at: 1_000,
+            funded: false,
+            compose_action,
+            bump: 255,
+            version: PendingDeposit::VERSION,
+        }
+    }
+
+    #[test]
+    fn parked_deposit_and_call_keeps_its_compose() {
+        let action = msg_codec::ComposeAction::RegisterReferrer { referrer: [0x55; 20] };
+        let mut encoded = Vec::new();
+        action.encode_into(&mut encoded);
+        let pending = pending(encoded);
+
+        // The longest action fits the account
+        let mut data = vec![0u8; PendingDeposit::SIZE];
+        pending.try_serialize(&mut &mut data[..]).unwrap();
+        let pending = PendingDeposit::try_deserialize(&mut &data[..]).unwrap();
+
+        let compose = pending.compose_msg().unwrap().unwrap();
+        assert_eq!(
+            compose,
+            msg_codec::ComposeMsg {
+                amount: 1_000_000,
+                evm_address: [0x11; 20],
+                vault: [0x22; 20],
+                cctp_nonce: pending.cctp_nonce,
+                src_eid: 30101,
+                action,
+            }
+        );
+    }
+
+    #[test]
+    fn parked_deposit_has_no_compose() {
+        assert!(pending(Vec::new()).compose_msg().unwrap().is_none());
+    }
+}
@@ -0,0 +1,16 @@
+use crate::*;
+
+/// Referrer registered for an EVM user through a composed deposit. Keyed by the user's EVM address.
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub evm_address: [u8; 20],     // User's EVM address
+    pub referrer: [u8; 20],        // Referrer's EVM address
+    pub bump: u8,
+    pub version: u8,               // Layout version, see `Referral::VERSION`
+}
+
+impl Referral {
+    pub const SIZE: usize = 8 + Referral::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
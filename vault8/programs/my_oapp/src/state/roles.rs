@@ -0,0 +1,47 @@
+use crate::*;
+
+/// Operational keys, each gating a class of instructions. `Store.admin` owns this account: it
+/// assigns every role with `set_role` and is the only key that can transfer itself.
+#[account]
+#[derive(InitSpace)]
+pub struct Roles {
+    pub config_admin: Pubkey,      // Peer, strategy, ALT and nonce configuration
+    pub strategy_keeper: Pubkey,   // Moves funds between strategies
+    pub pauser: Pubkey,            // Pauses and unpauses messaging
+    pub fee_collector: Pubkey,     // Receives protocol fees
+    pub bump: u8,
+    pub version: u8,               // Layout version, see `Roles::VERSION`
+    pub reserved: [u8; 64],        // Zeroed space for future fields
+}
+
+impl Roles {
+    pub const SIZE: usize = 8 + Roles::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Assigns every role to `admin`, as at `init_store`
+    pub fn init(&mut self, admin: Pubkey, bump: u8) {
+        self.config_admin = admin;
+        self.strategy_keeper = admin;
+        self.pauser = admin;
+        self.fee_collector = admin;
+        self.bump = bump;
+        self.version = Roles::VERSION;
+    }
+
+    pub fn set(&mut self, role: Role, account: Pubkey) {
+        match role {
+            Role::ConfigAdmin => self.config_admin = account,
+            Role::StrategyKeeper => self.strategy_keeper = account,
+            Role::Pauser => self.pauser = account,
+            Role::FeeCollector => self.fee_collector = account,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum Role {
+    ConfigAdmin,
+    StrategyKeeper,
+    Pauser,
+    FeeCollector,
+}
@@ -1,34 +1,179 @@
+use crate::errors::MyOAppError;
+use crate::strategy::{StrategyKind, BPS_DENOMINATOR, STRATEGY_COUNT};
 use crate::*;
 
 #[account]
+#[derive(InitSpace)]
 pub struct Store {
     pub admin: Pubkey, // This is required and should be consistent.
+    // Proposed by the admin, takes over on `accept_admin` (default when none)
+    pub pending_admin: Pubkey,
     pub bump: u8, // This is required and should be consistent.
     pub endpoint_program: Pubkey, // This is required and should be consistent.
-    // Jupiter Lend (devnet) configuration and SPL programs
+    // USDC and SPL programs shared by every strategy
     pub usdc_mint: Pubkey,
     pub token_program: Pubkey,
     pub associated_token_program: Pubkey,
     pub system_program: Pubkey,
-    // Jupiter Lend Earn-specific accounts for the USDC pool on current cluster
-    pub jl_lending_program: Pubkey,
-    pub jl_liquidity_program: Pubkey,
-    pub jl_lending_admin: Pubkey,
-    pub jl_lending: Pubkey,
-    pub jl_f_token_mint: Pubkey,
-    pub jl_supply_token_reserves_liquidity: Pubkey,
-    pub jl_lending_supply_position_on_liquidity: Pubkey,
-    pub jl_rate_model: Pubkey,
-    pub jl_vault: Pubkey,
-    pub jl_liquidity: Pubkey,
-    pub jl_rewards_rate_model: Pubkey,
+    // Weight and venue position per strategy, indexed by StrategyKind
+    pub allocations: [StrategyAllocation; STRATEGY_COUNT],
+    // Sum of every UserBalance.shares (vault shares over the idle buffer and the strategies)
+    pub total_shares: u64,
+    // Target share of total assets kept uninvested in the Store USDC ATA (bps)
+    pub idle_target_bps: u16,
+    // USDC in the Store USDC ATA backing vault shares (excludes parked deposits)
+    pub idle_assets: u64,
+    // USDC in the Store USDC ATA held for funded deposits parked over a cap
+    pub parked_assets: u64,
+    // Enforce ordered execution of inbound messages per (src_eid, sender) path
+    pub ordered_nonce: bool,
+    // Circuit breaker set by the pauser: stops messaging and fund movements into strategies
+    pub paused: bool,
+    // Cap on each UserBalance.total_deposited (0 = none)
+    pub user_deposit_cap: u64,
+    // Cap on total assets after a deposit (0 = none)
+    pub tvl_cap: u64,
+    // Delay (seconds) before a queued config change can execute; direct setters only work at 0
+    pub config_delay: i64,
+    // Id of the next queued config change
+    pub config_change_count: u64,
+    // Minimum seconds between two `report_nav` sends to the same peer (0 = no limit)
+    pub nav_report_interval: i64,
+    // Layout version, see `Store::VERSION`
+    pub version: u8,
+    // Vault shares `migrate_store` set aside for UserBalances under the old seeds, not yet migrated
+    pub legacy_shares: u64,
+    // Sum of total_deposited over the UserBalances under the old seeds not yet migrated
+    pub legacy_deposits: u64,
+    // Zeroed space for future fields; new fields go before it and shrink it
+    pub reserved: [u8; 96],
 }
 
 impl Store {
-    // Layout: discriminator(8) + admin(32) + bump(1) + endpoint_program(32)
-    // + 4 x Pubkey (usdc_mint, token_program, associated_token_program, system_program)
-    // + 11 x Pubkey (Jupiter Lend config)
-    pub const SIZE: usize = 8 + 32 + 1 + 32 + (4 * 32) + (11 * 32);
+    pub const SIZE: usize = 8 + Store::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+    /// Size of the original layout (admin, bump, endpoint, SPL programs and 11 Jupiter Lend
+    /// pubkeys), upgraded by `migrate_store`
+    pub const V0_SIZE: usize = 8 + 32 + 1 + 32 + (4 * 32) + (11 * 32);
+
+    /// Official ATA derivation: seeds [owner, token_program, mint] under the ATA program
+    pub fn associated_token_address(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let seeds: &[&[u8]] = &[&owner.to_bytes(), &self.token_program.to_bytes(), &mint.to_bytes()];
+        Pubkey::find_program_address(seeds, &self.associated_token_program).0
+    }
+
+    pub fn allocation(&self, kind: StrategyKind) -> &StrategyAllocation {
+        &self.allocations[kind.index()]
+    }
+
+    pub fn allocation_mut(&mut self, kind: StrategyKind) -> &mut StrategyAllocation {
+        &mut self.allocations[kind.index()]
+    }
+
+    /// Strategies with a weight or venue shares still held, in `StrategyKind` order. These are the
+    /// legs `lz_receive` resolves for every deposit and withdrawal.
+    pub fn allocated_strategies(&self) -> Vec<StrategyKind> {
+        StrategyKind::ALL
+            .into_iter()
+            .filter(|kind| {
+                let allocation = self.allocation(*kind);
+                allocation.weight_bps > 0 || allocation.shares > 0
+            })
+            .collect()
+    }
+
+    /// USDC in the Store USDC ATA (holding `ata_amount`) that neither backs vault shares nor is
+    /// held for a parked deposit: what a newly minted deposit can be funded from.
+    pub fn unallocated_usdc(&self, ata_amount: u64) -> u64 {
+        ata_amount.saturating_sub(self.idle_assets.saturating_add(self.parked_assets))
+    }
+
+    /// Idle buffer the Store aims to hold when the vault is worth `total_assets`.
+    pub fn idle_target(&self, total_assets: u64) -> u64 {
+        ((total_assets as u128) * (self.idle_target_bps as u128) / (BPS_DENOMINATOR as u128)) as u64
+    }
+
+    /// Whether a deposit of `amount` would take a user's cumulative deposits past the cap.
+    pub fn exceeds_user_deposit_cap(&self, total_deposited: u64, amount: u64) -> Result<bool> {
+        let total = total_deposited.checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        Ok(self.user_deposit_cap != 0 && total > self.user_deposit_cap)
+    }
+
+    /// Whether a deposit of `amount` would take total assets past the TVL cap.
+    pub fn exceeds_tvl_cap(&self, total_assets: u64, amount: u64) -> Result<bool> {
+        let total = total_assets.checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        Ok(self.tvl_cap != 0 && total > self.tvl_cap)
+    }
+
+    /// Rejects a deposit of `amount` that would take a user's cumulative deposits past the cap.
+    pub fn check_user_deposit_cap(&self, total_deposited: u64, amount: u64) -> Result<()> {
+        require!(
+            !self.exceeds_user_deposit_cap(total_deposited, amount)?,
+            MyOAppError::UserDepositCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Rejects a deposit of `amount` that would take total assets past the TVL cap.
+    pub fn check_tvl_cap(&self, total_assets: u64, amount: u64) -> Result<()> {
+        require!(!self.exceeds_tvl_cap(total_assets, amount)?, MyOAppError::TvlCapExceeded);
+        Ok(())
+    }
+
+    /// Vault shares `amount` USDC is worth while the strategies hold `total_assets` (rounded down).
+    /// The first deposit mints 1:1.
+    pub fn shares_for_deposit(&self, amount: u64, total_assets: u64) -> Result<u64> {
+        if self.total_shares == 0 || total_assets == 0 {
+            return Ok(amount);
+        }
+        let shares = (amount as u128) * (self.total_shares as u128) / (total_assets as u128);
+        Ok(u64::try_from(shares).map_err(|_| MyOAppError::Overflow)?)
+    }
+
+    /// Vault shares to burn for withdrawing `amount` USDC out of `total_assets`, rounded up so
+    /// remaining holders never pay for the rounding.
+    pub fn shares_for_withdrawal(&self, amount: u64, total_assets: u64) -> Result<u64> {
+        require!(amount > 0, MyOAppError::ZeroAmount);
+        require!(amount <= total_assets, MyOAppError::InsufficientStoreBalance);
+        let numerator = (amount as u128) * (self.total_shares as u128);
+        let shares = numerator.div_ceil(total_assets as u128);
+        Ok(u64::try_from(shares).map_err(|_| MyOAppError::Overflow)?)
+    }
+
+    /// Takes the vault shares owed to a legacy UserBalance that deposited `deposited`: its pro rata
+    /// part of the legacy shares left. The last record migrated takes what is left, rounding
+    /// included, so the legacy shares are handed out in full.
+    pub fn take_legacy_shares(&mut self, deposited: u64) -> Result<u64> {
+        require!(deposited <= self.legacy_deposits, MyOAppError::LegacyDepositsExceeded);
+        let shares = if deposited == self.legacy_deposits {
+            self.legacy_shares
+        } else {
+            ((deposited as u128) * (self.legacy_shares as u128) / (self.legacy_deposits as u128))
+                as u64
+        };
+        self.legacy_shares -= shares;
+        self.legacy_deposits -= deposited;
+        Ok(shares)
+    }
+
+    /// Adds vault shares credited to a user to the aggregate.
+    pub fn credit_shares(&mut self, shares: u64) -> Result<()> {
+        self.total_shares = self.total_shares.checked_add(shares).ok_or(MyOAppError::Overflow)?;
+        Ok(())
+    }
+
+    /// Removes vault shares debited from a user from the aggregate.
+    pub fn debit_shares(&mut self, shares: u64) -> Result<()> {
+        self.total_shares = self.total_shares.checked_sub(shares).ok_or(MyOAppError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Row of the Store allocation table
+#[derive(Clone, Copy, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct StrategyAllocation {
+    pub weight_bps: u16, // Share of each deposit routed to the strategy (bps)
+    pub shares: u64,     // Venue shares the Store holds in the strategy
 }
 
 /// LzReceiveTypesAccounts for V2 - REQUIRED by Executor
@@ -44,21 +189,153 @@ impl LzReceiveTypesAccounts {
     pub const SIZE: usize = 8 + LzReceiveTypesAccounts::INIT_SPACE;
 }
 
-/// UserBalance tracks cumulative deposits/withdrawals for each EVM user
+/// LzComposeTypesAccounts lists the accounts `lz_compose_types` needs - REQUIRED by Executor
+#[account]
+#[derive(InitSpace)]
+pub struct LzComposeTypesAccounts {
+    pub store: Pubkey, // OApp address
+    pub bump: u8,
+}
+
+impl LzComposeTypesAccounts {
+    pub const SIZE: usize = 8 + LzComposeTypesAccounts::INIT_SPACE;
+}
+
+/// UserBalance tracks cumulative deposits/withdrawals for each EVM user on each source chain,
+/// seeds [USER_BALANCE_SEED, src_eid, evm_address]
 #[account]
 #[derive(InitSpace)]
 pub struct UserBalance {
     pub evm_address: [u8; 20],     // 20-byte EVM address
     pub total_deposited: u64,      // Cumulative deposits (base units)
-    pub total_withdrawn: u64,      // Cumulative withdrawals (future)
-    pub ftoken_balance: u64,       // Current fToken balance from JL
+    pub total_withdrawn: u64,      // Cumulative withdrawals (base units)
+    pub shares: u64,               // Vault shares credited to this user
     pub last_updated: i64,         // Last activity timestamp
     pub deposit_count: u32,        // Number of deposits
     pub bump: u8,
+    pub version: u8,               // Layout version, see `UserBalance::VERSION`
+    pub vault: [u8; 20],           // EVM vault whose position holds the shares (zero = unlinked)
+    pub src_eid: u32,              // Source endpoint ID the user deposits from
+    pub reserved: [u8; 40],        // Zeroed space for future fields
 }
 
 impl UserBalance {
     pub const SIZE: usize = 8 + UserBalance::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+    /// Size of the original layout (evm_address, four u64 totals, deposit_count, bump), moved by
+    /// `migrate_user_balance`
+    pub const V0_SIZE: usize = 8 + 20 + (4 * 8) + 4 + 1;
+
+    /// Records a credited deposit and the vault shares it minted; sets identity fields on the first one.
+    pub fn record_deposit(
+        &mut self,
+        src_eid: u32,
+        evm_address: [u8; 20],
+        bump: u8,
+        amount: u64,
+        shares: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        if self.deposit_count == 0 {
+            self.src_eid = src_eid;
+            self.evm_address = evm_address;
+            self.bump = bump;
+            self.version = UserBalance::VERSION;
+        }
+        self.total_deposited =
+            self.total_deposited.checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        self.shares = self.shares.checked_add(shares).ok_or(MyOAppError::Overflow)?;
+        self.last_updated = timestamp;
+        self.deposit_count = self.deposit_count.checked_add(1).ok_or(MyOAppError::Overflow)?;
+        Ok(())
+    }
+
+    /// Records a withdrawal and debits the vault shares it burned. Yield accrues to the shares,
+    /// so a user may withdraw more than they deposited but never more shares than they hold.
+    pub fn record_withdrawal(&mut self, amount: u64, shares: u64, timestamp: i64) -> Result<()> {
+        require!(amount > 0, MyOAppError::ZeroAmount);
+        require!(shares <= self.shares, MyOAppError::InsufficientUserBalance);
+        self.shares -= shares;
+        self.total_withdrawn =
+            self.total_withdrawn.checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        self.last_updated = timestamp;
+        Ok(())
+    }
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(total_shares: u64) -> Store {
+        let mut store = Store::try_from_slice(&[0u8; Store::INIT_SPACE]).unwrap();
+        store.total_shares = total_shares;
+        store
+    }
+
+    #[test]
+    fn first_deposit_mints_one_to_one() {
+        assert_eq!(store(0).shares_for_deposit(1_000_000, 0).unwrap(), 1_000_000);
+        // Leftover assets with no shares outstanding still mint 1:1
+        assert_eq!(store(0).shares_for_deposit(1_000_000, 5).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn deposit_shares_round_down() {
+        // 3 shares over 4 assets: 1 asset is worth 0.75 shares
+        assert_eq!(store(3).shares_for_deposit(1, 4).unwrap(), 0);
+        assert_eq!(store(3).shares_for_deposit(3, 4).unwrap(), 2);
+        assert_eq!(store(1_000).shares_for_deposit(500, 1_100).unwrap(), 454);
+    }
+
+    #[test]
+    fn withdrawal_shares_round_up() {
+        assert_eq!(store(3).shares_for_withdrawal(1, 4).unwrap(), 1);
+        assert_eq!(store(3).shares_for_withdrawal(4, 4).unwrap(), 3);
+        assert_eq!(store(1_000).shares_for_withdrawal(500, 1_100).unwrap(), 455);
+        // Exact conversions are not rounded
+        assert_eq!(store(1_000).shares_for_withdrawal(550, 1_100).unwrap(), 500);
+    }
+
+    #[test]
+    fn unallocated_usdc_excludes_idle_and_parked_funds() {
+        let mut store = store(0);
+        store.idle_assets = 400;
+        store.parked_assets = 250;
+        // USDC held for a deposit parked over a cap cannot fund another deposit
+        assert_eq!(store.unallocated_usdc(1_000), 350);
+        assert_eq!(store.unallocated_usdc(600), 0);
+    }
+
+    #[test]
+    fn withdrawal_rejects_zero_and_more_than_assets() {
+        assert!(store(1_000).shares_for_withdrawal(0, 1_000).is_err());
+        assert!(store(1_000).shares_for_withdrawal(1_001, 1_000).is_err());
+    }
+
+    #[test]
+    fn round_trip_never_gains() {
+        let store = store(999_983);
+        let total_assets = 1_234_567;
+        for amount in [1, 7, 1_000, 99_999, 1_234_567] {
+            let minted = store.shares_for_deposit(amount, total_assets).unwrap();
+            let burned = store.shares_for_withdrawal(amount, total_assets).unwrap();
+            assert!(burned >= minted, "amount {amount}: minted {minted}, burned {burned}");
+        }
+    }
+
+    #[test]
+    fn legacy_shares_are_handed_out_in_full() {
+        let mut store = store(3_100_000);
+        store.legacy_shares = 3_100_000;
+        store.legacy_deposits = 3_000_000;
+        assert!(store.take_legacy_shares(3_000_001).is_err());
+        assert_eq!(store.take_legacy_shares(1_000_000).unwrap(), 1_033_333);
+        // The last record takes the rounding dust
+        assert_eq!(store.take_legacy_shares(2_000_000).unwrap(), 2_066_667);
+        assert_eq!((store.legacy_shares, store.legacy_deposits), (0, 0));
+        assert!(store.take_legacy_shares(1).is_err());
+    }
+}
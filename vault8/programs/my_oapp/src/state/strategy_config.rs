@@ -0,0 +1,43 @@
+use crate::errors::MyOAppError;
+use crate::strategy::StrategyKind;
+use crate::*;
+
+/// Maximum venue-specific pubkeys a `StrategyConfig` can hold
+pub const MAX_STRATEGY_ACCOUNTS: usize = 16;
+
+/// Per-venue configuration PDA, seeds [STRATEGY_SEED, kind]. Holds everything the adapter needs
+/// to resolve and invoke the venue's deposit/withdraw CPIs; `Store.allocations` decides how much
+/// of each deposit goes to it.
+#[account]
+#[derive(InitSpace)]
+pub struct StrategyConfig {
+    pub kind: StrategyKind,                 // Venue adapter
+    pub program_id: Pubkey,                 // Venue program invoked by the CPIs
+    pub share_mint: Pubkey,                 // Receipt token credited to the Store (JL fToken mint), if any
+    pub deposit_discriminator: [u8; 8],     // Venue deposit instruction discriminator
+    pub withdraw_discriminator: [u8; 8],    // Venue withdraw instruction discriminator
+    #[max_len(MAX_STRATEGY_ACCOUNTS)]
+    pub accounts: Vec<Pubkey>,              // Venue-specific accounts, in the adapter's order
+    pub bump: u8,
+    pub version: u8,                        // Layout version, see `StrategyConfig::VERSION`
+    pub reserved: [u8; 64],                 // Zeroed space for future fields
+}
+
+impl StrategyConfig {
+    pub const SIZE: usize = 8 + StrategyConfig::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Venue-specific account at `index`; the length is checked when the config is set.
+    pub fn account(&self, index: usize) -> Pubkey {
+        self.accounts[index]
+    }
+
+    /// Requires the account list to match what the venue adapter expects.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.accounts.len() == self.kind.adapter().config_accounts_len(),
+            MyOAppError::InvalidStrategyConfig
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::errors::MyOAppError;
+use crate::*;
+
+/// Share ledger of one EVM vault on one source chain, seeds [VAULT_POSITION_SEED, src_eid, vault].
+/// `vault` is the `ManagedVault` named in the deposit or withdrawal payload (the adapter that calls
+/// MyOApp passes its vault), so every vault bridging through the same peer has its exposure
+/// accounted and withdrawable separately.
+#[account]
+#[derive(InitSpace)]
+pub struct VaultPosition {
+    pub src_eid: u32,              // Source endpoint ID
+    pub vault: [u8; 20],           // EVM vault address
+    pub shares: u64,               // Vault shares held by this position
+    pub total_deposited: u64,      // Cumulative deposits (base units)
+    pub total_withdrawn: u64,      // Cumulative withdrawals (base units)
+    pub last_updated: i64,         // Last activity timestamp
+    pub bump: u8,
+    pub version: u8,               // Layout version, see `VaultPosition::VERSION`
+    pub reserved: [u8; 64],        // Zeroed space for future fields
+}
+
+impl VaultPosition {
+    pub const SIZE: usize = 8 + VaultPosition::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Sets the identity fields the first time the position is touched, then links `user_balance`
+    /// to it. A UserBalance holds the shares of one vault: the first link moves in the shares it
+    /// held in the pooled accounting (before positions existed), and a message naming another
+    /// vault is rejected so it cannot spend this vault's shares.
+    pub fn open_if_new(
+        &mut self,
+        src_eid: u32,
+        vault: [u8; 20],
+        bump: u8,
+        user_balance: &mut UserBalance,
+    ) -> Result<()> {
+        if self.version == 0 {
+            self.src_eid = src_eid;
+            self.vault = vault;
+            self.bump = bump;
+            self.version = VaultPosition::VERSION;
+        }
+        self.link(user_balance)
+    }
+
+    /// Records `user_balance` as held by this position, adding its pooled shares once.
+    pub fn link(&mut self, user_balance: &mut UserBalance) -> Result<()> {
+        if user_balance.vault != [0u8; 20] {
+            require!(user_balance.vault == self.vault, MyOAppError::VaultMismatch);
+            return Ok(());
+        }
+        // The zero address marks an unlinked UserBalance, so it cannot be a vault
+        require!(self.vault != [0u8; 20], MyOAppError::VaultMismatch);
+        self.shares = self.shares.checked_add(user_balance.shares).ok_or(MyOAppError::Overflow)?;
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(user_balance.total_deposited)
+            .ok_or(MyOAppError::Overflow)?;
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(user_balance.total_withdrawn)
+            .ok_or(MyOAppError::Overflow)?;
+        self.last_updated = self.last_updated.max(user_balance.last_updated);
+        user_balance.vault = self.vault;
+        Ok(())
+    }
+
+    /// Credits the vault shares a deposit minted.
+    pub fn record_deposit(&mut self, amount: u64, shares: u64, timestamp: i64) -> Result<()> {
+        self.total_deposited =
+            self.total_deposited.checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        self.shares = self.shares.checked_add(shares).ok_or(MyOAppError::Overflow)?;
+        self.last_updated = timestamp;
+        Ok(())
+    }
+
+    /// Debits the vault shares a withdrawal burned; a vault can only redeem its own shares.
+    pub fn record_withdrawal(&mut self, amount: u64, shares: u64, timestamp: i64) -> Result<()> {
+        require!(shares <= self.shares, MyOAppError::InsufficientVaultShares);
+        self.shares -= shares;
+        self.total_withdrawn =
+            self.total_withdrawn.checked_add(amount).ok_or(MyOAppError::Overflow)?;
+        self.last_updated = timestamp;
+        Ok(())
+    }
+}
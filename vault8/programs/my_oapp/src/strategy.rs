@@ -0,0 +1,501 @@
+use crate::errors::MyOAppError;
+use crate::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use oapp::endpoint_cpi::LzAccount;
+
+/// Number of `StrategyKind` variants, i.e. rows in `Store.allocations`
+pub const STRATEGY_COUNT: usize = 4;
+/// Allocation weights are basis points of each deposit and must sum to this
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Yield venue a `StrategyConfig` describes. The discriminant doubles as the config PDA seed and
+/// as the row of the venue in `Store.allocations`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+#[repr(u8)]
+pub enum StrategyKind {
+    JupiterLend = 0,
+    Kamino = 1,
+    MarginFi = 2,
+    Save = 3,
+}
+
+impl StrategyKind {
+    pub const ALL: [StrategyKind; STRATEGY_COUNT] =
+        [StrategyKind::JupiterLend, StrategyKind::Kamino, StrategyKind::MarginFi, StrategyKind::Save];
+
+    /// Row of the venue in `Store.allocations`
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Seed of the venue's `StrategyConfig` PDA: [STRATEGY_SEED, kind]
+    pub fn seed(self) -> [u8; 1] {
+        [self as u8]
+    }
+
+    /// Adapter implementing the venue's CPIs.
+    pub fn adapter(self) -> &'static dyn Strategy {
+        match self {
+            StrategyKind::JupiterLend => &jupiter_lend::JupiterLend,
+            StrategyKind::Kamino => &kamino::Kamino,
+            StrategyKind::MarginFi => &marginfi::MarginFi,
+            StrategyKind::Save => &save::Save,
+        }
+    }
+
+    /// Address of the venue's `StrategyConfig` PDA
+    pub fn config_address(self) -> Pubkey {
+        Pubkey::find_program_address(&[STRATEGY_SEED, &self.seed()], &crate::ID).0
+    }
+}
+
+/// A USDC yield venue the Store can deposit into and withdraw from.
+///
+/// Adapters resolve the venue's CPI account lists from its `StrategyConfig` (this is what
+/// `lz_receive_types` returns) and execute the CPIs against exactly those accounts, signed by
+/// the Store PDA. Shares are whatever the venue credits the Store with, measured around the CPI.
+pub trait Strategy {
+    /// Number of venue-specific pubkeys expected in `StrategyConfig.accounts`
+    fn config_accounts_len(&self) -> usize;
+
+    /// Number of accounts the deposit CPI takes (see `deposit_accounts`)
+    fn deposit_accounts_len(&self) -> usize;
+
+    /// Number of accounts the withdraw CPI takes (see `withdraw_accounts`)
+    fn withdraw_accounts_len(&self) -> usize;
+
+    /// Accounts for `deposit`, in CPI order; the last one is the venue program.
+    fn deposit_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount>;
+
+    /// Accounts for `withdraw`, in CPI order; the last one is the venue program.
+    fn withdraw_accounts(&self, store: &Store, store_key: &Pubkey, config: &StrategyConfig) -> Vec<LzAccount>;
+
+    /// Deposits `amount` USDC from the Store USDC ATA. Returns the shares credited to the Store.
+    fn deposit(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64>;
+
+    /// Withdraws `amount` USDC into the Store USDC ATA. Returns the shares burned.
+    fn withdraw(
+        &self,
+        store: &Store,
+        store_key: &Pubkey,
+        config: &StrategyConfig,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> Result<u64>;
+
+    /// Venue account `shares_to_assets` prices from. It is part of both the deposit and the
+    /// withdraw accounts.
+    fn price_account(&self, config: &StrategyConfig) -> Pubkey;
+
+    /// USDC value of `shares`, priced from the venue's `price_account` (e.g. the JL `Lending`).
+    fn shares_to_assets(&self, config: &StrategyConfig, price_account: &AccountInfo, shares: u64) -> Result<u64>;
+}
+
+/// One strategy's slice of the remaining accounts: its `StrategyConfig` PDA, followed by the
+/// accounts of a single operation (deposit, withdraw or pricing).
+pub struct Leg<'a, 'info> {
+    pub config: StrategyConfig,
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+/// Number of remaining accounts `deposit` consumes: a config PDA plus the deposit accounts for
+/// each allocated strategy.
+pub fn deposit_accounts_len(store: &Store) -> usize {
+    store.allocated_strategies().iter().map(|k| 1 + k.adapter().deposit_accounts_len()).sum()
+}
+
+/// Number of remaining accounts `withdraw` consumes: a config PDA plus the withdraw accounts for
+/// each allocated strategy.
+pub fn withdraw_accounts_len(store: &Store) -> usize {
+    store.allocated_strategies().iter().map(|k| 1 + k.adapter().withdraw_accounts_len()).sum()
+}
+
+/// Number of remaining accounts `total_assets` consumes: a config PDA plus the venue's price
+/// account for each allocated strategy.
+pub fn pricing_accounts_len(store: &Store) -> usize {
+    store.allocated_strategies().len() * 2
+}
+
+/// Deposit legs of the allocated strategies, in `StrategyKind` order. `configs` come from
+/// `load_configs`.
+pub fn deposit_accounts(store: &Store, store_key: &Pubkey, configs: &[StrategyConfig]) -> Vec<LzAccount> {
+    let mut accounts = Vec::new();
+    for config in configs {
+        accounts.push(config_account(config.kind));
+        accounts.extend(config.kind.adapter().deposit_accounts(store, store_key, config));
+    }
+    accounts
+}
+
+/// Withdraw legs of the allocated strategies, in `StrategyKind` order. `configs` come from
+/// `load_configs`.
+pub fn withdraw_accounts(store: &Store, store_key: &Pubkey, configs: &[StrategyConfig]) -> Vec<LzAccount> {
+    let mut accounts = Vec::new();
+    for config in configs {
+        accounts.push(config_account(config.kind));
+        accounts.extend(config.kind.adapter().withdraw_accounts(store, store_key, config));
+    }
+    accounts
+}
+
+/// Config PDA entry heading a strategy leg (read-only)
+fn config_account(kind: StrategyKind) -> LzAccount {
+    LzAccount { pubkey: kind.config_address(), is_signer: false, is_writable: false }
+}
+
+/// Deserializes the `StrategyConfig` PDA of `kind` from an account outside the Anchor context.
+pub fn load_config(kind: StrategyKind, account: &AccountInfo) -> Result<StrategyConfig> {
+    require_keys_eq!(*account.owner, crate::ID, MyOAppError::InvalidAccount);
+    let config = StrategyConfig::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    require!(config.kind == kind, MyOAppError::InvalidStrategyConfig);
+    let address =
+        Pubkey::create_program_address(&[STRATEGY_SEED, &kind.seed(), &[config.bump]], &crate::ID)
+            .map_err(|_| error!(MyOAppError::InvalidAccount))?;
+    require_keys_eq!(account.key(), address, MyOAppError::InvalidAccount);
+    Ok(config)
+}
+
+/// Loads the configs of the allocated strategies from the leading `accounts`, one per strategy
+/// in `StrategyKind` order. Used by the `*_types` instructions to resolve the strategy legs.
+pub fn load_configs(store: &Store, accounts: &[AccountInfo]) -> Result<Vec<StrategyConfig>> {
+    store
+        .allocated_strategies()
+        .into_iter()
+        .enumerate()
+        .map(|(i, kind)| load_config(kind, accounts.get(i).ok_or(MyOAppError::InvalidAccount)?))
+        .collect()
+}
+
+/// Splits the leading `accounts` into one leg per allocated strategy, each a config PDA followed
+/// by `len(adapter)` accounts. Returns the legs and the number of accounts consumed.
+fn legs<'a, 'info>(
+    store: &Store,
+    accounts: &'a [AccountInfo<'info>],
+    len: impl Fn(&dyn Strategy) -> usize,
+) -> Result<(Vec<Leg<'a, 'info>>, usize)> {
+    let mut legs = Vec::new();
+    let mut offset = 0;
+    for kind in store.allocated_strategies() {
+        let end = offset + 1 + len(kind.adapter());
+        require!(accounts.len() >= end, MyOAppError::InvalidAccount);
+        let config = load_config(kind, &accounts[offset])?;
+        legs.push(Leg { config, accounts: &accounts[offset + 1..end] });
+        offset = end;
+    }
+    Ok((legs, offset))
+}
+
+/// USDC value of the Store's venue shares in each leg, priced from the leg's price account.
+pub fn leg_values(store: &Store, legs: &[Leg]) -> Result<Vec<u64>> {
+    legs.iter()
+        .map(|leg| {
+            let adapter = leg.config.kind.adapter();
+            let price_account = find_account(leg.accounts, &adapter.price_account(&leg.config))?;
+            let shares = store.allocation(leg.config.kind).shares;
+            adapter.shares_to_assets(&leg.config, price_account, shares)
+        })
+        .collect()
+}
+
+pub fn sum(values: &[u64]) -> Result<u64> {
+    values
+        .iter()
+        .try_fold(0u64, |total, v| total.checked_add(*v))
+        .ok_or_else(|| error!(MyOAppError::Overflow))
+}
+
+/// Deposit legs of the allocated strategies at the start of `accounts`, and the number of
+/// accounts they span.
+pub fn deposit_legs<'a, 'info>(
+    store: &Store,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(Vec<Leg<'a, 'info>>, usize)> {
+    legs(store, accounts, |a| a.deposit_accounts_len())
+}
+
+/// Withdraw legs of the allocated strategies at the start of `accounts`, and the number of
+/// accounts they span.
+pub fn withdraw_legs<'a, 'info>(
+    store: &Store,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(Vec<Leg<'a, 'info>>, usize)> {
+    legs(store, accounts, |a| a.withdraw_accounts_len())
+}
+
+/// USDC value of everything backing the vault shares: the idle buffer plus the strategies.
+/// `accounts` are pricing legs: a config PDA followed by the venue's price account, per
+/// allocated strategy.
+pub fn total_assets(store: &Store, accounts: &[AccountInfo]) -> Result<u64> {
+    let (legs, _) = legs(store, accounts, |_| 1)?;
+    store.idle_assets.checked_add(sum(&leg_values(store, &legs)?)?).ok_or_else(|| error!(MyOAppError::Overflow))
+}
+
+/// Deposits `amount` USDC from the Store USDC ATA, first topping the idle buffer up to its target
+/// and investing the rest across the allocated strategies. `accounts` start with the deposit legs.
+/// Fails past the Store TVL cap. Returns the vault shares `amount` is worth at the pre-deposit
+/// share price (already credited to `Store.total_shares`) and the number of accounts consumed.
+pub fn deposit(
+    store: &mut Store,
+    store_key: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<(u64, usize)> {
+    let (legs, consumed) = deposit_legs(store, accounts)?;
+    let invested = sum(&leg_values(store, &legs)?)?;
+    let total_assets = store.idle_assets.checked_add(invested).ok_or(MyOAppError::Overflow)?;
+    store.check_tvl_cap(total_assets, amount)?;
+    let shares = store.shares_for_deposit(amount, total_assets)?;
+    // A deposit rounding down to no shares would hand its USDC to the other holders
+    require!(shares > 0, MyOAppError::ZeroShares);
+
+    let target = store.idle_target(total_assets.checked_add(amount).ok_or(MyOAppError::Overflow)?);
+    let to_idle = amount.min(target.saturating_sub(store.idle_assets));
+    // Whatever the venues did not take stays in the Store USDC ATA as idle
+    let spent = invest(store, store_key, &legs, amount - to_idle)?;
+    let kept = amount.checked_sub(spent).ok_or(MyOAppError::Overflow)?;
+    store.idle_assets = store.idle_assets.checked_add(kept).ok_or(MyOAppError::Overflow)?;
+    store.credit_shares(shares)?;
+    Ok((shares, consumed))
+}
+
+/// Withdraws `amount` USDC into the Store USDC ATA, drawing on the idle buffer first and redeeming
+/// the rest from the allocated strategies. `accounts` start with the withdraw legs. Returns the
+/// vault shares burned (rounded up, already debited from `Store.total_shares`) and the number of
+/// accounts consumed.
+pub fn withdraw(
+    store: &mut Store,
+    store_key: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<(u64, usize)> {
+    let (legs, consumed) = withdraw_legs(store, accounts)?;
+    let values = leg_values(store, &legs)?;
+    let total_assets = store.idle_assets.checked_add(sum(&values)?).ok_or(MyOAppError::Overflow)?;
+    let shares = store.shares_for_withdrawal(amount, total_assets)?;
+
+    // `amount` leaves the Store USDC ATA; the idle buffer covers it less what the venues paid in
+    let from_idle = amount.min(store.idle_assets);
+    let request = divest_amount(amount - from_idle, legs.len(), sum(&values)?);
+    let received = divest(store, store_key, &legs, &values, request)?;
+    store.idle_assets = idle_after_withdrawal(store.idle_assets, received, amount)?;
+    store.debit_shares(shares)?;
+    Ok((shares, consumed))
+}
+
+/// USDC to redeem from `legs` venues holding `invested` when a withdrawal needs `needed` beyond the
+/// idle buffer. A venue can pay out one unit less than asked, as it rounds the shares it burns in
+/// its own favour, so each leg is asked for one more unit (within what is invested); the surplus
+/// stays idle.
+fn divest_amount(needed: u64, legs: usize, invested: u64) -> u64 {
+    if needed == 0 {
+        return 0;
+    }
+    needed.saturating_add(legs as u64).min(invested)
+}
+
+/// Idle buffer left once `amount` leaves the Store USDC ATA, which held `idle` plus the `received`
+/// USDC the venues paid in.
+fn idle_after_withdrawal(idle: u64, received: u64, amount: u64) -> Result<u64> {
+    idle.checked_add(received)
+        .ok_or(MyOAppError::Overflow)?
+        .checked_sub(amount)
+        .ok_or_else(|| error!(MyOAppError::InsufficientStoreBalance))
+}
+
+/// Deposits `amount` USDC from the Store USDC ATA into the deposit `legs`, split by weight with the
+/// rounding remainder going to the first weighted strategy. Returns the USDC that actually left the
+/// ATA.
+pub fn invest(store: &mut Store, store_key: &Pubkey, legs: &[Leg], amount: u64) -> Result<u64> {
+    if amount == 0 {
+        return Ok(0);
+    }
+    let store_usdc_ata = store_usdc_ata(store, store_key, legs)?;
+    let usdc_before = token_amount(store, store_usdc_ata)?;
+    let weights: Vec<u64> =
+        legs.iter().map(|leg| store.allocation(leg.config.kind).weight_bps as u64).collect();
+    let first_weighted =
+        weights.iter().position(|w| *w > 0).ok_or(MyOAppError::InvalidStrategyWeights)?;
+    let mut amounts: Vec<u64> = weights
+        .iter()
+        .map(|w| ((amount as u128) * (*w as u128) / (BPS_DENOMINATOR as u128)) as u64)
+        .collect();
+    amounts[first_weighted] += amount - sum(&amounts)?;
+
+    for (leg, leg_amount) in legs.iter().zip(amounts) {
+        if leg_amount == 0 {
+            continue;
+        }
+        let adapter = leg.config.kind.adapter();
+        let venue_shares = adapter.deposit(store, store_key, &leg.config, leg.accounts, leg_amount)?;
+        let allocation = store.allocation_mut(leg.config.kind);
+        allocation.shares = allocation.shares.checked_add(venue_shares).ok_or(MyOAppError::Overflow)?;
+    }
+    usdc_before
+        .checked_sub(token_amount(store, store_usdc_ata)?)
+        .ok_or_else(|| error!(MyOAppError::Overflow))
+}
+
+/// Redeems `amount` USDC into the Store USDC ATA from the withdraw `legs`, pro rata to their
+/// `values`, with the rounding remainder taken from whichever legs still have room. Returns the
+/// USDC that actually landed in the ATA.
+pub fn divest(store: &mut Store, store_key: &Pubkey, legs: &[Leg], values: &[u64], amount: u64) -> Result<u64> {
+    if amount == 0 {
+        return Ok(0);
+    }
+    let invested = sum(values)?;
+    require!(amount <= invested, MyOAppError::InsufficientStoreBalance);
+    let store_usdc_ata = store_usdc_ata(store, store_key, legs)?;
+    let usdc_before = token_amount(store, store_usdc_ata)?;
+    let mut amounts: Vec<u64> = values
+        .iter()
+        .map(|v| ((amount as u128) * (*v as u128) / (invested as u128)) as u64)
+        .collect();
+    let mut rest = amount - sum(&amounts)?;
+    for (leg_amount, value) in amounts.iter_mut().zip(values) {
+        let extra = rest.min(value - *leg_amount);
+        *leg_amount += extra;
+        rest -= extra;
+    }
+
+    for (leg, leg_amount) in legs.iter().zip(amounts) {
+        if leg_amount == 0 {
+            continue;
+        }
+        let adapter = leg.config.kind.adapter();
+        let venue_shares = adapter.withdraw(store, store_key, &leg.config, leg.accounts, leg_amount)?;
+        let allocation = store.allocation_mut(leg.config.kind);
+        allocation.shares = allocation.shares.checked_sub(venue_shares).ok_or(MyOAppError::Overflow)?;
+    }
+    token_amount(store, store_usdc_ata)?
+        .checked_sub(usdc_before)
+        .ok_or_else(|| error!(MyOAppError::Overflow))
+}
+
+/// The Store USDC ATA, which every deposit and withdraw leg carries.
+fn store_usdc_ata<'a, 'info>(
+    store: &Store,
+    store_key: &Pubkey,
+    legs: &[Leg<'a, 'info>],
+) -> Result<&'a AccountInfo<'info>> {
+    let leg = legs.first().ok_or(MyOAppError::InvalidAccount)?;
+    find_account(leg.accounts, &store.associated_token_address(store_key, &store.usdc_mint))
+}
+
+/// Requires `accounts` to be exactly the `expected` list, key for key.
+pub fn verify_accounts(expected: &[LzAccount], accounts: &[AccountInfo]) -> Result<()> {
+    require!(accounts.len() == expected.len(), MyOAppError::InvalidAccount);
+    for (account, meta) in accounts.iter().zip(expected) {
+        require_keys_eq!(account.key(), meta.pubkey, MyOAppError::InvalidAccount);
+    }
+    Ok(())
+}
+
+/// Finds `key` among the strategy accounts (e.g. the Store USDC ATA).
+pub fn find_account<'a, 'info>(accounts: &'a [AccountInfo<'info>], key: &Pubkey) -> Result<&'a AccountInfo<'info>> {
+    accounts.iter().find(|a| a.key == key).ok_or_else(|| error!(MyOAppError::InvalidAccount))
+}
+
+/// Invokes the venue program (the last of `expected`) with the other accounts as metas, signed by
+/// the Store PDA. `accounts` must already have been checked against `expected`.
+pub fn invoke(
+    store: &Store,
+    store_key: &Pubkey,
+    config: &StrategyConfig,
+    expected: &[LzAccount],
+    accounts: &[AccountInfo],
+    data: Vec<u8>,
+) -> Result<()> {
+    let (program, cpi_accounts) = expected.split_last().ok_or(MyOAppError::InvalidAccount)?;
+    require_keys_eq!(program.pubkey, config.program_id, MyOAppError::InvalidAccount);
+    invoke_with_metas(store, store_key, config.program_id, cpi_accounts, accounts, data)
+}
+
+/// Invokes `program_id` with `metas` as the instruction accounts, signed by the Store PDA. For
+/// venues whose deposit/withdraw takes more than one CPI over a shared account list.
+pub fn invoke_with_metas(
+    store: &Store,
+    store_key: &Pubkey,
+    program_id: Pubkey,
+    metas: &[LzAccount],
+    accounts: &[AccountInfo],
+    data: Vec<u8>,
+) -> Result<()> {
+    let metas = metas
+        .iter()
+        .map(|a| AccountMeta {
+            pubkey: a.pubkey,
+            is_signer: a.pubkey == *store_key,
+            is_writable: a.is_writable,
+        })
+        .collect();
+    let ix = Instruction { program_id, accounts: metas, data };
+    let signer_seeds: &[&[u8]] = &[STORE_SEED, &[store.bump]];
+    invoke_signed(&ix, accounts, &[signer_seeds])?;
+    Ok(())
+}
+
+/// Instruction data for the common `discriminator || amount (u64 LE)` layout.
+pub fn amount_ix_data(discriminator: [u8; 8], amount: u64) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// Reads the `amount` field of an SPL token account owned by the Store's token program.
+pub fn token_amount(store: &Store, token_account: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(*token_account.owner, store.token_program, MyOAppError::InvalidAccount);
+    let data = token_account.try_borrow_data()?;
+    // SPL token account layout: mint(32) + owner(32) + amount(8) + ...
+    require!(data.len() >= 72, MyOAppError::InvalidAccount);
+    let mut amount = [0u8; 8];
+    amount.copy_from_slice(&data[64..72]);
+    Ok(u64::from_le_bytes(amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn withdrawal_covers_a_venue_paying_one_unit_short() {
+        // 100 USDC over 40 idle: the two legs are asked for 60 plus one unit each
+        let request = divest_amount(60, 2, 1_000);
+        assert_eq!(request, 62);
+        // Each venue rounds one unit away and pays 60 in total
+        assert_eq!(idle_after_withdrawal(40, request - 2, 100).unwrap(), 0);
+        // Venues paying in full leave the surplus idle
+        assert_eq!(idle_after_withdrawal(40, request, 100).unwrap(), 2);
+    }
+
+    #[test]
+    fn withdrawal_asks_no_more_than_is_invested() {
+        assert_eq!(divest_amount(0, 2, 1_000), 0);
+        assert_eq!(divest_amount(999, 2, 1_000), 1_000);
+        // A venue short of the full position leaves the withdrawal uncovered
+        assert!(idle_after_withdrawal(0, 999, 1_000).is_err());
+    }
+
+    #[test]
+    fn deposit_worth_no_shares_is_rejected() {
+        let mut store = Store::try_from_slice(&[0u8; Store::INIT_SPACE]).unwrap();
+        // 3 shares over 4 assets: 1 unit rounds down to no share
+        store.total_shares = 3;
+        store.idle_assets = 4;
+        match deposit(&mut store, &Pubkey::default(), &[], 1).unwrap_err() {
+            Error::AnchorError(e) => assert_eq!(e.error_code_number, MyOAppError::ZeroShares.into()),
+            e => panic!("unexpected error {e:?}"),
+        }
+        assert_eq!(store.total_shares, 3);
+        assert_eq!(store.idle_assets, 4);
+    }
+}
@@ -76,23 +76,21 @@ async function main() {
     console.log("✅ CCTP deposit initiated!");
     console.log("Gas used:", receipt.gasUsed.toString());
     
-    // Extract CCTP nonce from event
-    let cctpNonce = null;
+    // The CCTP V2 nonce is assigned by the attester; the relayer records it on MyOApp later
+    let initiated = false;
     for (const log of receipt.logs) {
         try {
             const parsed = myOApp.interface.parseLog(log);
             if (parsed.name === "CctpDepositInitiated") {
-                cctpNonce = parsed.args.cctpNonce.toString();
-                console.log("\n📋 CCTP Nonce:", cctpNonce);
-                console.log("Amount:", amount);
+                initiated = true;
                 console.log("📋 Amount:", ethers.utils.formatUnits(parsed.args.amount, 6), "USDC");
                 console.log("📋 Destination Domain:", parsed.args.destinationDomain.toString(), "(Solana)");
             }
         } catch {}
     }
     
-    if (!cctpNonce) {
-        console.warn("\n⚠️  Could not extract CCTP nonce from events");
+    if (!initiated) {
+        console.warn("\n⚠️  Could not find the CctpDepositInitiated event");
         console.warn("    Check transaction on BaseScan:", tx.hash);
         return;
     }
@@ -100,7 +98,6 @@ async function main() {
     // Save to file
     const data = {
         txHash: tx.hash,
-        cctpNonce: cctpNonce,
         user: signer.address,
         amount: AMOUNT.toString(),
         amountFormatted: ethers.utils.formatUnits(AMOUNT, 6),
@@ -51,7 +51,7 @@ async function main() {
 
     
     console.log("\n⏳ Quoting LayerZero fee...");
-    const fee = await myOApp.quoteDeposit(SOLANA_EID, options, false);
+    const fee = await myOApp.quoteDeposit(SOLANA_EID, signer.address, options, false);
     console.log("LayerZero fee:", ethers.utils.formatEther(fee.nativeFee), "ETH");
     
     // Confirm with user
@@ -67,7 +67,7 @@ async function main() {
     console.log("═".repeat(50));
     
     console.log("\n⏳ Sending LayerZero message...");
-    const tx = await myOApp.requestDeposit(SOLANA_EID, options, {
+    const tx = await myOApp.requestDeposit(SOLANA_EID, signer.address, options, {
         value: fee.nativeFee
     });
     console.log("Transaction hash:", tx.hash);
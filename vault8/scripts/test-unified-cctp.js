@@ -61,7 +61,7 @@ async function main() {
         console.log(`TX: ${tx.hash}`);
         console.log(`Gas used: ${receipt.gasUsed.toString()}\n`);
         
-        // Parse events to get nonce
+        // Parse events
         const events = receipt.logs
             .map(log => {
                 try {
@@ -74,7 +74,7 @@ async function main() {
         
         console.log(`📋 Events emitted: ${events.length}`);
         
-        // Find the final event with nonce (destinationDomain = 5 for Solana)
+        // Find the final event (destinationDomain = 5 for Solana)
         const finalEvent = events.find(e => {
             const domain = e.args.destinationDomain;
             // Handle both BigNumber and regular number
@@ -83,14 +83,11 @@ async function main() {
         });
         
         if (finalEvent) {
-            const nonce = finalEvent.args.cctpNonce;
-            const nonceValue = nonce.toString ? nonce.toString() : nonce;
             const depositAmount = finalEvent.args.depositAmount;
             const mintedAmount = finalEvent.args.mintedAmount;
             const fee = finalEvent.args.fee;
             
             console.log(`\n🎉 CCTP BURN SUCCESSFUL!`);
-            console.log(`   Nonce: ${nonceValue}`);
             console.log(`   Deposit Amount: ${ethers.utils.formatUnits(depositAmount, 6)} USDC`);
             console.log(`   CCTP Fee: ${ethers.utils.formatUnits(fee, 6)} USDC (1 bps = 0.01%)`);
             console.log(`   Minted Amount: ${ethers.utils.formatUnits(mintedAmount, 6)} USDC ← use this for LZ message`);
@@ -100,13 +97,12 @@ async function main() {
             console.log(`\n📝 IMPORTANT: The minted amount (${ethers.utils.formatUnits(mintedAmount, 6)} USDC) is what you'll receive on Solana.`);
             console.log(`   Use this amount in your LayerZero message!`);
         } else {
-            console.log("\n⚠️  Could not find final CCTP event with nonce");
+            console.log("\n⚠️  Could not find final CCTP event");
             console.log("   Events found:", events.map(e => ({
                 user: e.args.user,
                 depositAmount: e.args.depositAmount?.toString(),
                 mintedAmount: e.args.mintedAmount?.toString(),
                 fee: e.args.fee?.toString(),
-                nonce: e.args.cctpNonce.toString(),
                 domain: e.args.destinationDomain.toString()
             })));
         }
@@ -14,16 +14,20 @@ interface TaskArguments {
     fromEid: number
     dstEid: number
     message: string
+    userBalance?: string
     computeUnitPriceScaleFactor: number
     contractName: string
 }
 
 const action: ActionType<TaskArguments> = async (
-    { fromEid, dstEid, message, computeUnitPriceScaleFactor, contractName },
+    { fromEid, dstEid, message, userBalance, computeUnitPriceScaleFactor, contractName },
     hre: HardhatRuntimeEnvironment
 ) => {
     if (endpointIdToChainType(fromEid) === ChainType.SOLANA) {
-        await sendFromSolana(fromEid, dstEid, message, computeUnitPriceScaleFactor)
+        if (!userBalance) {
+            throw new Error('--user-balance is required when sending from Solana')
+        }
+        await sendFromSolana(fromEid, dstEid, userBalance, computeUnitPriceScaleFactor)
     } else if (endpointIdToChainType(fromEid) === ChainType.EVM) {
         await sendFromEvm(dstEid, message, contractName, hre)
     } else {
@@ -31,7 +35,14 @@ const action: ActionType<TaskArguments> = async (
     }
 }
 
-async function sendFromSolana(fromEid: number, dstEid: number, message: string, computeUnitPriceScaleFactor: number) {
+// Solana only sends typed messages; this sends a BalanceSnapshot of `userBalance`, signed by the
+// wallet as the strategy keeper
+async function sendFromSolana(
+    fromEid: number,
+    dstEid: number,
+    userBalance: string,
+    computeUnitPriceScaleFactor: number
+) {
     const solanaEid = fromEid
     const solanaDeployment = getSolanaDeployment(solanaEid)
     const { connection, umi, umiWalletSigner } = await deriveConnection(solanaEid)
@@ -40,11 +51,13 @@ async function sendFromSolana(fromEid: number, dstEid: number, message: string,
 
     const options = Options.newOptions().toBytes() // leaving empty, relying on enforced options instead
 
+    const message: myoapp.types.OutboundMessageArgs = { __kind: 'BalanceSnapshot' }
     const { nativeFee } = await myoappInstance.quote(umi.rpc, umiWalletSigner.publicKey, {
         dstEid,
         message,
         options,
         payInLzToken: false,
+        userBalance: publicKey(userBalance),
     })
 
     console.log('🔖 Native fee quoted:', nativeFee.toString())
@@ -55,6 +68,8 @@ async function sendFromSolana(fromEid: number, dstEid: number, message: string,
             message,
             options,
             nativeFee,
+            keeper: umiWalletSigner,
+            userBalance: publicKey(userBalance),
         })
     )
     txBuilder = await addComputeUnitInstructions(
@@ -69,7 +84,7 @@ async function sendFromSolana(fromEid: number, dstEid: number, message: string,
     const tx = await txBuilder.sendAndConfirm(umi)
     const txHash = bs58.encode(tx.signature)
 
-    console.log('✉️  Cross-chain message:', `"${message}"`, '→ endpointId', dstEid)
+    console.log('✉️  Balance snapshot of', userBalance, '→ endpointId', dstEid)
     console.log('🧾 Transaction hash:', txHash)
     console.log('🌐 Track transfer:', getLayerZeroScanLink(txHash, isV2Testnet(dstEid)))
 }
@@ -97,9 +112,10 @@ async function sendFromEvm(dstEid: number, message: string, contractName: string
 }
 
 // Note: for testing reference, Optimism Sepolia's eid is 40232 and Solana Devnet's eid is 40168
-task('lz:oapp:send', 'Sends a string message cross-chain', action)
+task('lz:oapp:send', 'Sends a message cross-chain', action)
     .addParam('fromEid', 'Source endpoint ID', undefined, types.int, false)
     .addParam('dstEid', 'Destination endpoint ID', undefined, types.int, false)
-    .addParam('message', 'String message to send', undefined, types.string, false)
+    .addOptionalParam('message', 'String message to send', '', types.string) // only if fromEid is EVM
+    .addOptionalParam('userBalance', 'UserBalance account to snapshot', undefined, types.string) // only if fromEid is Solana
     .addParam('computeUnitPriceScaleFactor', 'The compute unit price scale factor', 4, types.float, true) // only if fromEid is Solana
     .addOptionalParam('contractName', 'Name of the OApp contract in deployments folder', 'MyOApp', types.string) // only if fromEid is EVM
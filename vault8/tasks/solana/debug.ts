@@ -139,16 +139,16 @@ task('lz:oapp:solana:debug', 'Prints OApp Store and Peer information')
                     DebugLogger.keyValue('PeerConfig Account', peerConfigs[index].toString())
                     DebugLogger.keyValue('Peer Address', denormalizePeer(info.peerAddress, dstEid))
                     DebugLogger.keyHeader('Enforced Options')
-                    DebugLogger.keyValue(
-                        'Send',
-                        decodeLzReceiveOptions(uint8ArrayToHex(info.enforcedOptions.send, true)),
-                        2
-                    )
-                    DebugLogger.keyValue(
-                        'SendAndCall',
-                        decodeLzReceiveOptions(uint8ArrayToHex(info.enforcedOptions.sendAndCall, true)),
-                        2
-                    )
+                    for (const { msgType, options } of info.enforcedOptions.entries) {
+                        DebugLogger.keyValue(
+                            `MsgType ${msgType}`,
+                            decodeLzReceiveOptions(uint8ArrayToHex(options, true)),
+                            2
+                        )
+                    }
+                    DebugLogger.keyHeader('Inbound Rate Limit')
+                    DebugLogger.keyValue('Limit', info.inboundRateLimit.limit.toString(), 2)
+                    DebugLogger.keyValue('Window (s)', info.inboundRateLimit.window.toString(), 2)
 
                     printOAppReceiveConfigs(oAppReceiveConfig, network.chainName)
                     printOAppSendConfigs(oAppSendConfig, network.chainName)
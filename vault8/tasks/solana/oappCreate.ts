@@ -25,6 +25,10 @@ const action: ActionType<Args> = async ({ programId, eid }, hre: HardhatRuntimeE
     const myoappInstance: myoapp.MyOApp = new myoapp.MyOApp(publicKey(programId))
     const [oapp] = myoappInstance.pda.oapp()
     const { umi, umiWalletSigner } = await deriveConnection(eid)
+    // init_store also creates the LzReceiveTypes / LzComposeTypes / Roles accounts and only accepts
+    // the program's upgrade authority (checked against its ProgramData account) as payer
+    console.log(`ProgramData: ${myoappInstance.pda.programData()[0]}`)
+    console.log(`Roles: ${myoappInstance.pda.roles()[0]}`)
     const txBuilder = transactionBuilder().add(myoappInstance.initStore(umiWalletSigner, umiWalletSigner.publicKey))
     const tx = await txBuilder.sendAndConfirm(umi)
     console.log(`createTx: ${getExplorerTxLink(bs58.encode(tx.signature), isTestnet)}`)
@@ -25,10 +25,21 @@ function deriveStorePda(programId: PublicKey): PublicKey {
   return pda
 }
 
+// Jupiter Lend StrategyConfig PDA: seeds ["Strategy", StrategyKind::JupiterLend]
+function deriveJlStrategyPda(programId: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync([Buffer.from('Strategy'), Buffer.from([0])], programId)
+  return pda
+}
+
+function deriveRolesPda(programId: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync([Buffer.from('Roles')], programId)
+  return pda
+}
+
 function deriveAta(owner: PublicKey, mint: PublicKey, tokenProgram: PublicKey, associatedTokenProgram: PublicKey): PublicKey {
-  // SPL Associated Token Account PDA seeds: ["ata", owner, token_program, mint]
+  // SPL Associated Token Account PDA seeds: [owner, token_program, mint]
   const [ata] = PublicKey.findProgramAddressSync(
-    [Buffer.from('ata'), owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
+    [owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
     associatedTokenProgram,
   )
   return ata
@@ -88,17 +99,32 @@ task('lz:oapp:solana:set-jl-config', 'Sets Jupiter Lend + SPL config on Store an
 
     const program = new anchor.Program(idl, programId, provider)
     const store = deriveStorePda(programId)
+    const strategy = deriveJlStrategyPda(programId)
+    const roles = deriveRolesPda(programId)
 
     console.log('Program ID:', programId.toBase58())
     console.log('Store PDA  :', store.toBase58())
+    console.log('JL Strategy:', strategy.toBase58())
 
     if (printOnly) {
       console.log('ℹ️  Print-only mode: skipping on-chain set_jl_config')
     } else {
-      // Call set_jl_config
+      // Call set_jl_config; the wallet must hold the config admin role. The JL accounts are
+      // passed so the program can check the config against them.
       const txSig = await program.methods
         .setJlConfig(params as any)
-        .accounts({ store, admin: wallet.publicKey })
+        .accounts({
+          store,
+          strategy,
+          configAdmin: wallet.publicKey,
+          roles,
+          jlLendingAdmin: params.jlLendingAdmin,
+          jlLending: params.jlLending,
+          jlFTokenMint: params.jlFTokenMint,
+          jlVault: params.jlVault,
+          usdcMint: params.usdcMint,
+          systemProgram: SystemProgram.programId,
+        })
         .rpc()
 
       console.log('✅ set_jl_config sent. tx =', txSig)
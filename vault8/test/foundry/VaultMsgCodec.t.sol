@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.20;
+
+import { VaultMsgCodec } from "../../contracts/libs/VaultMsgCodec.sol";
+
+// Forge imports
+import { Test } from "forge-std/Test.sol";
+
+/// @dev Exposes the calldata decoders of VaultMsgCodec as external calls
+contract VaultMsgCodecHarness {
+    function isAck(bytes calldata payload) external pure returns (bool) {
+        return VaultMsgCodec.isAck(payload);
+    }
+
+    function decAck(bytes calldata payload) external pure returns (uint64, bytes32) {
+        return VaultMsgCodec.decAck(payload);
+    }
+
+    function isWithdrawComplete(bytes calldata payload) external pure returns (bool) {
+        return VaultMsgCodec.isWithdrawComplete(payload);
+    }
+
+    function decWithdrawComplete(bytes calldata payload) external pure returns (uint64, address, bytes32) {
+        return VaultMsgCodec.decWithdrawComplete(payload);
+    }
+
+    function isBalanceSnapshot(bytes calldata payload) external pure returns (bool) {
+        return VaultMsgCodec.isBalanceSnapshot(payload);
+    }
+
+    function decBalanceSnapshot(
+        bytes calldata payload
+    ) external pure returns (address, uint64, uint64, uint64, int64) {
+        return VaultMsgCodec.decBalanceSnapshot(payload);
+    }
+
+    function isNav(bytes calldata payload) external pure returns (bool) {
+        return VaultMsgCodec.isNav(payload);
+    }
+
+    function decNav(bytes calldata payload) external pure returns (uint64, int64, uint64) {
+        return VaultMsgCodec.decNav(payload);
+    }
+
+    function fromLeBytes8(bytes calldata b) external pure returns (uint64) {
+        return VaultMsgCodec.fromLeBytes8(b);
+    }
+}
+
+// Vectors match the ones in programs/my_oapp/src/msg_codec.rs
+contract VaultMsgCodecTest is Test {
+    address private constant EVM = address(bytes20(hex"1111111111111111111111111111111111111111"));
+    address private constant VAULT = address(bytes20(hex"2222222222222222222222222222222222222222"));
+    address private constant REFERRER = address(bytes20(hex"5555555555555555555555555555555555555555"));
+    bytes32 private constant NONCE = hex"3333333333333333333333333333333333333333333333333333333333333333";
+    bytes32 private constant GUID = hex"4444444444444444444444444444444444444444444444444444444444444444";
+    uint32 private constant BASE_DOMAIN = 6;
+    bytes4 private constant BASE_DOMAIN_LE = 0x06000000;
+
+    VaultMsgCodecHarness private codec;
+
+    function setUp() public {
+        codec = new VaultMsgCodecHarness();
+    }
+
+    // ==================== Outbound (Base -> Solana) ====================
+
+    function test_encDeposit() public {
+        bytes memory payload = VaultMsgCodec.encDeposit(1_000_000, EVM, VAULT, BASE_DOMAIN, NONCE);
+        bytes memory expected =
+            abi.encodePacked(bytes2(0x0101), bytes8(0x40420f0000000000), EVM, VAULT, BASE_DOMAIN_LE, NONCE);
+        assertEq(payload.length, 86);
+        assertEq(payload, expected);
+    }
+
+    function test_encWithdraw() public {
+        bytes memory payload = VaultMsgCodec.encWithdraw(500_000, EVM, VAULT);
+        assertEq(payload.length, 50);
+        assertEq(payload, abi.encodePacked(bytes2(0x0102), bytes8(0x20a1070000000000), EVM, VAULT));
+    }
+
+    function test_encDepositAndAllocate() public {
+        bytes memory payload = VaultMsgCodec.encDepositAndAllocate(7, EVM, VAULT, BASE_DOMAIN, NONCE);
+        bytes memory expected = abi.encodePacked(
+            bytes2(0x0106),
+            bytes8(0x0700000000000000),
+            EVM,
+            VAULT,
+            BASE_DOMAIN_LE,
+            NONCE,
+            VaultMsgCodec.COMPOSE_ALLOCATE
+        );
+        assertEq(payload.length, 87);
+        assertEq(payload, expected);
+    }
+
+    function test_encDepositAndRegisterReferrer() public {
+        bytes memory payload =
+            VaultMsgCodec.encDepositAndRegisterReferrer(7, EVM, VAULT, BASE_DOMAIN, NONCE, REFERRER);
+        bytes memory expected = abi.encodePacked(
+            bytes2(0x0106),
+            bytes8(0x0700000000000000),
+            EVM,
+            VAULT,
+            BASE_DOMAIN_LE,
+            NONCE,
+            VaultMsgCodec.COMPOSE_REGISTER_REFERRER,
+            REFERRER
+        );
+        assertEq(payload.length, 107);
+        assertEq(payload, expected);
+    }
+
+    // ==================== Inbound (Solana -> Base) ====================
+
+    function test_ackRoundTrip() public {
+        bytes memory payload = abi.encodePacked(bytes2(0x0103), bytes8(0x40420f0000000000), GUID);
+        assertTrue(codec.isAck(payload));
+        (uint64 amount, bytes32 guid) = codec.decAck(payload);
+        assertEq(amount, 1_000_000);
+        assertEq(guid, GUID);
+        assertFalse(codec.isAck(_truncated(payload)));
+        assertFalse(codec.isAck(_extended(payload)));
+    }
+
+    function test_withdrawCompleteRoundTrip() public {
+        bytes memory payload = abi.encodePacked(bytes2(0x0107), bytes8(0x0900000000000000), EVM, GUID);
+        assertTrue(codec.isWithdrawComplete(payload));
+        (uint64 amount, address user, bytes32 guid) = codec.decWithdrawComplete(payload);
+        assertEq(amount, 9);
+        assertEq(user, EVM);
+        assertEq(guid, GUID);
+        assertFalse(codec.isWithdrawComplete(_truncated(payload)));
+        assertFalse(codec.isWithdrawComplete(_extended(payload)));
+    }
+
+    function test_balanceSnapshotRoundTrip() public {
+        bytes memory payload = abi.encodePacked(
+            bytes2(0x0108),
+            EVM,
+            bytes8(0x0100000000000000),
+            bytes8(0x0200000000000000),
+            bytes8(0x0300000000000000),
+            bytes8(0xfcffffffffffffff)
+        );
+        assertTrue(codec.isBalanceSnapshot(payload));
+        (address user, uint64 shares, uint64 deposited, uint64 withdrawn, int64 timestamp) =
+            codec.decBalanceSnapshot(payload);
+        assertEq(user, EVM);
+        assertEq(shares, 1);
+        assertEq(deposited, 2);
+        assertEq(withdrawn, 3);
+        assertEq(timestamp, -4);
+        assertFalse(codec.isBalanceSnapshot(_truncated(payload)));
+        assertFalse(codec.isBalanceSnapshot(_extended(payload)));
+    }
+
+    function test_navRoundTrip() public {
+        bytes memory payload = abi.encodePacked(
+            bytes2(0x0109),
+            bytes8(0x0500000000000000),
+            bytes8(0x0600000000000000),
+            bytes8(0x0700000000000000)
+        );
+        assertTrue(codec.isNav(payload));
+        (uint64 value, int64 timestamp, uint64 slot) = codec.decNav(payload);
+        assertEq(value, 5);
+        assertEq(timestamp, 6);
+        assertEq(slot, 7);
+        assertFalse(codec.isNav(_truncated(payload)));
+        assertFalse(codec.isNav(_extended(payload)));
+    }
+
+    function test_rejectsWrongHeader() public {
+        // Unsupported version
+        assertFalse(codec.isAck(abi.encodePacked(bytes2(0x0203), bytes8(0x40420f0000000000), GUID)));
+        // Right length, wrong message type
+        assertFalse(codec.isAck(abi.encodePacked(bytes2(0x0109), bytes8(0x40420f0000000000), GUID)));
+        assertFalse(codec.isAck(""));
+    }
+
+    function testFuzz_leBytes8RoundTrip(uint64 x) public {
+        assertEq(codec.fromLeBytes8(VaultMsgCodec.toLeBytes8(x)), x);
+    }
+
+    // Truncated and over-long copies of a valid payload must not be recognized
+    function _truncated(bytes memory payload) private pure returns (bytes memory out) {
+        out = new bytes(payload.length - 1);
+        for (uint256 i = 0; i < out.length; i++) {
+            out[i] = payload[i];
+        }
+    }
+
+    function _extended(bytes memory payload) private pure returns (bytes memory) {
+        return abi.encodePacked(payload, bytes1(0));
+    }
+}